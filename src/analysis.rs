@@ -0,0 +1,123 @@
+use crate::models::{Property, PropertyKind, PropertySize};
+use std::collections::HashMap;
+
+/// Price per m², using the plot area for land and the living area otherwise
+///
+/// Falls back to the other area if the preferred one is missing.
+pub fn price_per_sqm(property: &Property) -> Option<f64> {
+    let (preferred, other) = if property.property_type.kind == PropertyKind::Land {
+        (property.parsed_ground_size(), property.parsed_living_size())
+    } else {
+        (property.parsed_living_size(), property.parsed_ground_size())
+    };
+    let positive = |size: Option<PropertySize>| size.map(|size| size.sqm).filter(|sqm| *sqm > 0.0);
+    let size = positive(preferred).or_else(|| positive(other))?;
+    Some(property.price.euros()? / size)
+}
+
+/// Mean and standard deviation of the price per m² of each property kind
+///
+/// Kinds with fewer than 3 priced properties of known size are left out,
+/// their spread says nothing.
+pub fn price_per_sqm_stats(properties: &[Property]) -> HashMap<PropertyKind, (f64, f64)> {
+    let mut by_kind: HashMap<PropertyKind, Vec<f64>> = HashMap::new();
+    for property in properties {
+        if let Some(value) = price_per_sqm(property) {
+            by_kind.entry(property.property_type.kind).or_default().push(value);
+        }
+    }
+    by_kind
+        .into_iter()
+        .filter(|(_, values)| values.len() >= 3)
+        .map(|(kind, values)| {
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+            (kind, (mean, variance.sqrt()))
+        })
+        .collect()
+}
+
+/// Properties whose price per m² lies more than `z_threshold` standard deviations from their kind's mean
+///
+/// Returns each outlier with its z-score, the most extreme first.
+pub fn find_price_per_sqm_outliers(properties: &[Property], z_threshold: f64) -> Vec<(&Property, f64)> {
+    let stats = price_per_sqm_stats(properties);
+    let mut outliers: Vec<(&Property, f64)> = properties
+        .iter()
+        .filter_map(|property| {
+            let (mean, std_dev) = stats.get(&property.property_type.kind)?;
+            if *std_dev == 0.0 {
+                return None;
+            }
+            let z = (price_per_sqm(property)? - mean) / std_dev;
+            (z.abs() > z_threshold).then_some((property, z))
+        })
+        .collect();
+    outliers.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+    outliers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ListingType, Location, Price};
+
+    fn sale(kind: PropertyKind, price: &str) -> Property {
+        Property {
+            url: format!("https://www.vol.at/{}-{}", kind, price),
+            internal_id: None,
+            page_fingerprint: None,
+            completeness_score: None,
+            name: "Test".to_string(),
+            price: Price::from_str(price),
+            location: Location::new("Dornbirn"),
+            property_type: kind.into(),
+            listing_type: ListingType::Sold,
+            unavailable_reason: None,
+            date: None,
+            coordinates: None,
+            quality_score: None,
+            plot_polygon: None,
+            description: None,
+            features: Vec::new(),
+            driving_distance_km: None,
+            rooms: None,
+            floor_level: None,
+            construction_year: None,
+            agency_name: None,
+            agency_phone: None,
+            address: None,
+            size_living: None,
+            size_ground: None,
+            first_seen: None,
+            last_seen: None,
+        }
+    }
+
+    #[test]
+    fn test_find_price_per_sqm_outliers() {
+        let sized = |kind, price: &str, size: &str| {
+            let mut property = sale(kind, price);
+            property.url = format!("https://www.vol.at/{}-{}-{}", kind, price, size);
+            property.size_living = Some(size.to_string());
+            property
+        };
+        let mut properties: Vec<Property> = (0..10)
+            .map(|i| sized(PropertyKind::Apartment, &(400_000 + i * 10_000).to_string(), "80"))
+            .collect();
+        // Price given in thousands
+        properties.push(sized(PropertyKind::Apartment, "450", "80"));
+        // Too few houses for statistics
+        properties.push(sized(PropertyKind::House, "9000000", "100"));
+
+        let mut land = sized(PropertyKind::Land, "300000", "");
+        land.size_ground = Some("600".to_string());
+        assert_eq!(price_per_sqm(&land), Some(500.0));
+
+        let outliers = find_price_per_sqm_outliers(&properties, 2.0);
+        assert_eq!(outliers.len(), 1);
+        assert_eq!(outliers[0].0.price.raw, "450");
+        assert!(outliers[0].1 < -2.0);
+        assert!(find_price_per_sqm_outliers(&properties, 10.0).is_empty());
+    }
+}
@@ -42,15 +42,14 @@ fn main() -> Result<()> {
         true // Default to new mode when no specific options provided
     };
     
-    let options = ScrapingOptions {
-        output_file: args.output,
-        max_pages: args.max_pages,
-        max_items: args.max_items,
-        refresh_days: args.refresh,
-        new: use_new_mode,
-        cookies: None, // laendleimmo doesn't use cookies
-        debug: args.debug,
-    };
+    let options = ScrapingOptions::builder()
+        .output_file(&args.output)
+        .max_pages(args.max_pages)
+        .max_items(args.max_items)
+        .refresh_days(args.refresh)
+        .new_mode(use_new_mode)
+        .debug(args.debug)
+        .build();
     
     // Run laendleimmo.at scraper with new simplified API
     let laendleimmo_scraper = LaendleimmoScraper;
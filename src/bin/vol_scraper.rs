@@ -46,15 +46,15 @@ fn main() -> Result<()> {
         true // Default to new mode when no specific options provided
     };
     
-    let options = ScrapingOptions {
-        output_file: args.output,
-        max_pages: args.max_pages,
-        max_items: args.max_items,
-        refresh_days: args.refresh,
-        new: use_new_mode,
-        cookies: args.cookies,
-        debug: args.debug,
-    };
+    let options = ScrapingOptions::builder()
+        .output_file(&args.output)
+        .max_pages(args.max_pages)
+        .max_items(args.max_items)
+        .refresh_days(args.refresh)
+        .new_mode(use_new_mode)
+        .cookies(args.cookies)
+        .debug(args.debug)
+        .build();
     
     // Run vol.at scraper with new simplified API
     let vol_scraper = VolScraper;
@@ -1,10 +1,19 @@
-use crate::models::{Property, PropertyType, ListingType};
-use crate::tui::ScraperTUI;
-use crate::utils;
-use crate::{debug, debug_println};
-use anyhow::Result;
+use crate::models::{Coordinates, Property};
+use crate::ratelimit::TokenBucket;
+use crate::tui::{IconMode, ScraperTUI};
+use crate::utils::UserAgentRotator;
+use crate::{laendleimmo_scraper, storage, utils};
+use crate::debug::{self, PerformanceCounter};
+use crate::debug_println;
+use crate::error::ScrapeError;
+use crate::http_logging::SendLogged;
+use anyhow::{Context, Result};
 use chrono;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone)]
 pub struct ScrapingOptions {
@@ -15,6 +24,276 @@ pub struct ScrapingOptions {
     pub new: bool,
     pub cookies: Option<String>,
     pub debug: bool,
+    /// Drop scraped properties with a living size below this value (m²)
+    pub min_size_living: Option<f64>,
+    /// Drop scraped properties with a living size above this value (m²)
+    pub max_size_living: Option<f64>,
+    /// Append the URLs queued for scraping to this file, one per line
+    pub export_urls: Option<String>,
+    /// Gather URLs from listing pages but don't scrape property pages or write the database
+    pub dry_run: bool,
+    /// Sustained property page requests per second, per domain
+    pub requests_per_second: f64,
+    /// Number of requests allowed in a burst before rate limiting kicks in
+    pub burst: u32,
+    /// Maximum number of properties scraped across all scrapers of a run
+    pub total_max_items: Option<usize>,
+    /// Remaining budget of `total_max_items`, shared by all scrapers using these options
+    pub total_items_remaining: Option<Arc<AtomicUsize>>,
+    /// Only scrape property pages located in these districts (empty means all)
+    pub regions: Vec<String>,
+    /// Reference point to compute each scraped property's driving distance from
+    pub commute_from: Option<Coordinates>,
+    /// Write the output file after every N scraped properties
+    pub save_interval: usize,
+    /// User agents to rotate through in addition to the built-in pool
+    pub additional_user_agents: Vec<String>,
+    /// Custom processing of the newly scraped properties at the end of each scraper run
+    pub post_scrape_hook: Option<PostScrapeHook>,
+    /// How often a failed property page request is retried
+    pub max_retries: u8,
+    /// Per-domain replacements of `max_retries`, keyed by domain like "vol.at"
+    pub retry_overrides: HashMap<String, u8>,
+    /// Extra headers for every HTTP request, overriding `utils::DEFAULT_HTTP_HEADERS`
+    pub http_headers: Vec<(String, String)>,
+    /// Take the URLs to scrape from the platform's sitemap instead of its listing pages
+    pub bootstrap_from_sitemap: bool,
+    /// Fetch laendleimmo.at pages older than this many days again without caches
+    pub min_freshness_days: Option<u32>,
+    /// Search filters laendleimmo.at applies to its listing pages
+    pub listing_filters: laendleimmo_scraper::ListingFilters,
+    /// Prefer the RSS feed over the topic pages when gathering new vol.at URLs
+    pub use_rss: bool,
+    /// How the output file is read and written if it is a CSV file
+    pub csv: storage::CsvOptions,
+    /// How the TUI renders its status icons
+    pub icon_mode: IconMode,
+    /// Properties per row of the TUI, see `ScraperTUI::with_compact_columns`
+    pub compact_columns: usize,
+}
+
+/// `ScrapingOptionsBuilder` state before the output file was set
+#[derive(Debug, Clone, Copy)]
+pub struct NoOutputFile;
+
+/// `ScrapingOptionsBuilder` state once the output file was set, which allows `build()`
+#[derive(Debug, Clone, Copy)]
+pub struct WithOutputFile;
+
+/// Builder for `ScrapingOptions` that won't build without an output file
+///
+/// Every other option starts at its `ScrapingOptions::default()` value.
+/// Options that are optional take a value or an `Option`, so CLI arguments
+/// can be passed on as they are.
+///
+/// ```
+/// use laendlefinder::common_scraper::ScrapingOptions;
+///
+/// let options = ScrapingOptions::builder()
+///     .output_file("properties.csv")
+///     .max_pages(3)
+///     .cookies(None)
+///     .debug(true)
+///     .build();
+/// assert_eq!(options.max_pages, Some(3));
+/// ```
+///
+/// ```compile_fail
+/// use laendlefinder::common_scraper::ScrapingOptions;
+///
+/// // No output file set
+/// let options = ScrapingOptions::builder().max_pages(3).build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScrapingOptionsBuilder<State = NoOutputFile> {
+    options: ScrapingOptions,
+    state: PhantomData<State>,
+}
+
+impl ScrapingOptionsBuilder<NoOutputFile> {
+    pub fn new() -> Self {
+        ScrapingOptionsBuilder { options: ScrapingOptions::default(), state: PhantomData }
+    }
+}
+
+impl Default for ScrapingOptionsBuilder<NoOutputFile> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScrapingOptionsBuilder<WithOutputFile> {
+    pub fn build(self) -> ScrapingOptions {
+        self.options
+    }
+}
+
+impl<State> ScrapingOptionsBuilder<State> {
+    pub fn output_file(self, path: &str) -> ScrapingOptionsBuilder<WithOutputFile> {
+        let mut options = self.options;
+        options.output_file = path.to_string();
+        ScrapingOptionsBuilder { options, state: PhantomData }
+    }
+
+    pub fn max_pages(mut self, max_pages: impl Into<Option<usize>>) -> Self {
+        self.options.max_pages = max_pages.into();
+        self
+    }
+
+    pub fn max_items(mut self, max_items: impl Into<Option<usize>>) -> Self {
+        self.options.max_items = max_items.into();
+        self
+    }
+
+    pub fn refresh_days(mut self, refresh_days: impl Into<Option<u32>>) -> Self {
+        self.options.refresh_days = refresh_days.into();
+        self
+    }
+
+    /// Sets `ScrapingOptions::new`, named apart from the constructor
+    pub fn new_mode(mut self, new: bool) -> Self {
+        self.options.new = new;
+        self
+    }
+
+    pub fn cookies(mut self, cookies: impl Into<Option<String>>) -> Self {
+        self.options.cookies = cookies.into();
+        self
+    }
+
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.options.debug = debug;
+        self
+    }
+
+    pub fn min_size_living(mut self, min_size_living: impl Into<Option<f64>>) -> Self {
+        self.options.min_size_living = min_size_living.into();
+        self
+    }
+
+    pub fn max_size_living(mut self, max_size_living: impl Into<Option<f64>>) -> Self {
+        self.options.max_size_living = max_size_living.into();
+        self
+    }
+
+    pub fn export_urls(mut self, export_urls: impl Into<Option<String>>) -> Self {
+        self.options.export_urls = export_urls.into();
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.options.dry_run = dry_run;
+        self
+    }
+
+    pub fn requests_per_second(mut self, requests_per_second: f64) -> Self {
+        self.options.requests_per_second = requests_per_second;
+        self
+    }
+
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.options.burst = burst;
+        self
+    }
+
+    /// Limit the properties scraped by all scrapers together, see `ScrapingOptions::with_total_max_items`
+    pub fn total_max_items(mut self, total_max_items: impl Into<Option<usize>>) -> Self {
+        self.options = self.options.with_total_max_items(total_max_items.into());
+        self
+    }
+
+    pub fn regions(mut self, regions: Vec<String>) -> Self {
+        self.options.regions = regions;
+        self
+    }
+
+    pub fn commute_from(mut self, commute_from: impl Into<Option<Coordinates>>) -> Self {
+        self.options.commute_from = commute_from.into();
+        self
+    }
+
+    pub fn save_interval(mut self, save_interval: usize) -> Self {
+        self.options.save_interval = save_interval;
+        self
+    }
+
+    pub fn additional_user_agents(mut self, additional_user_agents: Vec<String>) -> Self {
+        self.options.additional_user_agents = additional_user_agents;
+        self
+    }
+
+    pub fn post_scrape_hook(mut self, post_scrape_hook: impl Into<Option<PostScrapeHook>>) -> Self {
+        self.options.post_scrape_hook = post_scrape_hook.into();
+        self
+    }
+
+    pub fn max_retries(mut self, max_retries: u8) -> Self {
+        self.options.max_retries = max_retries;
+        self
+    }
+
+    pub fn retry_overrides(mut self, retry_overrides: HashMap<String, u8>) -> Self {
+        self.options.retry_overrides = retry_overrides;
+        self
+    }
+
+    pub fn http_headers(mut self, http_headers: Vec<(String, String)>) -> Self {
+        self.options.http_headers = http_headers;
+        self
+    }
+
+    pub fn bootstrap_from_sitemap(mut self, bootstrap_from_sitemap: bool) -> Self {
+        self.options.bootstrap_from_sitemap = bootstrap_from_sitemap;
+        self
+    }
+
+    pub fn min_freshness_days(mut self, min_freshness_days: impl Into<Option<u32>>) -> Self {
+        self.options.min_freshness_days = min_freshness_days.into();
+        self
+    }
+
+    pub fn listing_filters(mut self, listing_filters: laendleimmo_scraper::ListingFilters) -> Self {
+        self.options.listing_filters = listing_filters;
+        self
+    }
+
+    pub fn use_rss(mut self, use_rss: bool) -> Self {
+        self.options.use_rss = use_rss;
+        self
+    }
+
+    pub fn csv(mut self, csv: storage::CsvOptions) -> Self {
+        self.options.csv = csv;
+        self
+    }
+
+    pub fn icon_mode(mut self, icon_mode: IconMode) -> Self {
+        self.options.icon_mode = icon_mode;
+        self
+    }
+
+    pub fn compact_columns(mut self, compact_columns: usize) -> Self {
+        self.options.compact_columns = compact_columns;
+        self
+    }
+}
+
+type PostScrapeFn = dyn Fn(&[Property]) -> Result<()> + Send + Sync;
+
+/// Callback run with the properties a scraper run has newly scraped
+#[derive(Clone)]
+pub struct PostScrapeHook(pub Arc<PostScrapeFn>);
+
+impl PostScrapeHook {
+    pub fn new(hook: impl Fn(&[Property]) -> Result<()> + Send + Sync + 'static) -> Self {
+        PostScrapeHook(Arc::new(hook))
+    }
+}
+
+impl std::fmt::Debug for PostScrapeHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PostScrapeHook")
+    }
 }
 
 impl Default for ScrapingOptions {
@@ -27,27 +306,319 @@ impl Default for ScrapingOptions {
             new: true,
             cookies: None,
             debug: false,
+            min_size_living: None,
+            max_size_living: None,
+            export_urls: None,
+            dry_run: false,
+            requests_per_second: 0.5,
+            burst: 1,
+            total_max_items: None,
+            total_items_remaining: None,
+            regions: Vec::new(),
+            commute_from: None,
+            save_interval: 1,
+            additional_user_agents: Vec::new(),
+            post_scrape_hook: None,
+            max_retries: 0,
+            retry_overrides: HashMap::new(),
+            http_headers: Vec::new(),
+            bootstrap_from_sitemap: false,
+            min_freshness_days: None,
+            listing_filters: laendleimmo_scraper::ListingFilters::default(),
+            use_rss: false,
+            csv: storage::CsvOptions::default(),
+            icon_mode: IconMode::Unicode,
+            compact_columns: 1,
         }
     }
 }
 
+impl ScrapingOptions {
+    /// Start building options; only the output file has to be set
+    pub fn builder() -> ScrapingOptionsBuilder<NoOutputFile> {
+        ScrapingOptionsBuilder::new()
+    }
+
+    /// Set up a budget of `total_max_items` shared by every scraper run with these options
+    pub fn with_total_max_items(mut self, total_max_items: Option<usize>) -> Self {
+        self.total_max_items = total_max_items;
+        self.total_items_remaining = total_max_items.map(|n| Arc::new(AtomicUsize::new(n)));
+        self
+    }
+
+    /// Number of items left in the shared budget, None if unlimited
+    pub fn remaining_total_items(&self) -> Option<usize> {
+        self.total_items_remaining
+            .as_ref()
+            .map(|remaining| remaining.load(Ordering::SeqCst))
+    }
+
+    /// True once all scrapers together have scraped `total_max_items` properties
+    pub fn total_items_exhausted(&self) -> bool {
+        self.remaining_total_items() == Some(0)
+    }
+
+    /// Count one scraped property against the shared budget
+    fn consume_total_item(&self) {
+        if let Some(remaining) = &self.total_items_remaining {
+            let _ = remaining.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+        }
+    }
+
+    /// Set the driving distance from `commute_from` if the property has coordinates but no distance yet
+    ///
+    /// The OSRM request waits for its rate limiter in `context`.
+    pub fn annotate_commute(&self, context: &mut ScrapingContext, property: &mut Property) {
+        let (Some(from), Some(to)) = (self.commute_from, property.coordinates) else {
+            return;
+        };
+        if property.driving_distance_km.is_some() {
+            return;
+        }
+        context.wait_for_domain(utils::domain_from_url(utils::OSRM_ROUTE_URL));
+        match utils::compute_driving_distance((from.lat, from.lng), (to.lat, to.lng)) {
+            Ok(km) => property.driving_distance_km = Some((km * 10.0).round() / 10.0),
+            Err(e) => debug_println!("Failed to compute driving distance for {}: {}", property.url, e),
+        }
+    }
+
+    /// Number of retries for a URL, using the override of its domain if there is one
+    pub fn max_retries_for(&self, url: &str) -> u8 {
+        let domain = utils::domain_from_url(url);
+        self.retry_overrides
+            .iter()
+            .find(|(key, _)| domain == key.as_str() || domain.ends_with(&format!(".{}", key)))
+            .map_or(self.max_retries, |(_, retries)| *retries)
+    }
+
+    /// Check whether a scraped property satisfies the living size constraints
+    ///
+    /// Properties without a parseable living size are kept, since the size is
+    /// frequently missing (e.g. for land) and we can't tell if it would match.
+    pub fn matches_size_living(&self, property: &Property) -> bool {
+        let size = match property.parsed_living_size().map(|size| size.sqm) {
+            Some(size) => size,
+            None => return true,
+        };
+        self.min_size_living.map_or(true, |min| size >= min)
+            && self.max_size_living.map_or(true, |max| size <= max)
+    }
+}
+
+/// Outcome of one scraper's run, used for the combined summary
+#[derive(Debug, Clone)]
+pub struct ScrapingRun {
+    pub scraper_name: String,
+    pub new_scraped: usize,
+    /// How many of the scraped properties were already stored
+    pub updated: usize,
+    pub failed: usize,
+    pub known: usize,
+    pub duration: Duration,
+}
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static CATCHING_INTERRUPTS: AtomicBool = AtomicBool::new(false);
+
+/// Stop scraping gracefully on Ctrl-C or SIGTERM so buffered results get saved
+///
+/// While a scrape loop runs, the current request is finished and unsaved
+/// properties are flushed before the run ends with an error. Outside of it,
+/// and on a second signal, the process exits immediately as without a handler.
+pub fn install_interrupt_handler() -> Result<()> {
+    ctrlc::set_handler(|| {
+        if !CATCHING_INTERRUPTS.load(Ordering::SeqCst) || INTERRUPTED.swap(true, Ordering::SeqCst) {
+            std::process::exit(130);
+        }
+        eprintln!("\nInterrupted, saving scraped properties...");
+    })?;
+    Ok(())
+}
+
+/// Interrupts are left to the loop checking `is_interrupted` while this is alive
+struct CatchInterrupts;
+
+impl CatchInterrupts {
+    fn new() -> Self {
+        CATCHING_INTERRUPTS.store(true, Ordering::SeqCst);
+        CatchInterrupts
+    }
+}
+
+impl Drop for CatchInterrupts {
+    fn drop(&mut self) {
+        CATCHING_INTERRUPTS.store(false, Ordering::SeqCst);
+    }
+}
+
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// The public OSRM demo server allows one request per second, whatever the scraping rate
+const OSRM_REQUESTS_PER_SECOND: f64 = 1.0;
+
+/// Number of most recently used user agents that are not picked again
+const USER_AGENT_NO_REPEAT: usize = 5;
+
+/// Mutable state shared across the requests of a scraping run
+pub struct ScrapingContext {
+    rate_limiters: HashMap<String, TokenBucket>,
+    requests_per_second: f64,
+    burst: u32,
+    user_agents: UserAgentRotator,
+    performance: PerformanceCounter,
+    paywalled_urls: Vec<String>,
+    min_freshness_days: Option<u32>,
+    http_headers: Vec<(String, String)>,
+}
+
+impl ScrapingContext {
+    pub fn new(options: &ScrapingOptions) -> Self {
+        let osrm_domain = utils::domain_from_url(utils::OSRM_ROUTE_URL).to_string();
+        Self {
+            rate_limiters: HashMap::from([(osrm_domain, TokenBucket::new(1.0, OSRM_REQUESTS_PER_SECOND))]),
+            requests_per_second: options.requests_per_second,
+            burst: options.burst.max(1),
+            user_agents: UserAgentRotator::with_additional(&options.additional_user_agents, USER_AGENT_NO_REPEAT),
+            performance: PerformanceCounter::new(),
+            paywalled_urls: Vec::new(),
+            min_freshness_days: options.min_freshness_days,
+            http_headers: options.http_headers.clone(),
+        }
+    }
+
+    /// Client builder for requests to the scraped sites, sending the configured `http_headers`
+    pub fn http_client_builder(&self) -> reqwest::blocking::ClientBuilder {
+        utils::http_client_builder(&self.http_headers)
+    }
+
+    /// Age in days after which a page expected to be fresh is fetched again without caches
+    pub fn min_freshness_days(&self) -> Option<u32> {
+        self.min_freshness_days
+    }
+
+    /// URLs skipped because their article needs a subscription
+    pub fn paywalled_urls(&self) -> &[String] {
+        &self.paywalled_urls
+    }
+
+    /// Outcomes of the requests made with this context, recorded by `SendLogged::send_counted`
+    pub fn performance(&self) -> &PerformanceCounter {
+        &self.performance
+    }
+
+    /// User agent for the next property page request
+    pub fn next_user_agent(&mut self) -> &str {
+        self.user_agents.next()
+    }
+
+    fn bucket_for_domain(&mut self, domain: &str) -> &mut TokenBucket {
+        let (burst, rate) = (self.burst as f64, self.requests_per_second);
+        self.rate_limiters
+            .entry(domain.to_string())
+            .or_insert_with(|| TokenBucket::new(burst, rate))
+    }
+
+    /// How long the next request to the given domain has to wait
+    pub fn delay_for_domain(&mut self, domain: &str) -> Duration {
+        self.bucket_for_domain(domain).time_until_token()
+    }
+
+    /// Wait until the rate limiter of the given domain allows another request
+    pub fn wait_for_domain(&mut self, domain: &str) {
+        self.bucket_for_domain(domain).wait_for_token();
+    }
+}
+
 pub trait PlatformScraper {
     fn base_url(&self) -> &str;
+    /// Gather property URLs from the first `options.max_pages` listing pages
     fn scrape_listings(
         &self,
-        max_pages: Option<usize>,
+        options: &ScrapingOptions,
+        context: &mut ScrapingContext,
         tui: Option<&mut ScraperTUI>,
         existing_urls: &HashSet<String>,
     ) -> Result<Vec<String>>;
     fn scrape_new_urls(
         &self,
+        options: &ScrapingOptions,
+        context: &mut ScrapingContext,
         tui: Option<&mut ScraperTUI>,
         existing_urls: &HashSet<String>,
     ) -> Result<Vec<String>>;
-    fn scrape_property(&self, url: &str, cookies: Option<&str>) -> Result<Property>;
+    /// Scrape a property page with the next user agent and the HTTP headers of `context`
+    fn scrape_property(&self, context: &mut ScrapingContext, url: &str, cookies: Option<&str>) -> Result<Property>;
+    /// Scrape a property page unless its `utils::page_fingerprint` is still the one of `stored`
+    ///
+    /// Returns None for an unchanged page. Platforms that can't fingerprint a page
+    /// before parsing it always scrape. Requests beyond the first have to wait
+    /// for the rate limiter of `context`.
+    fn scrape_property_if_changed(
+        &self,
+        context: &mut ScrapingContext,
+        url: &str,
+        cookies: Option<&str>,
+        _stored: Option<&Property>,
+    ) -> Result<Option<Property>> {
+        self.scrape_property(context, url, cookies).map(Some)
+    }
+    /// Whether a discovered URL lies in one of the requested regions
+    ///
+    /// Platforms whose URLs don't encode a region keep every URL.
+    fn url_matches_regions(&self, _url: &str, _regions: &[String]) -> bool {
+        true
+    }
+    /// Sitemaps tried in order by `--bootstrap-from-sitemap`
+    fn sitemap_urls(&self) -> Vec<String> {
+        vec![
+            format!("https://www.{}/sitemap.xml", self.base_url()),
+            format!("https://www.{}/sitemap_index.xml", self.base_url()),
+        ]
+    }
+    /// Whether a URL from the platform's sitemap is a property page
+    fn is_property_url(&self, url: &str) -> bool {
+        url.contains(self.base_url())
+    }
+    /// Check that the platform responds successfully within `HEALTH_CHECK_TIMEOUT`
+    fn health_check(&self, context: &mut ScrapingContext) -> Result<()> {
+        fetch_for_health_check(context, &format!("https://www.{}/", self.base_url())).map(|_| ())
+    }
 }
 
-pub fn scrape_single_url<T: PlatformScraper>(
+/// Slowest acceptable response of a health check
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Fetch a page for a health check, failing on non-2xx statuses and slow responses
+pub fn fetch_for_health_check(context: &mut ScrapingContext, url: &str) -> Result<String> {
+    let started = Instant::now();
+    let response = context
+        .http_client_builder()
+        .user_agent(context.next_user_agent())
+        .timeout(HEALTH_CHECK_TIMEOUT * 2)
+        .build()?
+        .get(url)
+        .send_counted(context.performance())
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow::anyhow!("{} returned HTTP status {}", url, status));
+    }
+    let body = response.text().with_context(|| format!("Failed to read {}", url))?;
+    let elapsed = started.elapsed();
+    if elapsed > HEALTH_CHECK_TIMEOUT {
+        return Err(anyhow::anyhow!(
+            "{} took {}ms, more than the {}s limit",
+            url,
+            elapsed.as_millis(),
+            HEALTH_CHECK_TIMEOUT.as_secs()
+        ));
+    }
+    Ok(body)
+}
+
+pub fn scrape_single_url<T: PlatformScraper + ?Sized>(
     scraper: &T,
     url: &str,
     options: &ScrapingOptions,
@@ -55,124 +626,41 @@ pub fn scrape_single_url<T: PlatformScraper>(
     // Set global debug flag
     debug::set_debug(options.debug);
 
-    let mut tui = ScraperTUI::new();
+    let mut tui = ScraperTUI::for_options(options);
+    let mut context = ScrapingContext::new(options);
 
     // 1. Load all existing properties
-    let mut all_properties = utils::load_properties_from_csv(&options.output_file)?;
+    let mut all_properties = storage::load_properties(&options.output_file, options.csv)?;
     tui.show_summary(all_properties.len())?;
 
-    // 2. Find existing entry position for in-place update (check both URL and property ID)
-    let mut existing_position = all_properties.iter().position(|p| p.url == url);
-    
-    // If not found by URL, check by property ID for laendleimmo URLs
-    if existing_position.is_none() {
-        if let Some(property_id) = utils::extract_property_id(url) {
-            if url.contains("laendleimmo.at") {
-                existing_position = all_properties.iter().position(|p| {
-                    if let Some(existing_id) = utils::extract_property_id(&p.url) {
-                        existing_id == property_id && p.url.contains("laendleimmo.at")
-                    } else {
-                        false
-                    }
-                });
-                if let Some(pos) = existing_position {
-                    debug_println!("Found existing entry by property ID {} at position {}: existing URL {} vs new URL {}", 
-                                 property_id, pos, all_properties[pos].url, url);
-                }
-            }
-        }
-    }
-    
-    if let Some(pos) = existing_position {
-        debug_println!("Found existing entry at position {} for URL: {}", pos, url);
-    }
-
-    // 3. Scrape the specific URL
+    // 2. Scrape the specific URL
     tui.add_property(url.to_string())?;
     tui.start_scraping_property(url)?;
 
     let mut failed_urls = Vec::new();
     
-    match scraper.scrape_property(url, options.cookies.as_deref()) {
-        Ok(property) => {
-            // Handle single property update in-place to preserve order
-            let merged_property = if let Some(pos) = existing_position {
-                let existing = &all_properties[pos];
-                
-                // Determine which URL to use - prefer the more recent or more specific one
-                let final_url = if property.url != existing.url {
-                    // URLs are different - this happens when property type classification changes
-                    // Use the new URL if it has better classification or if property is becoming available again
-                    if property.listing_type == ListingType::Available || 
-                       (property.property_type != PropertyType::Unknown && existing.property_type == PropertyType::Unknown) {
-                        debug_println!("Updating URL from {} to {} (better classification or became available)", 
-                                     existing.url, property.url);
-                        property.url.clone()
-                    } else {
-                        existing.url.clone()
-                    }
-                } else {
-                    property.url.clone()
-                };
-                
-                // Property already exists, merge the data intelligently
-                if property.listing_type == ListingType::Unavailable && existing.listing_type != ListingType::Unavailable {
-                    // Property became unavailable - preserve all existing data except status and dates
-                    debug_println!("Property became unavailable, preserving existing data: {}", final_url);
-                    Property {
-                        url: final_url,
-                        name: if existing.name != "Unknown Property" && existing.name != "Unavailable Property" { existing.name.clone() } else { property.name },
-                        price: existing.price.clone(), // Always preserve existing price when becoming unavailable
-                        location: if existing.location != "Unknown" { existing.location.clone() } else { property.location },
-                        property_type: if existing.property_type != PropertyType::Unknown { existing.property_type.clone() } else { property.property_type },
-                        listing_type: property.listing_type, // Update to unavailable
-                        date: existing.date.or(property.date), // Preserve original listing date
-                        coordinates: existing.coordinates.or(property.coordinates),
-                        address: existing.address.clone().or(property.address),
-                        size_living: existing.size_living.clone().or(property.size_living),
-                        size_ground: existing.size_ground.clone().or(property.size_ground),
-                        // Keep the earliest first_seen date
-                        first_seen: existing.first_seen.or(property.first_seen),
-                        // Preserve existing last_seen since property became unavailable
-                        last_seen: existing.last_seen.or(property.last_seen),
-                    }
-                } else {
-                    // Normal property update - use new data but preserve existing data when scraper fails
-                    Property {
-                        url: final_url,
-                        name: if property.name.is_empty() || property.name == "Unknown Property" || property.name == "Unavailable Property" { existing.name.clone() } else { property.name },
-                        price: if property.price.is_empty() || property.price == "Unknown" || property.price == "Unavailable" { existing.price.clone() } else { property.price },
-                        location: if property.location.is_empty() || property.location == "Unknown" { existing.location.clone() } else { property.location },
-                        property_type: if property.property_type == PropertyType::Unknown { existing.property_type.clone() } else { property.property_type },
-                        listing_type: property.listing_type, // Always update listing status
-                        date: property.date.or(existing.date),
-                        coordinates: property.coordinates.or(existing.coordinates),
-                        address: property.address.or(existing.address.clone()),
-                        size_living: property.size_living.or(existing.size_living.clone()),
-                        size_ground: property.size_ground.or(existing.size_ground.clone()),
-                        // Keep the earliest first_seen date
-                        first_seen: existing.first_seen.or(property.first_seen),
-                        // Use the latest last_seen date
-                        last_seen: property.last_seen.or(existing.last_seen),
-                    }
-                }
-            } else {
-                // New property, just use it as-is
-                property
-            };
-            
-            // Update in-place or add at end for new properties
+    match scraper.scrape_property(&mut context, url, options.cookies.as_deref()) {
+        Ok(mut property) => {
+            options.annotate_commute(&mut context, &mut property);
+            // The internal ID survives URL changes, so prefer it over the URL to find the stored entry
+            let existing_position = property
+                .internal_id
+                .as_ref()
+                .and_then(|id| all_properties.iter().position(|p| p.internal_id.as_ref() == Some(id)))
+                .or_else(|| all_properties.iter().position(|p| p.url == property.url));
+            // Merge into the existing entry in-place to preserve order, or add at the end
             if let Some(pos) = existing_position {
-                all_properties[pos] = merged_property;
+                debug_println!("Found existing entry at position {} for URL: {}", pos, url);
+                all_properties[pos].merge_with(property);
             } else {
-                all_properties.push(merged_property);
+                all_properties.push(property);
             }
             
             tui.complete_property(url)?;
             debug_println!("Successfully scraped and updated: {}", url);
             
             // Save immediately after successful scrape
-            utils::save_properties_to_csv(&all_properties, &options.output_file)?;
+            storage::save_properties(&all_properties, &options.output_file, options.csv)?;
             
             // Show final summary
             tui.show_final_summary(1, all_properties.len())?;
@@ -200,14 +688,27 @@ pub fn scrape_single_url<T: PlatformScraper>(
 pub fn run_scraper_with_options<T: PlatformScraper>(
     scraper: &T,
     options: &ScrapingOptions,
-) -> Result<()> {
+) -> Result<ScrapingRun> {
     // Set global debug flag
     debug::set_debug(options.debug);
 
-    let mut tui = ScraperTUI::new();
+    let started = Instant::now();
+    let finish = |new_scraped: usize, failed: usize, known: usize| ScrapingRun {
+        scraper_name: scraper.base_url().to_string(),
+        new_scraped,
+        updated: 0,
+        failed,
+        known,
+        duration: started.elapsed(),
+    };
+    let mut known = 0;
+
+    let mut tui = ScraperTUI::for_options(options);
+    let mut context = ScrapingContext::new(options);
+    tui.set_title(&format!("{} Scraper", scraper.base_url()))?;
 
     // 1. Load all existing properties
-    let mut all_properties = utils::load_properties_from_csv(&options.output_file)?;
+    let mut all_properties = storage::load_properties(&options.output_file, options.csv)?;
     tui.show_summary(all_properties.len())?;
 
     let relevant_urls: Vec<String> = all_properties
@@ -221,63 +722,59 @@ pub fn run_scraper_with_options<T: PlatformScraper>(
 
     let urls_to_scrape = if let Some(refresh_days) = options.refresh_days {
         // In refresh mode, filter and prioritize properties older than N days
-        let refresh_days = refresh_days.max(1); // Default to 1 day minimum
-        let cutoff_date = chrono::Utc::now().naive_utc().date() - chrono::Duration::days(refresh_days as i64);
-        
-        let mut relevant_properties: Vec<&Property> = all_properties
-            .iter()
-            .filter(|x| {
-                // Filter by platform URL
-                if !x.url.contains(scraper.base_url()) {
-                    return false;
-                }
-                // Only refresh available properties - no point in refreshing unavailable or sold properties
-                if x.listing_type != ListingType::Available {
-                    return false;
-                }
-                // Filter by age - include properties without last_seen or with old last_seen
-                match x.last_seen {
-                    None => true, // Properties without last_seen should be refreshed
-                    Some(last_seen) => last_seen <= cutoff_date, // Properties older than cutoff
-                }
-            })
-            .collect();
-            
-        if relevant_properties.is_empty() {
-            debug_println!("Refresh mode: no properties older than {} days found", refresh_days);
+        let prioritized_urls = urls_due_for_refresh(
+            all_properties.iter().filter(|x| x.url.contains(scraper.base_url())),
+            refresh_days,
+        );
+
+        if prioritized_urls.is_empty() {
             tui.update_listing_status(0, 0)?;
-            return Ok(());
+            return Ok(finish(0, 0, 0));
         }
-        
-        // Sort by main property date (oldest first), then by first_seen for properties without date
-        relevant_properties.sort_by(|a, b| {
-            match (a.date, b.date) {
-                (Some(a_date), Some(b_date)) => a_date.cmp(&b_date), // oldest first
-                (None, Some(_)) => std::cmp::Ordering::Less, // properties without date come first
-                (Some(_), None) => std::cmp::Ordering::Greater,
-                (None, None) => a.first_seen.cmp(&b.first_seen), // fallback to first_seen
+
+        tui.update_listing_status_refresh(0, prioritized_urls.len())?;
+        prioritized_urls
+    } else if options.bootstrap_from_sitemap {
+        // Sitemap mode: take all property URLs from the first sitemap that loads
+        let existing_urls: HashSet<String> = relevant_urls.into_iter().collect();
+
+        let mut sitemap_urls = None;
+        for sitemap_url in scraper.sitemap_urls() {
+            match utils::load_urls_from_sitemap(&mut context, &sitemap_url) {
+                Ok(urls) => {
+                    sitemap_urls = Some(urls);
+                    break;
+                }
+                Err(e) => debug_println!("Sitemap {} not usable: {:#}", sitemap_url, e),
             }
-        });
-        
-        let prioritized_urls: Vec<String> = relevant_properties
+        }
+        let found_urls: Vec<String> = sitemap_urls
+            .ok_or_else(|| anyhow::anyhow!("No sitemap found for {}", scraper.base_url()))?
             .into_iter()
-            .map(|p| p.url.clone())
+            .filter(|url| scraper.is_property_url(url))
             .collect();
-            
-        debug_println!("Refresh mode: found {} properties older than {} days (cutoff: {})", 
-                      prioritized_urls.len(), refresh_days, cutoff_date);
-        tui.update_listing_status_refresh(0, prioritized_urls.len())?;
-        prioritized_urls
+
+        let (known_urls, new_urls): (Vec<String>, Vec<String>) =
+            found_urls.into_iter().partition(|url| existing_urls.contains(url));
+
+        tui.update_listing_status(new_urls.len(), known_urls.len())?;
+        known = known_urls.len();
+
+        if new_urls.is_empty() {
+            return Ok(finish(0, 0, known));
+        }
+
+        new_urls
     } else if options.new {
         // New mode: gather new links until no new ones found in 5 consecutive pages
         // Create a set of existing URLs for fast lookup
         let existing_urls: HashSet<String> = relevant_urls.into_iter().collect();
 
-        let found_urls = scraper.scrape_new_urls(Some(&mut tui), &existing_urls)?;
+        let found_urls = scraper.scrape_new_urls(options, &mut context, Some(&mut tui), &existing_urls)?;
 
         if found_urls.is_empty() {
             tui.update_listing_status(0, 0)?;
-            return Ok(());
+            return Ok(finish(0, 0, 0));
         }
 
         // Update last_seen for existing properties that were found in listings
@@ -292,9 +789,9 @@ pub fn run_scraper_with_options<T: PlatformScraper>(
         }
         
         // Save updated properties if any last_seen dates were updated
-        if updated_count > 0 {
-            let deduplicated = deduplicate_properties_by_url(all_properties.clone());
-            utils::save_properties_to_csv(&deduplicated, &options.output_file)?;
+        if updated_count > 0 && !options.dry_run {
+            let deduplicated = deduplicate_properties_by_internal_id(all_properties.clone());
+            storage::save_properties(&deduplicated, &options.output_file, options.csv)?;
         }
 
         // Filter out existing URLs in normal mode
@@ -310,9 +807,10 @@ pub fn run_scraper_with_options<T: PlatformScraper>(
         }
 
         tui.update_listing_status(new_urls.len(), known_count)?;
+        known = known_count;
 
         if new_urls.is_empty() {
-            return Ok(());
+            return Ok(finish(0, 0, known));
         }
 
         new_urls
@@ -321,11 +819,11 @@ pub fn run_scraper_with_options<T: PlatformScraper>(
         // Create a set of existing URLs for fast lookup
         let existing_urls: HashSet<String> = relevant_urls.into_iter().collect();
 
-        let found_urls = scraper.scrape_listings(options.max_pages, Some(&mut tui), &existing_urls)?;
+        let found_urls = scraper.scrape_listings(options, &mut context, Some(&mut tui), &existing_urls)?;
 
         if found_urls.is_empty() {
             tui.update_listing_status(0, 0)?;
-            return Ok(());
+            return Ok(finish(0, 0, 0));
         }
 
         // Update last_seen for existing properties that were found in listings
@@ -340,9 +838,9 @@ pub fn run_scraper_with_options<T: PlatformScraper>(
         }
         
         // Save updated properties if any last_seen dates were updated
-        if updated_count > 0 {
-            let deduplicated = deduplicate_properties_by_url(all_properties.clone());
-            utils::save_properties_to_csv(&deduplicated, &options.output_file)?;
+        if updated_count > 0 && !options.dry_run {
+            let deduplicated = deduplicate_properties_by_internal_id(all_properties.clone());
+            storage::save_properties(&deduplicated, &options.output_file, options.csv)?;
         }
 
         // Filter out existing URLs in normal mode
@@ -358,24 +856,52 @@ pub fn run_scraper_with_options<T: PlatformScraper>(
         }
 
         tui.update_listing_status(new_urls.len(), known_count)?;
+        known = known_count;
 
         if new_urls.is_empty() {
-            return Ok(());
+            return Ok(finish(0, 0, known));
         }
 
         new_urls
     };
 
-    // Apply max_items limit if specified
-    let urls_to_scrape = if let Some(max_items) = options.max_items {
+    // Drop URLs outside the requested regions before loading their pages
+    let urls_to_scrape = if options.regions.is_empty() {
         urls_to_scrape
+    } else {
+        let before = urls_to_scrape.len();
+        let in_region: Vec<String> = urls_to_scrape
             .into_iter()
-            .take(max_items)
+            .filter(|url| scraper.url_matches_regions(url, &options.regions))
+            .collect();
+        tui.show_region_filtered(before - in_region.len())?;
+        in_region
+    };
+
+    // Apply max_items limit and the budget shared with other scrapers, if specified
+    let limit = match (options.max_items, options.remaining_total_items()) {
+        (Some(max_items), Some(remaining)) => Some(max_items.min(remaining)),
+        (max_items, remaining) => max_items.or(remaining),
+    };
+    let urls_to_scrape = if let Some(limit) = limit {
+        urls_to_scrape
+            .into_iter()
+            .take(limit)
             .collect::<Vec<_>>()
     } else {
         urls_to_scrape
     };
 
+    if let Some(export_path) = &options.export_urls {
+        utils::export_urls(&urls_to_scrape, export_path)?;
+        debug_println!("Exported {} URLs to {}", urls_to_scrape.len(), export_path);
+    }
+
+    if options.dry_run {
+        tui.show_dry_run(urls_to_scrape.len())?;
+        return Ok(finish(0, 0, known));
+    }
+
     // Add all properties to TUI as pending
     for url in &urls_to_scrape {
         tui.add_property(url.clone())?;
@@ -385,24 +911,325 @@ pub fn run_scraper_with_options<T: PlatformScraper>(
     tui.show_initial_progress_bar()?;
 
     // Scrape the selected URLs
+    let (newly_scraped, failed_urls) =
+        scrape_urls(&urls_to_scrape, &mut all_properties, options, &mut tui, &mut context, |_| Some(scraper))?;
+
+    // Final cleanup and summary (properties already saved after each scrape)
+    let scraped_count = newly_scraped.len();
+    
+    // Calculate final totals for summary
+    let mut final_properties = all_properties.clone();
+    final_properties.extend(newly_scraped.clone());
+    
+    let deduplicated_properties = deduplicate_properties_by_internal_id(final_properties);
+
+    // Show final summary
+    tui.show_final_summary(scraped_count, deduplicated_properties.len())?;
+
+    // Show failure report if there were any failures
+    tui.show_failure_report(&failed_urls)?;
+    print_paywall_report(&context, options);
+    print_performance_summary(&context);
+
+    if let Some(PostScrapeHook(hook)) = &options.post_scrape_hook {
+        hook(&newly_scraped).context("Post-scrape hook failed")?;
+    }
+
+    let stored_urls: HashSet<&str> = all_properties.iter().map(|p| p.url.as_str()).collect();
+    let updated = newly_scraped.iter().filter(|p| stored_urls.contains(p.url.as_str())).count();
+    Ok(ScrapingRun { updated, ..finish(scraped_count, failed_urls.len(), known) })
+}
+
+/// Compute the missing driving distances of stored properties from `options.commute_from`
+///
+/// Properties scraped before `commute_from` was set have coordinates but no
+/// distance. OSRM requests wait for its rate limiter. Returns how many
+/// distances were added.
+pub fn backfill_driving_distances(properties: &mut [Property], options: &ScrapingOptions) -> usize {
+    let mut context = ScrapingContext::new(options);
+    let mut added = 0;
+    for property in properties.iter_mut().filter(|p| p.driving_distance_km.is_none()) {
+        options.annotate_commute(&mut context, property);
+        if property.driving_distance_km.is_some() {
+            added += 1;
+        }
+    }
+    added
+}
+
+/// Scrape one current listing of a platform to check the scraper still works
+///
+/// Runs the platform's health check first. The test URL is the first property
+/// found on the platform's first listing page, so it always exists. Returns
+/// the scraped URL, or why the check failed. Only the cookies and HTTP headers
+/// of `options` are used.
+pub fn test_scraper(scraper: &dyn PlatformScraper, options: &ScrapingOptions) -> std::result::Result<String, String> {
+    let options = ScrapingOptions::builder()
+        .output_file(&options.output_file)
+        .max_pages(1)
+        .cookies(options.cookies.clone())
+        .http_headers(options.http_headers.clone())
+        .build();
+    let mut context = ScrapingContext::new(&options);
+    scraper
+        .health_check(&mut context)
+        .map_err(|e| format!("health check failed: {:#}", e))?;
+    let urls = scraper
+        .scrape_listings(&options, &mut context, None, &HashSet::new())
+        .map_err(|e| format!("listing page failed: {}", e))?;
+    let url = urls.first().ok_or("no properties found on the listing page")?;
+
+    let property = scraper
+        .scrape_property(&mut context, url, options.cookies.as_deref())
+        .map_err(|e| format!("{}: {}", url, e))?;
+
+    let problems = check_scraped_property(&property);
+    if problems.is_empty() {
+        Ok(url.clone())
+    } else {
+        Err(format!("{}: {}", url, problems.join(", ")))
+    }
+}
+
+/// Fields of a freshly scraped property that look wrong, suggesting a broken scraper
+pub fn check_scraped_property(property: &Property) -> Vec<String> {
+    let mut problems = Vec::new();
+    if property.name.trim().is_empty() {
+        problems.push("empty name".to_string());
+    }
+    if property.price.euros().is_none() && !property.is_unavailable() {
+        problems.push(format!("unparseable price '{}'", property.price));
+    }
+    if property.location.is_unknown() {
+        problems.push("unknown location".to_string());
+    }
+    if property.coordinates.is_some_and(|c| !c.is_in_vorarlberg()) {
+        problems.push("coordinates outside Vorarlberg".to_string());
+    }
+    problems
+}
+
+/// URLs of available properties not seen for `refresh_days` days, oldest listing first
+pub fn urls_due_for_refresh<'a>(
+    properties: impl Iterator<Item = &'a Property>,
+    refresh_days: u32,
+) -> Vec<String> {
+    let refresh_days = refresh_days.max(1); // Default to 1 day minimum
+    let cutoff_date = chrono::Utc::now().naive_utc().date() - chrono::Duration::days(refresh_days as i64);
+
+    let mut relevant_properties: Vec<&Property> = properties
+        .filter(|x| {
+            // Only refresh available properties - no point in refreshing unavailable or sold properties
+            if !x.is_available() {
+                return false;
+            }
+            // Filter by age - include properties without last_seen or with old last_seen
+            match x.last_seen {
+                None => true, // Properties without last_seen should be refreshed
+                Some(last_seen) => last_seen <= cutoff_date, // Properties older than cutoff
+            }
+        })
+        .collect();
+
+    // Sort by main property date (oldest first), then by first_seen for properties without date
+    relevant_properties.sort_by(|a, b| {
+        match (a.date, b.date) {
+            (Some(a_date), Some(b_date)) => a_date.cmp(&b_date), // oldest first
+            (None, Some(_)) => std::cmp::Ordering::Less, // properties without date come first
+            (Some(_), None) => std::cmp::Ordering::Greater,
+            (None, None) => a.first_seen.cmp(&b.first_seen), // fallback to first_seen
+        }
+    });
+
+    debug_println!("Refresh mode: found {} properties older than {} days (cutoff: {})",
+                  relevant_properties.len(), refresh_days, cutoff_date);
+    relevant_properties.into_iter().map(|p| p.url.clone()).collect()
+}
+
+/// Re-scrape the properties of an existing CSV file, skipping listing discovery
+///
+/// With `refresh_days` set only properties due for a refresh are scraped.
+/// Results are merged into the output file in place.
+pub fn scrape_input_csv(input_csv: &str, options: &ScrapingOptions) -> Result<ScrapingRun> {
+    // Loading a missing file yields no properties, which would hide a mistyped path
+    if !std::path::Path::new(input_csv).exists() {
+        return Err(anyhow::anyhow!("Input CSV not found: {}", input_csv));
+    }
+    let properties = storage::load_properties(input_csv, options.csv)?;
+    let urls = match options.refresh_days {
+        Some(refresh_days) => urls_due_for_refresh(properties.iter(), refresh_days),
+        None => properties.into_iter().map(|p| p.url).collect(),
+    };
+    batch_scrape_urls(&urls, options)
+}
+
+/// Scrape a list of URLs from one or more platforms and merge them into the output file
+///
+/// The scraper for each URL is picked by its domain; URLs of unsupported
+/// domains are reported as failures.
+pub fn batch_scrape_urls(urls: &[String], options: &ScrapingOptions) -> Result<ScrapingRun> {
+    debug::set_debug(options.debug);
+
+    let started = Instant::now();
+    let finish = |new_scraped: usize, updated: usize, failed: usize| ScrapingRun {
+        scraper_name: "batch".to_string(),
+        new_scraped,
+        updated,
+        failed,
+        known: 0,
+        duration: started.elapsed(),
+    };
+    let mut tui = ScraperTUI::for_options(options);
+    let mut context = ScrapingContext::new(options);
+    tui.set_title("Batch Scraper")?;
+
+    let mut all_properties = storage::load_properties(&options.output_file, options.csv)?;
+    tui.show_summary(all_properties.len())?;
+
+    if options.dry_run {
+        tui.show_dry_run(urls.len())?;
+        return Ok(finish(0, 0, 0));
+    }
+
+    for url in urls {
+        tui.add_property(url.clone())?;
+    }
+    tui.show_initial_progress_bar()?;
+
+    let (newly_scraped, failed_urls) =
+        scrape_urls(urls, &mut all_properties, options, &mut tui, &mut context, crate::scrapers::scraper_for_url)?;
+
+    let stored_urls: HashSet<&str> = all_properties.iter().map(|p| p.url.as_str()).collect();
+    let updated = newly_scraped.iter().filter(|p| stored_urls.contains(p.url.as_str())).count();
+    let run = finish(newly_scraped.len(), updated, failed_urls.len());
+
+    let mut final_properties = all_properties;
+    final_properties.extend(newly_scraped.iter().cloned());
+    let deduplicated_properties = deduplicate_properties_by_internal_id(final_properties);
+
+    tui.show_final_summary(newly_scraped.len(), deduplicated_properties.len())?;
+    tui.show_failure_report(&failed_urls)?;
+    print_paywall_report(&context, options);
+    print_performance_summary(&context);
+
+    Ok(run)
+}
+
+fn print_performance_summary(context: &ScrapingContext) {
+    if !context.performance().is_empty() {
+        println!("\nRequests per domain:\n{}", context.performance().summary_table());
+    }
+}
+
+fn print_paywall_report(context: &ScrapingContext, options: &ScrapingOptions) {
+    if context.paywalled_urls().is_empty() {
+        return;
+    }
+    println!("\n{} properties are behind a subscriber paywall:", context.paywalled_urls().len());
+    for url in context.paywalled_urls() {
+        println!("  {}", url);
+    }
+    if options.cookies.is_some() {
+        println!("The cookies did not unlock them, check them with --check-cookies");
+    } else {
+        println!("Provide the cookies of a subscriber session with --cookies to scrape them");
+    }
+}
+
+/// A URL that could not be scraped, with the error message
+type FailedUrl = (String, String);
+
+/// Scrape property pages one by one, saving the merged result after each success
+///
+/// Returns the scraped properties and the failed URLs with their error. Stored
+/// properties whose page did not change are only marked as seen again in
+/// `all_properties` and don't count against the total item budget.
+fn scrape_urls<'a>(
+    urls: &[String],
+    all_properties: &mut [Property],
+    options: &ScrapingOptions,
+    tui: &mut ScraperTUI,
+    context: &mut ScrapingContext,
+    scraper_for_url: impl Fn(&str) -> Option<&'a dyn PlatformScraper>,
+) -> Result<(Vec<Property>, Vec<FailedUrl>)> {
     let mut newly_scraped = Vec::new();
     let mut failed_urls = Vec::new();
-    
-    for url in urls_to_scrape.iter() {
+    let mut unsaved = 0;
+    let save = |all_properties: &[Property], newly_scraped: &[Property]| {
+        let mut current_properties = all_properties.to_vec();
+        current_properties.extend(newly_scraped.iter().cloned());
+
+        // Use deduplication logic to properly handle unavailable transitions
+        let deduplicated = deduplicate_properties_by_internal_id(current_properties);
+        storage::save_properties(&deduplicated, &options.output_file, options.csv)
+    };
+
+    let _catch_interrupts = CatchInterrupts::new();
+    for url in urls {
+        if is_interrupted() {
+            break;
+        }
+
+        let Some(scraper) = scraper_for_url(url) else {
+            failed_urls.push((url.clone(), "Unsupported URL domain".to_string()));
+            tui.fail_property(url)?;
+            continue;
+        };
+
+        // Be respectful to the server and avoid rate limiting
+        let delay = context.delay_for_domain(scraper.base_url());
+        if !delay.is_zero() {
+            tui.show_delay_countdown(delay.as_millis() as u64)?;
+        }
+        context.wait_for_domain(scraper.base_url());
         tui.start_scraping_property(url)?;
 
-        match scraper.scrape_property(url, options.cookies.as_deref()) {
-            Ok(property) => {
-                newly_scraped.push(property.clone());
+        // On refresh, pages that did not change since the last scrape are not parsed again
+        let stored = options
+            .refresh_days
+            .and_then(|_| all_properties.iter().position(|p| p.url == *url));
+
+        let max_retries = options.max_retries_for(url);
+        let scraped = fetch_with_retry(context, scraper.base_url(), max_retries, |context| {
+            let stored = stored.map(|i| &all_properties[i]);
+            scraper.scrape_property_if_changed(context, url, options.cookies.as_deref(), stored)
+        });
+        match scraped {
+            Ok(None) => {
+                // An unchanged page only counts as seen again
+                let i = stored.expect("only stored properties have a fingerprint");
+                all_properties[i].last_seen = Some(chrono::Utc::now().naive_utc().date());
+                unsaved += 1;
+                if unsaved >= options.save_interval {
+                    save(all_properties, &newly_scraped)?;
+                    unsaved = 0;
+                }
+                tui.set_unsaved_count(unsaved);
+                tui.complete_property(url)?;
+            }
+            Ok(Some(property)) if !options.matches_size_living(&property) => {
+                // Size is only known after scraping the detail page, so filter post-scrape
+                debug_println!("Filtered out by living size ({:?}): {}", property.size_living, url);
+                tui.filter_property(url)?;
+            }
+            Ok(Some(mut property)) => {
+                options.annotate_commute(context, &mut property);
+                newly_scraped.push(property);
+                options.consume_total_item();
+
+                // Save progress after every `save_interval` successful scrapes
+                unsaved += 1;
+                if unsaved >= options.save_interval {
+                    save(all_properties, &newly_scraped)?;
+                    unsaved = 0;
+                }
+                tui.set_unsaved_count(unsaved);
                 tui.complete_property(url)?;
-                
-                // Save progress after each successful scrape
-                let mut current_properties = all_properties.clone();
-                current_properties.extend(newly_scraped.clone());
-                
-                // Use deduplication logic to properly handle unavailable transitions
-                let deduplicated = deduplicate_properties_by_url(current_properties);
-                utils::save_properties_to_csv(&deduplicated, &options.output_file)?;
+            }
+            Err(e) if ScrapeError::is_paywall(&e) => {
+                debug_println!("{}", e);
+                context.paywalled_urls.push(url.clone());
+                tui.fail_property(url)?;
             }
             Err(e) => {
                 failed_urls.push((url.clone(), e.to_string()));
@@ -410,134 +1237,156 @@ pub fn run_scraper_with_options<T: PlatformScraper>(
             }
         }
 
-        // Add a delay to be respectful to the server and avoid rate limiting
-        std::thread::sleep(std::time::Duration::from_millis(2000));
+        if options.total_items_exhausted() {
+            debug_println!("Reached total item limit of {:?} for this run", options.total_max_items);
+            break;
+        }
     }
 
-    // Final cleanup and summary (properties already saved after each scrape)
-    let scraped_count = newly_scraped.len();
-    
-    // Calculate final totals for summary
-    let mut final_properties = all_properties.clone();
-    final_properties.extend(newly_scraped.clone());
-    
-    let deduplicated_properties = deduplicate_properties_by_url(final_properties);
+    if unsaved > 0 {
+        save(all_properties, &newly_scraped)?;
+        tui.set_unsaved_count(0);
+    }
+    if is_interrupted() {
+        return Err(anyhow::anyhow!("Interrupted, {} scraped properties were saved", newly_scraped.len()));
+    }
 
-    // Show final summary
-    tui.show_final_summary(scraped_count, deduplicated_properties.len())?;
+    Ok((newly_scraped, failed_urls))
+}
 
-    // Show failure report if there were any failures
-    tui.show_failure_report(&failed_urls)?;
+/// Extra wait before retrying after a CAPTCHA, multiplied by the attempt number
+const CAPTCHA_BACKOFF: Duration = Duration::from_secs(30);
 
-    Ok(())
+/// Additional delay before retry `attempt` (1-based) after `error`
+///
+/// CAPTCHAs mean the site considers us a bot, so back off increasingly long
+/// instead of just waiting for the rate limiter.
+fn retry_backoff(error: &anyhow::Error, attempt: u8) -> Duration {
+    if ScrapeError::is_captcha(error) {
+        CAPTCHA_BACKOFF * attempt as u32
+    } else {
+        Duration::ZERO
+    }
+}
+
+/// Call `fetch` until it succeeds, retrying up to `max_retries` times
+///
+/// Each retry waits for the domain's rate limiter like a regular request, plus
+/// the `retry_backoff` of the error.
+fn fetch_with_retry<T>(
+    context: &mut ScrapingContext,
+    domain: &str,
+    max_retries: u8,
+    mut fetch: impl FnMut(&mut ScrapingContext) -> Result<T>,
+) -> Result<T> {
+    let mut attempt = 0;
+    loop {
+        match fetch(context) {
+            // Retrying won't get past a paywall
+            Err(e) if attempt < max_retries && !is_interrupted() && !ScrapeError::is_paywall(&e) => {
+                attempt += 1;
+                debug_println!("Request to {} failed, retry {}/{}: {}", domain, attempt, max_retries, e);
+                let backoff = retry_backoff(&e, attempt);
+                let backoff_until = Instant::now() + backoff;
+                while !is_interrupted() && Instant::now() < backoff_until {
+                    std::thread::sleep(backoff.min(Duration::from_millis(200)));
+                }
+                context.wait_for_domain(domain);
+            }
+            result => return result,
+        }
+    }
 }
 
 /// Deduplicate properties by URL and property ID, merging first_seen/last_seen dates properly
 /// PRESERVES ORDER: Updates existing properties in-place, appends new ones at the end
-/// For laendleimmo.at URLs, also checks for duplicates by property ID to handle URL structure changes
+/// The platform's internal ID is the primary key when known. For laendleimmo.at URLs,
+/// also checks for duplicates by the ID in the URL to handle URL structure changes
 pub fn deduplicate_properties_by_url(properties: Vec<Property>) -> Vec<Property> {
-    let mut result = Vec::new();
-    let mut seen_urls = std::collections::HashSet::new();
+    let mut result: Vec<Property> = Vec::new();
+    let mut seen_urls = std::collections::HashMap::new(); // url -> index in result
+    let mut seen_internal_ids = std::collections::HashMap::new(); // internal_id -> index in result
     let mut seen_property_ids = std::collections::HashMap::new(); // property_id -> index in result
     
     // First pass: collect all unique properties in original order
     for property in properties {
-        let property_id = utils::extract_property_id(&property.url);
-        let mut existing_pos = None;
+        let property_id = utils::extract_property_id(&property.url)
+            .filter(|_| property.url.contains("laendleimmo.at"));
+        let url = property.url.clone();
+        let mut existing_pos: Option<usize> = None;
         
-        // Check for existing URL first
-        if seen_urls.contains(&property.url) {
-            existing_pos = result.iter().position(|p: &Property| p.url == property.url);
+        // The internal ID survives URL changes, so check it first
+        if let Some(index) = property.internal_id.as_ref().and_then(|id| seen_internal_ids.get(id)) {
+            existing_pos = Some(*index);
+        }
+        // Then check for existing URL
+        else if let Some(index) = seen_urls.get(&property.url) {
+            existing_pos = Some(*index);
         }
         // If not found by URL but we have a property ID, check by property ID for laendleimmo
-        else if let Some(id) = &property_id {
-            if property.url.contains("laendleimmo.at") {
-                if let Some(&index) = seen_property_ids.get(id) {
-                    existing_pos = Some(index);
-                    debug_println!("Found duplicate property by ID {}: existing URL {} vs new URL {}", 
-                                 id, result[index].url, property.url);
-                }
-            }
+        else if let Some(index) = property_id.as_ref().and_then(|id| seen_property_ids.get(id)) {
+            existing_pos = Some(*index);
         }
         
-        if existing_pos.is_none() {
-            // New property
-            seen_urls.insert(property.url.clone());
-            if let Some(id) = property_id {
-                if property.url.contains("laendleimmo.at") {
-                    seen_property_ids.insert(id, result.len());
+        let index = match existing_pos {
+            // Merge into the existing property in-place to preserve order
+            Some(existing_pos) => {
+                if result[existing_pos].url != property.url {
+                    debug_println!("Found duplicate property by ID: existing URL {} vs new URL {}",
+                                 result[existing_pos].url, property.url);
                 }
+                result[existing_pos].merge_with(property);
+                existing_pos
             }
-            result.push(property);
-        } else {
-            // Find existing property and merge
-            if let Some(existing_pos) = existing_pos {
-                let existing = &result[existing_pos];
-                // Determine which URL to use - prefer the more recent or more specific one
-                let final_url = if property.url != existing.url {
-                    // URLs are different - this happens when property type classification changes
-                    // Use the new URL if it has better classification or if property is becoming available again
-                    if property.listing_type == ListingType::Available || 
-                       (property.property_type != PropertyType::Unknown && existing.property_type == PropertyType::Unknown) {
-                        debug_println!("Updating URL from {} to {} (better classification or became available)", 
-                                     existing.url, property.url);
-                        property.url.clone()
-                    } else {
-                        existing.url.clone()
-                    }
-                } else {
-                    property.url.clone()
-                };
-                
-                let merged_property = if property.listing_type == ListingType::Unavailable && existing.listing_type != ListingType::Unavailable {
-                    // Property became unavailable - preserve all existing data except status and dates
-                    debug_println!("Property became unavailable, preserving existing data: {}", final_url);
-                    Property {
-                        url: final_url,
-                        name: if existing.name != "Unknown Property" && existing.name != "Unavailable Property" { existing.name.clone() } else { property.name },
-                        price: existing.price.clone(), // Always preserve existing price when becoming unavailable
-                        location: if existing.location != "Unknown" { existing.location.clone() } else { property.location },
-                        property_type: if existing.property_type != PropertyType::Unknown { existing.property_type.clone() } else { property.property_type },
-                        listing_type: property.listing_type, // Update to unavailable
-                        date: existing.date.or(property.date), // Preserve original listing date
-                        coordinates: existing.coordinates.or(property.coordinates),
-                        address: existing.address.clone().or(property.address),
-                        size_living: existing.size_living.clone().or(property.size_living),
-                        size_ground: existing.size_ground.clone().or(property.size_ground),
-                        // Keep the earliest first_seen date
-                        first_seen: existing.first_seen.or(property.first_seen),
-                        // Preserve existing last_seen since property became unavailable
-                        last_seen: existing.last_seen.or(property.last_seen),
-                    }
-                } else {
-                    // Normal property update - use new data but preserve existing data when scraper fails
-                    Property {
-                        url: final_url,
-                        name: if property.name.is_empty() || property.name == "Unknown Property" || property.name == "Unavailable Property" { existing.name.clone() } else { property.name },
-                        price: if property.price.is_empty() || property.price == "Unknown" || property.price == "Unavailable" { existing.price.clone() } else { property.price },
-                        location: if property.location.is_empty() || property.location == "Unknown" { existing.location.clone() } else { property.location },
-                        property_type: if property.property_type == PropertyType::Unknown { existing.property_type.clone() } else { property.property_type },
-                        listing_type: property.listing_type, // Always update listing status
-                        date: property.date.or(existing.date),
-                        coordinates: property.coordinates.or(existing.coordinates),
-                        address: property.address.or(existing.address.clone()),
-                        size_living: property.size_living.or(existing.size_living.clone()),
-                        size_ground: property.size_ground.or(existing.size_ground.clone()),
-                        // Keep the earliest first_seen date
-                        first_seen: existing.first_seen.or(property.first_seen),
-                        // Use the latest last_seen date
-                        last_seen: property.last_seen.or(existing.last_seen),
-                    }
-                };
-                // Update in-place to preserve order
-                result[existing_pos] = merged_property;
+            // New property
+            None => {
+                result.push(property);
+                result.len() - 1
             }
+        };
+        
+        // Register all keys of the merged property so later duplicates find it by any of them
+        let merged = &result[index];
+        seen_urls.insert(url, index);
+        seen_urls.insert(merged.url.clone(), index);
+        if let Some(id) = &merged.internal_id {
+            seen_internal_ids.entry(id.clone()).or_insert(index);
+        }
+        if let Some(id) = property_id {
+            seen_property_ids.entry(id).or_insert(index);
         }
     }
     
     result
 }
 
+/// Deduplicate properties by their platform's internal ID, falling back to URL deduplication
+///
+/// laendleimmo.at changes a property's URL when it is reclassified, so the same
+/// ID can turn up under several URLs. The merged property keeps the URL it was
+/// seen under most recently; on equal `last_seen` dates the later one wins.
+pub fn deduplicate_properties_by_internal_id(properties: Vec<Property>) -> Vec<Property> {
+    let mut latest_urls: HashMap<String, (Option<chrono::NaiveDate>, String)> = HashMap::new();
+    for property in &properties {
+        if let Some(id) = &property.internal_id {
+            if latest_urls.get(id).map_or(true, |(last_seen, _)| property.last_seen >= *last_seen) {
+                latest_urls.insert(id.clone(), (property.last_seen, property.url.clone()));
+            }
+        }
+    }
+
+    let mut deduplicated = deduplicate_properties_by_url(properties);
+    for property in &mut deduplicated {
+        if let Some((_, url)) = property.internal_id.as_ref().and_then(|id| latest_urls.get(id)) {
+            if property.url != *url {
+                debug_println!("Using most recently seen URL {} instead of {}", url, property.url);
+                property.url = url.clone();
+            }
+        }
+    }
+    deduplicated
+}
+
 // Legacy functions for backwards compatibility
 pub struct ScrapingResult {
     pub scraped_properties: Vec<Property>,
@@ -545,21 +1394,363 @@ pub struct ScrapingResult {
     pub is_refresh: bool,
 }
 
+/// Merge scraped properties into the stored ones
+///
+/// A refreshed property updates its stored entry in place through
+/// `Property::merge_with`, so data the scraper didn't find this time is kept.
+/// Stored properties that weren't scraped stay as they are.
 pub fn merge_properties_with_refresh(
     mut existing_properties: Vec<Property>,
     result: ScrapingResult,
     _platform_domain: &str,
 ) -> Vec<Property> {
-    if result.is_refresh {
-        // In refresh mode, only remove properties that were actually scraped
-        // Keep all other properties (including other platforms and non-scraped URLs from this platform)
-        existing_properties.retain(|p| !result.scraped_urls.contains(&p.url));
+    // Deduplication merges each later occurrence into the first one
+    existing_properties.extend(result.scraped_properties);
+    deduplicate_properties_by_internal_id(existing_properties)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ListingType;
+
+    #[test]
+    fn test_retry_backoff() {
+        let captcha: anyhow::Error = ScrapeError::Captcha { url: "https://www.vol.at/x".to_string() }.into();
+        let captcha = captcha.context("Failed to scrape");
+        assert_eq!(retry_backoff(&captcha, 1), CAPTCHA_BACKOFF);
+        assert_eq!(retry_backoff(&captcha, 3), CAPTCHA_BACKOFF * 3);
+        assert_eq!(retry_backoff(&anyhow::anyhow!("HTTP error status: 500"), 2), Duration::ZERO);
     }
 
-    // Add the newly scraped properties
-    existing_properties.extend(result.scraped_properties);
+    /// Held by tests that run `scrape_urls`, which catches interrupts while it runs
+    static SCRAPE_URLS_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
 
-    // Deduplicate by URL (keep the last occurrence to preserve refreshed data)
-    deduplicate_properties_by_url(existing_properties)
-}
+    #[test]
+    fn test_interrupts_are_caught_only_while_scraping() {
+        let _lock = SCRAPE_URLS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        assert!(!CATCHING_INTERRUPTS.load(Ordering::SeqCst));
+        {
+            let _catch_interrupts = CatchInterrupts::new();
+            assert!(CATCHING_INTERRUPTS.load(Ordering::SeqCst));
+        }
+        assert!(!CATCHING_INTERRUPTS.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_osrm_requests_are_throttled_to_one_per_second() {
+        let mut context = ScrapingContext::new(&ScrapingOptions { requests_per_second: 100.0, ..Default::default() });
+        let osrm = utils::domain_from_url(utils::OSRM_ROUTE_URL);
+        assert_eq!(context.delay_for_domain(osrm), Duration::ZERO);
+        context.wait_for_domain(osrm);
+        let delay = context.delay_for_domain(osrm);
+        assert!(delay > Duration::from_millis(900) && delay <= Duration::from_secs(1), "{:?}", delay);
+    }
+
+    #[test]
+    fn test_fetch_for_health_check() {
+        let mut context = ScrapingContext::new(&ScrapingOptions::default());
+        let (url, _) = utils::serve_once("200 OK", "ok");
+        assert_eq!(fetch_for_health_check(&mut context, &url).unwrap(), "ok");
+        let (url, _) = utils::serve_once("503 Service Unavailable", "down");
+        let error = fetch_for_health_check(&mut context, &url).unwrap_err();
+        assert!(error.to_string().contains("503"), "{}", error);
+    }
+
+    #[test]
+    fn test_scraping_options_builder() {
+        let options = ScrapingOptions::builder()
+            .max_items(5)
+            .output_file("out.csv")
+            .total_max_items(10)
+            .min_freshness_days(None)
+            .build();
+        assert_eq!(options.output_file, "out.csv");
+        assert_eq!(options.max_items, Some(5));
+        assert_eq!(options.remaining_total_items(), Some(10));
+        // Everything else keeps the defaults
+        assert!(options.new);
+        assert_eq!(options.save_interval, ScrapingOptions::default().save_interval);
+        assert_eq!(options.requests_per_second, ScrapingOptions::default().requests_per_second);
+    }
+
+    #[test]
+    fn test_retry_overrides() {
+        let options = ScrapingOptions {
+            max_retries: 2,
+            retry_overrides: HashMap::from([("vol.at".to_string(), 5), ("laendleimmo.at".to_string(), 0)]),
+            requests_per_second: 1000.0,
+            ..Default::default()
+        };
+        assert_eq!(options.max_retries_for("https://www.vol.at/x"), 5);
+        assert_eq!(options.max_retries_for("https://www.laendleimmo.at/immobilien/x"), 0);
+        assert_eq!(options.max_retries_for("https://example.com/x"), 2);
+
+        let mut context = ScrapingContext::new(&options);
+        let mut calls = 0;
+        let result = fetch_with_retry(&mut context, "vol.at", 5, |_| {
+            calls += 1;
+            if calls < 3 { Err(anyhow::anyhow!("flaky")) } else { Ok(calls) }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut calls = 0;
+        let result: Result<()> = fetch_with_retry(&mut context, "laendleimmo.at", 0, |_| {
+            calls += 1;
+            Err(anyhow::anyhow!("down"))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    fn listing(url: &str, internal_id: Option<&str>, price: &str) -> Property {
+        Property {
+            url: url.to_string(),
+            internal_id: internal_id.map(str::to_string),
+            page_fingerprint: None,
+            completeness_score: None,
+            name: "Wohnung".to_string(),
+            price: crate::models::Price::from_str(price),
+            location: crate::models::Location::new("Dornbirn"),
+            property_type: crate::models::PropertyKind::Apartment.into(),
+            listing_type: ListingType::Available,
+            unavailable_reason: None,
+            date: None,
+            coordinates: None,
+            quality_score: None,
+            plot_polygon: None,
+            description: None,
+            features: Vec::new(),
+            driving_distance_km: None,
+            rooms: None,
+            floor_level: None,
+            construction_year: None,
+            agency_name: None,
+            agency_phone: None,
+            address: None,
+            size_living: None,
+            size_ground: None,
+            first_seen: None,
+            last_seen: None,
+        }
+    }
+
+    #[test]
+    fn test_deduplicate_properties_by_url_merges_internal_ids() {
+        let properties = vec![
+            listing("https://www.laendleimmo.at/immobilien/grundstueck/vorarlberg/dornbirn/wohnen-am-see", Some("4711"), "300000"),
+            listing("https://www.vol.at/wohnung-dornbirn", None, "250000"),
+            listing("https://www.laendleimmo.at/immobilien/wohnung/vorarlberg/dornbirn/wohnung-am-see", Some("4711"), "320000"),
+            listing("https://www.laendleimmo.at/immobilien/grundstueck/vorarlberg/dornbirn/wohnen-am-see", None, "330000"),
+        ];
+        let deduplicated = deduplicate_properties_by_url(properties);
+        assert_eq!(deduplicated.len(), 2);
+        assert_eq!(deduplicated[0].internal_id.as_deref(), Some("4711"));
+        assert_eq!(deduplicated[0].price.raw, "330000");
+        assert_eq!(deduplicated[1].url, "https://www.vol.at/wohnung-dornbirn");
+    }
+
+    #[test]
+    fn test_deduplicate_properties_by_internal_id_keeps_latest_url() {
+        let seen = |mut property: Property, day: u32| {
+            property.last_seen = chrono::NaiveDate::from_ymd_opt(2025, 6, day);
+            property
+        };
+        let old_url = "https://www.laendleimmo.at/immobilien/wohnung/vorarlberg/dornbirn/wohnen-am-see";
+        let new_url = "https://www.laendleimmo.at/immobilien/grundstueck/vorarlberg/dornbirn/wohnen-am-see";
+        let properties = vec![
+            seen(listing(new_url, Some("4711"), "300000"), 20),
+            seen(listing(old_url, Some("4711"), "310000"), 10),
+            seen(listing("https://www.vol.at/wohnung-dornbirn", None, "250000"), 15),
+        ];
+
+        let deduplicated = deduplicate_properties_by_internal_id(properties);
+
+        assert_eq!(deduplicated.len(), 2);
+        assert_eq!(deduplicated[0].url, new_url);
+        assert_eq!(deduplicated[1].url, "https://www.vol.at/wohnung-dornbirn");
+    }
+
+    #[test]
+    fn test_check_scraped_property() {
+        let url = "https://www.vol.at/wohnung-dornbirn";
+        assert!(check_scraped_property(&listing(url, None, "250000")).is_empty());
+
+        let mut broken = listing(url, None, "auf Anfrage");
+        broken.name = " ".to_string();
+        broken.location = crate::models::Location::unknown();
+        broken.coordinates = Some(Coordinates::new(47.27, 11.39).unwrap());
+        assert_eq!(
+            check_scraped_property(&broken),
+            vec![
+                "empty name".to_string(),
+                "unparseable price 'auf Anfrage'".to_string(),
+                "unknown location".to_string(),
+                "coordinates outside Vorarlberg".to_string(),
+            ]
+        );
+
+        let mut unavailable = listing(url, None, "Unavailable");
+        unavailable.listing_type = ListingType::Unavailable;
+        assert!(check_scraped_property(&unavailable).is_empty());
+    }
+
+    #[test]
+    fn test_urls_due_for_refresh() {
+        let today = chrono::Utc::now().naive_utc().date();
+        let days_ago = |days: i64| Some(today - chrono::Duration::days(days));
+        let property = |url: &str, last_seen, date| {
+            let mut property = listing(url, None, "250000");
+            property.last_seen = last_seen;
+            property.date = date;
+            property
+        };
+        let mut sold = property("https://www.vol.at/sold", None, None);
+        sold.listing_type = ListingType::Sold;
+        let properties = [
+            property("https://www.vol.at/old-listing", days_ago(10), days_ago(300)),
+            property("https://www.vol.at/recently-seen", days_ago(2), days_ago(400)),
+            property("https://www.vol.at/never-seen", None, days_ago(100)),
+            property("https://www.vol.at/undated", days_ago(7), None),
+            sold,
+        ];
+
+        assert_eq!(
+            urls_due_for_refresh(properties.iter(), 7),
+            vec![
+                "https://www.vol.at/undated".to_string(),
+                "https://www.vol.at/old-listing".to_string(),
+                "https://www.vol.at/never-seen".to_string(),
+            ]
+        );
+        // Refreshing at most daily, everything seen before today is due
+        assert_eq!(urls_due_for_refresh(properties.iter(), 0).len(), 4);
+    }
 
+    #[test]
+    fn test_scrape_input_csv() {
+        let _lock = SCRAPE_URLS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = std::env::temp_dir();
+        let input = dir.join(format!("laendlefinder-input-{}.csv", std::process::id()));
+        let output = dir.join(format!("laendlefinder-input-output-{}.csv", std::process::id()));
+        let today = chrono::Utc::now().naive_utc().date();
+        let mut recent = listing("https://www.example.com/recent", None, "250000");
+        recent.last_seen = Some(today);
+        let mut old = listing("https://www.example.com/old", None, "300000");
+        old.last_seen = Some(today - chrono::Duration::days(30));
+        storage::save_properties(&[recent, old], input.to_str().unwrap(), storage::CsvOptions::default()).unwrap();
+
+        // URLs of unsupported domains fail without any request, which tells how many were picked
+        let options = ScrapingOptions { output_file: output.to_str().unwrap().to_string(), ..Default::default() };
+        let all = scrape_input_csv(input.to_str().unwrap(), &options);
+        let due = scrape_input_csv(input.to_str().unwrap(), &ScrapingOptions { refresh_days: Some(7), ..options.clone() });
+        let dry_run = scrape_input_csv(input.to_str().unwrap(), &ScrapingOptions { dry_run: true, ..options.clone() });
+        let missing = scrape_input_csv(dir.join("laendlefinder-no-such-input.csv").to_str().unwrap(), &options);
+        let _ = std::fs::remove_file(&input);
+
+        assert_eq!(all.unwrap().failed, 2);
+        assert_eq!(due.unwrap().failed, 1);
+        let dry_run = dry_run.unwrap();
+        assert_eq!((dry_run.new_scraped, dry_run.failed), (0, 0));
+        assert!(missing.is_err());
+        assert!(!output.exists());
+    }
+
+    /// Reports every page as unchanged since the last scrape
+    struct UnchangedScraper;
+
+    impl PlatformScraper for UnchangedScraper {
+        fn base_url(&self) -> &str {
+            "example.com"
+        }
+        fn scrape_listings(
+            &self,
+            _options: &ScrapingOptions,
+            _context: &mut ScrapingContext,
+            _tui: Option<&mut ScraperTUI>,
+            _existing_urls: &HashSet<String>,
+        ) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+        fn scrape_new_urls(
+            &self,
+            _options: &ScrapingOptions,
+            _context: &mut ScrapingContext,
+            _tui: Option<&mut ScraperTUI>,
+            _existing_urls: &HashSet<String>,
+        ) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+        fn scrape_property(&self, _context: &mut ScrapingContext, url: &str, _cookies: Option<&str>) -> Result<Property> {
+            Err(anyhow::anyhow!("{} should not be parsed again", url))
+        }
+        fn scrape_property_if_changed(
+            &self,
+            _context: &mut ScrapingContext,
+            _url: &str,
+            _cookies: Option<&str>,
+            _stored: Option<&Property>,
+        ) -> Result<Option<Property>> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn test_unchanged_page_is_seen_again_without_consuming_budget() {
+        let _lock = SCRAPE_URLS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let output = std::env::temp_dir().join(format!("laendlefinder-unchanged-{}.csv", std::process::id()));
+        let url = "https://www.example.com/unchanged";
+        let mut stored = listing(url, None, "250000");
+        stored.page_fingerprint = Some("fingerprint".to_string());
+        stored.last_seen = Some(chrono::Utc::now().naive_utc().date() - chrono::Duration::days(30));
+        let mut all_properties = vec![stored];
+        let options = ScrapingOptions {
+            output_file: output.to_str().unwrap().to_string(),
+            refresh_days: Some(7),
+            ..Default::default()
+        }
+        .with_total_max_items(Some(1));
+        let mut context = ScrapingContext::new(&options);
+
+        let (newly_scraped, failed_urls) = scrape_urls(
+            &[url.to_string()],
+            &mut all_properties,
+            &options,
+            &mut ScraperTUI::new(),
+            &mut context,
+            |_| Some(&UnchangedScraper),
+        )
+        .unwrap();
+        let saved = storage::load_properties(output.to_str().unwrap(), storage::CsvOptions::default());
+        let _ = std::fs::remove_file(&output);
+
+        assert!(newly_scraped.is_empty());
+        assert!(failed_urls.is_empty());
+        assert_eq!(options.remaining_total_items(), Some(1));
+        assert_eq!(all_properties[0].last_seen, Some(chrono::Utc::now().naive_utc().date()));
+        assert_eq!(saved.unwrap()[0].last_seen, all_properties[0].last_seen);
+    }
+
+    #[test]
+    fn test_merge_properties_with_refresh_keeps_stored_data() {
+        let url = "https://www.vol.at/wohnung-dornbirn";
+        let mut stored = listing(url, None, "250000");
+        stored.description = Some("Helle Wohnung mit Seeblick".to_string());
+        let other = listing("https://www.vol.at/haus-lustenau", None, "500000");
+        let refreshed = listing(url, None, "260000");
+        let result = ScrapingResult {
+            scraped_properties: vec![refreshed],
+            scraped_urls: vec![url.to_string()],
+            is_refresh: true,
+        };
+
+        let merged = merge_properties_with_refresh(vec![stored, other], result, "vol.at");
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].url, url);
+        assert_eq!(merged[0].price.raw, "260000");
+        assert_eq!(merged[0].description.as_deref(), Some("Helle Wohnung mit Seeblick"));
+        assert_eq!(merged[1].url, "https://www.vol.at/haus-lustenau");
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Where the config file is looked up when no `--config` is given
+///
+/// `~/.config/laendlefinder/config.toml` on Linux and macOS,
+/// `%APPDATA%\laendlefinder\config.toml` on Windows.
+pub fn default_config_path() -> PathBuf {
+    let config_dir = if cfg!(windows) {
+        dirs::config_dir()
+    } else {
+        dirs::home_dir().map(|home| home.join(".config"))
+    };
+    config_dir.unwrap_or_default().join("laendlefinder").join("config.toml")
+}
+
+/// Read a TOML config file into pairs of long flag names and their values
+///
+/// Keys are flag names without the leading dashes, e.g. `max-items = 50`;
+/// underscores are accepted in place of dashes. A single value becomes a list
+/// of one, an array a list of its values.
+pub fn load_config(path: &Path) -> Result<Vec<(String, Vec<String>)>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let table: toml::Table = content
+        .parse()
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+    table
+        .into_iter()
+        .map(|(key, value)| {
+            let values = match &value {
+                toml::Value::Array(values) => values.iter().map(config_value).collect(),
+                value => config_value(value).map(|value| vec![value]),
+            }
+            .ok_or_else(|| anyhow::anyhow!("Unsupported value for '{}' in config file {}", key, path.display()))?;
+            Ok((key.replace('_', "-"), values))
+        })
+        .collect()
+}
+
+/// A TOML value as it would be written on the command line
+fn config_value(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) | toml::Value::Datetime(_) => {
+            Some(value.to_string())
+        }
+        toml::Value::Array(_) | toml::Value::Table(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_config() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-config-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "output = \"/data/properties.db\"\nmax_items = 50\ndebug = true\nregion = [\"Bregenz\", \"Dornbirn\"]\n",
+        )
+        .unwrap();
+        let mut config = load_config(&path).unwrap();
+        std::fs::write(&path, "[geocoding]\nlocate = true\n").unwrap();
+        let nested = load_config(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        config.sort();
+        assert_eq!(
+            config,
+            vec![
+                ("debug".to_string(), vec!["true".to_string()]),
+                ("max-items".to_string(), vec!["50".to_string()]),
+                ("output".to_string(), vec!["/data/properties.db".to_string()]),
+                ("region".to_string(), vec!["Bregenz".to_string(), "Dornbirn".to_string()]),
+            ]
+        );
+        assert!(nested.is_err());
+        assert!(default_config_path().ends_with("laendlefinder/config.toml"));
+    }
+}
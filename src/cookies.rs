@@ -0,0 +1,85 @@
+use crate::common_scraper::ScrapingContext;
+use crate::http_logging::SendLogged;
+use crate::scraper;
+use crate::debug_println;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// Outcome of checking vol.at cookies against a live property page
+#[derive(Debug, Clone, PartialEq)]
+pub enum CookieValidationResult {
+    /// Property data was returned, cookies work
+    Valid,
+    /// A login wall was returned instead of property data
+    Expired,
+    /// No cookies were provided
+    Missing,
+}
+
+/// Load a cookie string from a file, or use the argument itself if no such file exists
+pub fn load_cookie_string(path_or_cookies: &str) -> Result<String> {
+    let path = Path::new(path_or_cookies);
+    if path.exists() {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read cookie file: {}", path.display()))?;
+        return Ok(content.trim().to_string());
+    }
+    Ok(path_or_cookies.trim().to_string())
+}
+
+/// Make one test request to a vol.at property page and check if it yields property data
+///
+/// Both requests send the HTTP headers of `context`.
+pub fn validate_cookies(context: &mut ScrapingContext, cookie_str: &str) -> Result<CookieValidationResult> {
+    if cookie_str.trim().is_empty() {
+        return Ok(CookieValidationResult::Missing);
+    }
+
+    // Use the most recent article from the topic index as a test page
+    let test_url = scraper::scrape_index_page(context)?
+        .into_iter()
+        .next()
+        .context("No property articles found on vol.at index page")?;
+    debug_println!("Validating cookies against: {}", test_url);
+
+    let response = context
+        .http_client_builder()
+        .build()?
+        .get(&test_url)
+        .header("User-Agent", context.next_user_agent())
+        .header("Cookie", cookie_str.trim())
+        .send_counted(context.performance())
+        .context("Failed to fetch test property page")?;
+
+    let final_url = response.url().to_string();
+    let html = response.text().context("Failed to get response text")?;
+
+    // A redirect to a login page or a missing data node means we hit the login wall
+    if final_url.contains("login") || !html.contains("newExternalPostDataNode") {
+        debug_println!("No property data received, final URL: {}", final_url);
+        return Ok(CookieValidationResult::Expired);
+    }
+
+    Ok(CookieValidationResult::Valid)
+}
+
+/// Print step-by-step instructions for exporting fresh vol.at cookies from the browser
+pub fn print_cookie_instructions(cookie_file: &str) {
+    println!("How to obtain fresh vol.at cookies:");
+    println!();
+    println!("Firefox:");
+    println!("  1. Open https://www.vol.at and log in");
+    println!("  2. Press F12 to open the developer tools and select the \"Network\" tab");
+    println!("  3. Reload the page (F5) and click the first request to www.vol.at");
+    println!("  4. In \"Headers\" → \"Request Headers\", right-click \"Cookie\" and choose \"Copy Value\"");
+    println!();
+    println!("Chrome:");
+    println!("  1. Open https://www.vol.at and log in");
+    println!("  2. Press F12 (or Ctrl+Shift+I) and select the \"Network\" tab");
+    println!("  3. Reload the page (F5) and click the first document request to www.vol.at");
+    println!("  4. Under \"Headers\" → \"Request Headers\", select the whole \"cookie:\" value and copy it");
+    println!();
+    println!("Then save the copied value as a single line to {}", cookie_file);
+    println!("and run `laendlefinder --check-cookies` again to verify.");
+}
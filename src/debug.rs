@@ -1,4 +1,7 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 static DEBUG_ENABLED: AtomicBool = AtomicBool::new(false);
 
@@ -27,3 +30,216 @@ macro_rules! debug_eprintln {
         }
     };
 }
+
+static TRACE_SLOW_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Enable slow-call tracing, overriding each tracer's threshold with `threshold_ms`
+///
+/// A threshold of 0 disables tracing again.
+pub fn set_trace_slow_ms(threshold_ms: u64) {
+    TRACE_SLOW_MS.store(threshold_ms, Ordering::Relaxed);
+}
+
+pub fn is_tracing_enabled() -> bool {
+    TRACE_SLOW_MS.load(Ordering::Relaxed) > 0
+}
+
+/// Logs the time spent in a scope when it exceeds a threshold
+///
+/// Create one at the start of a slow operation and the elapsed time is checked
+/// when it is dropped. Only active when tracing is enabled via `set_trace_slow_ms`,
+/// and like all debug output only printed in debug mode.
+pub struct Tracer {
+    name: &'static str,
+    threshold_ms: u64,
+    start: Instant,
+}
+
+impl Tracer {
+    pub fn new(name: &'static str, threshold_ms: u64) -> Self {
+        let configured = TRACE_SLOW_MS.load(Ordering::Relaxed);
+        Self {
+            name,
+            threshold_ms: if configured > 0 { configured } else { threshold_ms },
+            start: Instant::now(),
+        }
+    }
+
+    fn is_slow(&self, elapsed: Duration) -> bool {
+        elapsed.as_millis() > self.threshold_ms as u128
+    }
+}
+
+impl Drop for Tracer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        if is_tracing_enabled() && self.is_slow(elapsed) {
+            crate::debug_println!(
+                "Slow call: {} took {}ms (threshold {}ms)",
+                self.name,
+                elapsed.as_millis(),
+                self.threshold_ms
+            );
+        }
+    }
+}
+
+/// Request outcomes of one domain
+#[derive(Debug, Clone, Default)]
+pub struct DomainStats {
+    pub successes: usize,
+    pub failures: usize,
+    /// Response times of successful requests
+    pub durations: Vec<Duration>,
+    /// Failure count per HTTP status code, failures without a status aren't counted here
+    pub error_codes: HashMap<u16, usize>,
+}
+
+impl DomainStats {
+    pub fn total(&self) -> usize {
+        self.successes + self.failures
+    }
+
+    pub fn success_rate(&self) -> f64 {
+        if self.total() == 0 {
+            0.0
+        } else {
+            self.successes as f64 / self.total() as f64
+        }
+    }
+
+    pub fn mean_duration(&self) -> Option<Duration> {
+        let count = u32::try_from(self.durations.len()).ok().filter(|&n| n > 0)?;
+        Some(self.durations.iter().sum::<Duration>() / count)
+    }
+
+    /// 95th percentile response time by the nearest-rank method
+    pub fn p95_duration(&self) -> Option<Duration> {
+        let mut sorted = self.durations.clone();
+        sorted.sort();
+        let rank = (sorted.len() * 95).div_ceil(100);
+        sorted.get(rank.checked_sub(1)?).copied()
+    }
+
+    /// Status codes ordered by frequency, ties by code
+    pub fn most_common_errors(&self) -> Vec<(u16, usize)> {
+        let mut codes: Vec<(u16, usize)> = self.error_codes.iter().map(|(&code, &n)| (code, n)).collect();
+        codes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        codes
+    }
+}
+
+/// Tracks request success and failure rates and response times per domain
+#[derive(Debug, Default)]
+pub struct PerformanceCounter {
+    domains: Mutex<HashMap<String, DomainStats>>,
+}
+
+impl PerformanceCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, domain: &str, update: impl FnOnce(&mut DomainStats)) {
+        let mut domains = self.domains.lock().unwrap_or_else(|e| e.into_inner());
+        update(domains.entry(domain.to_string()).or_default());
+    }
+
+    pub fn record_success(&self, domain: &str, duration: Duration) {
+        self.update(domain, |stats| {
+            stats.successes += 1;
+            stats.durations.push(duration);
+        });
+    }
+
+    pub fn record_failure(&self, domain: &str, status_code: Option<u16>) {
+        self.update(domain, |stats| {
+            stats.failures += 1;
+            if let Some(code) = status_code {
+                *stats.error_codes.entry(code).or_default() += 1;
+            }
+        });
+    }
+
+    /// Snapshot of the stats of every domain, sorted by domain
+    pub fn stats(&self) -> Vec<(String, DomainStats)> {
+        let domains = self.domains.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<(String, DomainStats)> =
+            domains.iter().map(|(domain, stats)| (domain.clone(), stats.clone())).collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
+    /// Per-domain table of requests, success rate, mean and p95 response time and top error codes
+    pub fn summary_table(&self) -> String {
+        let millis = |d: Option<Duration>| d.map_or("-".to_string(), |d| format!("{}ms", d.as_millis()));
+        let mut table = format!(
+            "{:<20} {:>8} {:>8} {:>10} {:>10}  {}\n",
+            "Domain", "Requests", "Success", "Mean", "p95", "Errors"
+        );
+        for (domain, stats) in self.stats() {
+            let errors: Vec<String> = stats
+                .most_common_errors()
+                .iter()
+                .take(3)
+                .map(|(code, n)| format!("{}×{}", code, n))
+                .collect();
+            table.push_str(&format!(
+                "{:<20} {:>8} {:>7.1}% {:>10} {:>10}  {}\n",
+                domain,
+                stats.total(),
+                stats.success_rate() * 100.0,
+                millis(stats.mean_duration()),
+                millis(stats.p95_duration()),
+                if errors.is_empty() { "-".to_string() } else { errors.join(", ") },
+            ));
+        }
+        table
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.domains.lock().unwrap_or_else(|e| e.into_inner()).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracer_is_slow() {
+        let tracer = Tracer { name: "test", threshold_ms: 100, start: Instant::now() };
+        assert!(!tracer.is_slow(Duration::from_millis(100)));
+        assert!(tracer.is_slow(Duration::from_millis(101)));
+    }
+
+    #[test]
+    fn test_performance_counter() {
+        let counter = PerformanceCounter::new();
+        for ms in 1..=20 {
+            counter.record_success("vol.at", Duration::from_millis(ms * 10));
+        }
+        counter.record_failure("vol.at", Some(503));
+        counter.record_failure("laendleimmo.at", Some(404));
+        counter.record_failure("laendleimmo.at", Some(429));
+        counter.record_failure("laendleimmo.at", Some(404));
+        counter.record_failure("laendleimmo.at", None);
+
+        let stats = counter.stats();
+        let (domain, laendleimmo) = &stats[0];
+        assert_eq!(domain, "laendleimmo.at");
+        assert_eq!(laendleimmo.total(), 4);
+        assert_eq!(laendleimmo.success_rate(), 0.0);
+        assert_eq!(laendleimmo.mean_duration(), None);
+        assert_eq!(laendleimmo.most_common_errors(), vec![(404, 2), (429, 1)]);
+
+        let (_, vol) = &stats[1];
+        assert_eq!(vol.total(), 21);
+        assert_eq!(vol.mean_duration(), Some(Duration::from_millis(105)));
+        assert_eq!(vol.p95_duration(), Some(Duration::from_millis(190)));
+
+        let table = counter.summary_table();
+        assert!(table.contains("404×2, 429×1"), "{}", table);
+        assert!(table.contains("95.2%"), "{}", table);
+    }
+}
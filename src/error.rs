@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// Scraping failures callers need to tell apart from other errors
+///
+/// Returned wrapped in `anyhow::Error`; use `downcast_ref::<ScrapeError>()` to
+/// check for a variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrapeError {
+    /// The site answered with a CAPTCHA or bot challenge instead of the page
+    Captcha { url: String },
+    /// The article is only readable with a subscription
+    Paywall { url: String, with_cookies: bool },
+}
+
+impl fmt::Display for ScrapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScrapeError::Captcha { url } => write!(f, "CAPTCHA challenge instead of content: {}", url),
+            ScrapeError::Paywall { url, with_cookies: false } => write!(f, "Behind the subscriber paywall: {}", url),
+            ScrapeError::Paywall { url, with_cookies: true } => {
+                write!(f, "Behind the subscriber paywall even with cookies, check them with --check-cookies: {}", url)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScrapeError {}
+
+impl ScrapeError {
+    /// Whether an error, or any error in its chain, is a CAPTCHA challenge
+    pub fn is_captcha(error: &anyhow::Error) -> bool {
+        error
+            .chain()
+            .any(|e| matches!(e.downcast_ref::<ScrapeError>(), Some(ScrapeError::Captcha { .. })))
+    }
+
+    /// Whether an error, or any error in its chain, is a paywalled article
+    pub fn is_paywall(error: &anyhow::Error) -> bool {
+        error
+            .chain()
+            .any(|e| matches!(e.downcast_ref::<ScrapeError>(), Some(ScrapeError::Paywall { .. })))
+    }
+}
@@ -0,0 +1,320 @@
+use crate::models::{Property, PropertyKind, PropertyType};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+
+/// A compound property query parsed from a mini query language
+///
+/// Terms have the form `field:value` and are separated by spaces, which means
+/// AND. `OR` between two terms matches either of them and binds tighter than
+/// AND, so `type:house OR type:land price:<500000` is "(house or land) and
+/// cheaper than 500000". A `NOT` prefix negates the following term. Values
+/// with spaces can be quoted: `location:"Sankt Anton"`.
+///
+/// Supported fields:
+/// - `type:house` and `listing:sold`
+/// - `location:Feldkirch` and `name:Garten` (case-insensitive substring)
+/// - `price:<500000`, `size:>=100`, `ground:>600`, `rooms:3`, `commute:<=20`
+///   with optional `<`, `<=`, `>`, `>=` or `=`
+/// - `date:>=2024-01-01`
+/// - `has:coordinates`, `has:address`, `has:price`, `has:date`, `has:description`
+///   or a feature like `has:sauna` or `has:Terrasse`, in English or German
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyQuery {
+    /// All clauses must match; a clause matches if any of its terms does
+    clauses: Vec<Vec<Term>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    negated: bool,
+    condition: Condition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum NumericField {
+    Price,
+    SizeLiving,
+    SizeGround,
+    Rooms,
+    Commute,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Type(PropertyKind),
+    Listing(String),
+    Location(String),
+    Name(String),
+    Number(NumericField, Comparison, f64),
+    Date(Comparison, NaiveDate),
+    Has(String),
+}
+
+impl Comparison {
+    /// Split a leading comparison operator off a value, defaulting to equality
+    fn split(value: &str) -> (Self, &str) {
+        for (prefix, comparison) in [
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+            ("=", Comparison::Equal),
+        ] {
+            if let Some(rest) = value.strip_prefix(prefix) {
+                return (comparison, rest);
+            }
+        }
+        (Comparison::Equal, value)
+    }
+
+    fn holds<T: PartialOrd>(self, actual: T, expected: T) -> bool {
+        match self {
+            Comparison::Less => actual < expected,
+            Comparison::LessOrEqual => actual <= expected,
+            Comparison::Equal => actual == expected,
+            Comparison::GreaterOrEqual => actual >= expected,
+            Comparison::Greater => actual > expected,
+        }
+    }
+}
+
+impl Condition {
+    fn parse(term: &str) -> Result<Self> {
+        let (field, value) = term
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Expected field:value, got '{}'", term))?;
+        if value.is_empty() {
+            return Err(anyhow!("Missing value for '{}'", field));
+        }
+
+        let number = |field: NumericField| -> Result<Condition> {
+            let (comparison, number) = Comparison::split(value);
+            let number = number
+                .parse::<f64>()
+                .map_err(|_| anyhow!("Invalid number in '{}'", term))?;
+            Ok(Condition::Number(field, comparison, number))
+        };
+
+        match field.to_lowercase().as_str() {
+            "type" => match PropertyType::from_string(value).kind {
+                PropertyKind::Unknown if value != "unknown" => Err(anyhow!("Unknown property type '{}'", value)),
+                kind => Ok(Condition::Type(kind)),
+            },
+            "listing" => match value.to_lowercase().as_str() {
+                listing @ ("available" | "sold" | "unavailable") => Ok(Condition::Listing(listing.to_string())),
+                _ => Err(anyhow!("Unknown listing type '{}'", value)),
+            },
+            "location" => Ok(Condition::Location(value.to_lowercase())),
+            "name" => Ok(Condition::Name(value.to_lowercase())),
+            "price" => number(NumericField::Price),
+            "size" => number(NumericField::SizeLiving),
+            "ground" => number(NumericField::SizeGround),
+            "rooms" => number(NumericField::Rooms),
+            "commute" => number(NumericField::Commute),
+            "date" => {
+                let (comparison, date) = Comparison::split(value);
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map_err(|_| anyhow!("Invalid date in '{}', expected YYYY-MM-DD", term))?;
+                Ok(Condition::Date(comparison, date))
+            }
+            // Features are stored normalized, so `has:Terrasse` finds "terrace"
+            "has" => Ok(Condition::Has(crate::laendleimmo_scraper::normalize_feature(value))),
+            _ => Err(anyhow!("Unknown query field '{}'", field)),
+        }
+    }
+
+    fn matches(&self, property: &Property) -> bool {
+        match self {
+            Condition::Type(kind) => property.property_type.kind == *kind,
+            Condition::Listing(listing) => property.listing_type.to_string() == *listing,
+            Condition::Location(text) => property.location.to_string().to_lowercase().contains(text),
+            Condition::Name(text) => property.name.to_lowercase().contains(text),
+            Condition::Number(field, comparison, expected) => {
+                let actual = match field {
+                    NumericField::Price => property.price.euros(),
+                    NumericField::SizeLiving => property.parsed_living_size().map(|size| size.sqm),
+                    NumericField::SizeGround => property.parsed_ground_size().map(|size| size.sqm),
+                    NumericField::Rooms => property.rooms,
+                    NumericField::Commute => property.driving_distance_km,
+                };
+                // Properties without the value never match a comparison
+                actual.is_some_and(|actual| comparison.holds(actual, *expected))
+            }
+            Condition::Date(comparison, expected) => {
+                property.date.is_some_and(|date| comparison.holds(date, *expected))
+            }
+            Condition::Has(what) => match what.as_str() {
+                "coordinates" => property.coordinates.is_some(),
+                "address" => property.address.is_some(),
+                "price" => !property.price.is_unknown(),
+                "date" => property.date.is_some(),
+                "description" => property.description.is_some(),
+                feature => property.features.iter().any(|f| f.eq_ignore_ascii_case(feature)),
+            },
+        }
+    }
+}
+
+/// Split a query into words, keeping double-quoted parts together
+fn tokenize(query: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        return Err(anyhow!("Unterminated quote in query"));
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+impl PropertyQuery {
+    pub fn parse(query_str: &str) -> Result<PropertyQuery> {
+        let mut clauses: Vec<Vec<Term>> = Vec::new();
+        let mut negated = false;
+        let mut after_or = false;
+
+        for token in tokenize(query_str)? {
+            match token.as_str() {
+                "OR" => {
+                    if clauses.is_empty() || after_or || negated {
+                        return Err(anyhow!("OR must be placed between two terms"));
+                    }
+                    after_or = true;
+                }
+                "NOT" => negated = !negated,
+                _ => {
+                    let term = Term { negated, condition: Condition::parse(&token)? };
+                    match clauses.last_mut() {
+                        Some(clause) if after_or => clause.push(term),
+                        _ => clauses.push(vec![term]),
+                    }
+                    negated = false;
+                    after_or = false;
+                }
+            }
+        }
+
+        if after_or || negated {
+            return Err(anyhow!("Query ends with {}", if after_or { "OR" } else { "NOT" }));
+        }
+        if clauses.is_empty() {
+            return Err(anyhow!("Empty query"));
+        }
+        Ok(PropertyQuery { clauses })
+    }
+
+    pub fn matches(&self, property: &Property) -> bool {
+        self.clauses.iter().all(|clause| {
+            clause
+                .iter()
+                .any(|term| term.condition.matches(property) != term.negated)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Coordinates, ListingType, Location, Price};
+
+    fn property(kind: PropertyKind, price: &str, location: &str) -> Property {
+        Property {
+            url: "https://www.vol.at/test".to_string(),
+            internal_id: None,
+            page_fingerprint: None,
+            completeness_score: None,
+            name: "Test".to_string(),
+            price: Price::from_str(price),
+            location: Location::new(location),
+            property_type: kind.into(),
+            listing_type: ListingType::Sold,
+            unavailable_reason: None,
+            date: NaiveDate::from_ymd_opt(2024, 5, 1),
+            coordinates: None,
+            quality_score: None,
+            plot_polygon: None,
+            description: None,
+            features: vec!["sauna".to_string()],
+            driving_distance_km: None,
+            rooms: Some(4.0),
+            floor_level: None,
+            construction_year: None,
+            agency_name: None,
+            agency_phone: None,
+            address: None,
+            size_living: Some("120".to_string()),
+            size_ground: None,
+            first_seen: None,
+            last_seen: None,
+        }
+    }
+
+    #[test]
+    fn test_query_matches() {
+        let mut house = property(PropertyKind::House, "450000", "Feldkirch");
+        house.features.push("balcony".to_string());
+        let mut land = property(PropertyKind::Land, "650000", "Rankweil");
+        land.coordinates = Some(Coordinates::new(47.27, 9.64).unwrap());
+
+        let cases = [
+            ("type:house", true, false),
+            ("type:house OR type:land", true, true),
+            ("type:house OR type:land price:<500000", true, false),
+            ("type:house OR type:land has:coordinates", false, true),
+            ("NOT type:house", false, true),
+            ("location:feldkirch", true, false),
+            ("location:\"Feldkirch\" rooms:>=4 size:>100", true, false),
+            ("has:sauna date:>=2024-01-01 listing:sold", true, true),
+            ("has:terrace OR has:Balkon", true, false),
+            ("has:Dachterrasse", false, false),
+            ("commute:<20", false, false),
+            ("NOT has:coordinates OR price:>600000", true, true),
+        ];
+        for (query, matches_house, matches_land) in cases {
+            let parsed = PropertyQuery::parse(query).unwrap();
+            assert_eq!(parsed.matches(&house), matches_house, "{} on house", query);
+            assert_eq!(parsed.matches(&land), matches_land, "{} on land", query);
+        }
+    }
+
+    #[test]
+    fn test_query_parse_errors() {
+        for query in ["", "house", "type:castle", "price:<cheap", "OR type:house", "type:house OR", "NOT", "colour:red", "location:\"Feld"] {
+            assert!(PropertyQuery::parse(query).is_err(), "{:?} should not parse", query);
+        }
+    }
+
+    #[test]
+    fn test_query_tells_unknown_from_unavailable_type() {
+        let unknown = property(PropertyKind::Unknown, "450000", "Feldkirch");
+        let gone = property(PropertyKind::Unavailable, "450000", "Feldkirch");
+
+        let query = PropertyQuery::parse("type:unknown").unwrap();
+        assert!(query.matches(&unknown) && !query.matches(&gone));
+        let query = PropertyQuery::parse("type:unavailable").unwrap();
+        assert!(!query.matches(&unknown) && query.matches(&gone));
+    }
+}
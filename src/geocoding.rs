@@ -1,10 +1,14 @@
 use anyhow::Result;
 use reqwest::blocking::Client;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::thread;
 use std::time::Duration;
-use crate::models::Property;
+use crate::common_scraper::ScrapingOptions;
+use crate::models::{Coordinates, Property};
+use crate::tui::ScraperTUI;
+use crate::debug::Tracer;
+use crate::http_logging::SendLogged;
 use crate::debug_println;
 use crossterm::{
     cursor::MoveToPreviousLine,
@@ -18,6 +22,76 @@ use std::io;
 struct NominatimResponse {
     lat: String,
     lon: String,
+    #[serde(default)]
+    importance: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NominatimReverseResponse {
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    address: HashMap<String, String>,
+}
+
+impl NominatimReverseResponse {
+    /// Format as "Street 1, 6850 Dornbirn", falling back to Nominatim's display name
+    fn to_address(&self) -> Option<String> {
+        let street = self.address.get("road").map(|road| match self.address.get("house_number") {
+            Some(number) => format!("{} {}", road, number),
+            None => road.clone(),
+        });
+        let place = ["city", "town", "village", "municipality"]
+            .iter()
+            .find_map(|key| self.address.get(*key))
+            .map(|place| match self.address.get("postcode") {
+                Some(postcode) => format!("{} {}", postcode, place),
+                None => place.clone(),
+            });
+
+        match (street, place) {
+            (Some(street), Some(place)) => Some(format!("{}, {}", street, place)),
+            _ => self.display_name.clone(),
+        }
+    }
+}
+
+/// A successful geocoding lookup
+#[derive(Debug, Clone, Copy)]
+pub struct GeocodeResult {
+    pub lat: f64,
+    pub lng: f64,
+    /// Nominatim importance scaled to 0-100
+    pub quality_score: Option<u8>,
+}
+
+/// Which properties with existing coordinates should be geocoded again
+#[derive(Debug, Clone, Default)]
+pub struct ForceGeocodeOptions {
+    /// Re-geocode only these property URLs
+    pub urls: Vec<String>,
+    /// Re-geocode all properties with coordinates
+    pub all: bool,
+    /// Only re-geocode properties whose quality score is below this threshold
+    pub min_quality: Option<u8>,
+}
+
+impl ForceGeocodeOptions {
+    /// Whether any property with existing coordinates is selected for re-geocoding
+    pub fn is_active(&self) -> bool {
+        self.all || !self.urls.is_empty()
+    }
+
+    /// Whether a property with existing coordinates should be geocoded again
+    pub fn should_regeocode(&self, property: &Property) -> bool {
+        let forced = self.all || self.urls.iter().any(|url| url == &property.url);
+        let below_threshold = match (self.min_quality, property.quality_score) {
+            (Some(min_quality), Some(score)) => score < min_quality,
+            // Scores are unknown for coordinates that predate quality tracking
+            _ => true,
+        };
+        forced && below_threshold
+    }
 }
 
 pub struct GeocodingTUI {
@@ -136,12 +210,26 @@ impl GeocodingTUI {
         Ok(())
     }
 
+    /// Show how many addresses were answered from the cache vs. fetched from Nominatim
+    pub fn show_cache_stats(&self, hits: usize, misses: usize) -> io::Result<()> {
+        let lookups = hits + misses;
+        let hit_rate = (hits * 100).checked_div(lookups).unwrap_or(0);
+
+        execute!(
+            io::stdout(),
+            SetForegroundColor(Color::DarkGrey),
+            Print(format!("💾 Cache: {} hits, {} misses ({}% hit rate) | {} Nominatim requests\n",
+                         hits, misses, hit_rate, misses)),
+            ResetColor
+        )?;
+
+        Ok(())
+    }
+
     fn show_progress(&self) -> io::Result<()> {
-        let percentage = if self.total_properties > 0 {
-            (self.current_index * 100) / self.total_properties
-        } else {
-            0
-        };
+        let percentage = (self.current_index * 100)
+            .checked_div(self.total_properties)
+            .unwrap_or(0);
 
         let remaining = self.total_properties - self.current_index;
 
@@ -161,14 +249,17 @@ impl GeocodingTUI {
 
 pub struct Geocoder {
     client: Client,
-    cache: HashMap<String, Option<(f64, f64)>>,
+    cache: HashMap<String, Option<GeocodeResult>>,
+    reverse_cache: HashMap<String, Option<String>>,
     request_count: usize,
     rate_limit_delay_ms: u64,
+    pub cache_hits: usize,
+    pub cache_misses: usize,
 }
 
 impl Geocoder {
     pub fn new() -> Result<Self> {
-        let client = Client::builder()
+        let client = crate::utils::http_client_builder(&[])
             .user_agent("LaendleFinder/1.0 (Real Estate Scraper)")
             .timeout(Duration::from_secs(10))
             .build()?;
@@ -176,8 +267,11 @@ impl Geocoder {
         Ok(Geocoder {
             client,
             cache: HashMap::new(),
+            reverse_cache: HashMap::new(),
             request_count: 0,
             rate_limit_delay_ms: 0, // No delay - test maximum speed
+            cache_hits: 0,
+            cache_misses: 0,
         })
     }
 
@@ -189,7 +283,7 @@ impl Geocoder {
         self.request_count += 1;
     }
 
-    pub fn geocode_address(&mut self, address: &str) -> Result<Option<(f64, f64)>> {
+    pub fn geocode_address(&mut self, address: &str) -> Result<Option<GeocodeResult>> {
         if address.trim().is_empty() {
             return Ok(None);
         }
@@ -199,8 +293,10 @@ impl Geocoder {
         // Check cache first
         if let Some(cached_result) = self.cache.get(&cache_key) {
             debug_println!("Cache hit for address: {}", address);
+            self.cache_hits += 1;
             return Ok(*cached_result);
         }
+        self.cache_misses += 1;
 
         // Rate limit before making request
         self.rate_limit();
@@ -219,7 +315,8 @@ impl Geocoder {
             urlencoding::encode(&enhanced_address)
         );
 
-        let response = self.client.get(&url).send()?;
+        let _t = Tracer::new("fetch_geocode", 5000);
+        let response = self.client.get(&url).send_logged()?;
         
         if !response.status().is_success() {
             if response.status().as_u16() == 429 {
@@ -240,7 +337,10 @@ impl Geocoder {
             match (geocode_result.lat.parse::<f64>(), geocode_result.lon.parse::<f64>()) {
                 (Ok(lat), Ok(lng)) => {
                     debug_println!("Successfully geocoded: {} -> ({}, {})", address, lat, lng);
-                    Some((lat, lng))
+                    let quality_score = geocode_result
+                        .importance
+                        .map(|importance| (importance * 100.0).round().clamp(0.0, 100.0) as u8);
+                    Some(GeocodeResult { lat, lng, quality_score })
                 }
                 _ => {
                     debug_println!("Failed to parse coordinates for: {}", address);
@@ -257,29 +357,96 @@ impl Geocoder {
         Ok(result)
     }
 
-    pub fn geocode_property(&mut self, property: &mut Property) -> Result<bool> {
+    /// Look up the address at `(lat, lng)` using Nominatim's reverse endpoint
+    pub fn reverse_geocode(&mut self, coords: (f64, f64)) -> Result<Option<String>> {
+        let (lat, lng) = coords;
+        let cache_key = format!("{:.6},{:.6}", lat, lng);
+
+        if let Some(cached_result) = self.reverse_cache.get(&cache_key) {
+            debug_println!("Cache hit for coordinates: {}", cache_key);
+            self.cache_hits += 1;
+            return Ok(cached_result.clone());
+        }
+        self.cache_misses += 1;
+
+        self.rate_limit();
+
+        let url = format!(
+            "https://nominatim.openstreetmap.org/reverse?format=json&lat={}&lon={}&zoom=18&addressdetails=1",
+            lat, lng
+        );
+
+        let _t = Tracer::new("fetch_reverse_geocode", 5000);
+        let response = self.client.get(&url).send_logged()?;
+
+        if !response.status().is_success() {
+            if response.status().as_u16() == 429 {
+                println!("🚫 Rate limit hit (HTTP 429)! Adding {}ms delay for future requests.", self.rate_limit_delay_ms + 200);
+                self.rate_limit_delay_ms = (self.rate_limit_delay_ms + 200).min(2000); // Cap at 2 seconds
+                thread::sleep(Duration::from_secs(1));
+            }
+            debug_println!("HTTP error {}: {}", response.status(), url);
+            self.reverse_cache.insert(cache_key, None);
+            return Ok(None);
+        }
+
+        // Nominatim answers coordinates without a result with {"error": "..."}
+        let result = response.json::<NominatimReverseResponse>()?.to_address();
+        match &result {
+            Some(address) => debug_println!("Reverse geocoded: {} -> {}", cache_key, address),
+            None => debug_println!("No address found for: {}", cache_key),
+        }
+
+        self.reverse_cache.insert(cache_key, result.clone());
+        Ok(result)
+    }
+
+    /// Geocode a property; existing coordinates are only replaced when `force` is set
+    pub fn geocode_property(&mut self, property: &mut Property, force: bool) -> Result<bool> {
         // Skip if coordinates already exist
-        if property.coordinates.is_some() {
+        if property.coordinates.is_some() && !force {
             return Ok(false);
         }
 
-        // Try address first, then location
-        let address_to_geocode = if let Some(ref addr) = property.address {
-            if !addr.trim().is_empty() {
-                Some(addr.clone())
-            } else {
-                Some(property.location.clone())
-            }
+        // Try the address first, then the wider municipality search
+        let mut queries = Vec::new();
+        if let Some(address) = property.address.as_deref().filter(|address| !address.trim().is_empty()) {
+            queries.push(address.to_string());
+        }
+        let location = property.location.to_string();
+        if location.contains("Vorarlberg") {
+            queries.push(location);
         } else {
-            Some(property.location.clone())
-        };
+            queries.push(format!("{}, Vorarlberg", location));
+        }
+
+        for query in queries {
+            let Some(result) = self.geocode_address(&query)? else { continue };
+            // Nominatim happily returns a namesake elsewhere, e.g. a street in Vienna
+            if let Some(problem) = implausible_location(&result) {
+                debug_println!("Suspicious geocoding result for {} ({}): ({}, {}) is {}",
+                    property.name, query, result.lat, result.lng, problem);
+                continue;
+            }
 
-        if let Some(address) = address_to_geocode {
-            if let Some((lat, lng)) = self.geocode_address(&address)? {
-                property.coordinates = Some((lat, lng));
-                debug_println!("Geocoded property: {} -> ({}, {})", 
-                    property.name, lat, lng);
-                return Ok(true);
+            // Never overwrite existing coordinates with a worse result
+            if property.coordinates.is_some()
+                && result.quality_score < property.quality_score
+            {
+                debug_println!("Keeping existing coordinates of {} (quality {:?} > {:?})",
+                    property.name, property.quality_score, result.quality_score);
+                return Ok(false);
+            }
+
+            match Coordinates::new(result.lat, result.lng) {
+                Ok(coordinates) => {
+                    property.coordinates = Some(coordinates);
+                    property.quality_score = result.quality_score;
+                    debug_println!("Geocoded property: {} -> ({}, {})", 
+                        property.name, result.lat, result.lng);
+                    return Ok(true);
+                }
+                Err(e) => debug_println!("Discarding geocoding result for {}: {}", property.name, e),
             }
         }
 
@@ -287,13 +454,32 @@ impl Geocoder {
     }
 }
 
-pub fn geocode_properties(properties: &mut Vec<Property>, output_file: &str) -> Result<usize> {
+/// Why a geocoding result can't be the location of a Vorarlberg property, if it can't
+fn implausible_location(result: &GeocodeResult) -> Option<&'static str> {
+    if !crate::utils::is_coordinate_in_austria(result.lat, result.lng) {
+        Some("outside Austria")
+    } else if !crate::utils::is_coordinate_in_vorarlberg(result.lat, result.lng) {
+        Some("outside Vorarlberg")
+    } else {
+        None
+    }
+}
+
+pub fn geocode_properties(properties: &mut [Property], options: &ScrapingOptions) -> Result<usize> {
+    geocode_properties_with_options(properties, options, &ForceGeocodeOptions::default())
+}
+
+/// Geocode properties missing coordinates plus those selected for re-geocoding by `force`
+///
+/// Saves to the output file of `options` after every successful lookup.
+pub fn geocode_properties_with_options(properties: &mut [Property], options: &ScrapingOptions, force: &ForceGeocodeOptions) -> Result<usize> {
     let mut geocoder = Geocoder::new()?;
 
     // Collect indices of properties that need geocoding
     let indices_needing_geocode: Vec<usize> = properties.iter()
         .enumerate()
-        .filter(|(_, p)| p.coordinates.is_none() && (p.address.is_some() || !p.location.trim().is_empty()))
+        .filter(|(_, p)| p.coordinates.is_none() || force.should_regeocode(p))
+        .filter(|(_, p)| p.address.is_some() || !p.location.is_unknown())
         .map(|(i, _)| i)
         .collect();
 
@@ -302,6 +488,9 @@ pub fn geocode_properties(properties: &mut Vec<Property>, output_file: &str) ->
         println!("📍 No properties need geocoding");
         return Ok(0);
     }
+    if indices_needing_geocode.len() > LARGE_BATCH_SIZE && !force.is_active() {
+        return batch_geocode_with_progress(properties, &mut geocoder, options);
+    }
 
     let mut tui = GeocodingTUI::new(indices_needing_geocode.len());
     tui.start_geocoding()?;
@@ -309,17 +498,17 @@ pub fn geocode_properties(properties: &mut Vec<Property>, output_file: &str) ->
     for index in indices_needing_geocode {
         let address_to_show = {
             let property = &properties[index];
-            property.address.as_ref()
-                .unwrap_or(&property.location)
-                .clone()
+            property.address.clone()
+                .unwrap_or_else(|| property.location.to_string())
         };
         
         let property_name = properties[index].name.clone();
-        let geocoded = geocoder.geocode_property(&mut properties[index])?;
+        let regeocode = force.should_regeocode(&properties[index]);
+        let geocoded = geocoder.geocode_property(&mut properties[index], regeocode)?;
         
         // Save immediately after successful geocoding
         if geocoded {
-            crate::utils::save_properties_to_csv(properties, output_file)?;
+            crate::storage::save_properties(properties, &options.output_file, options.csv)?;
             debug_println!("Saved properties after geocoding: {}", property_name);
         }
         
@@ -327,10 +516,115 @@ pub fn geocode_properties(properties: &mut Vec<Property>, output_file: &str) ->
     }
 
     tui.complete_geocoding()?;
+    tui.show_cache_stats(geocoder.cache_hits, geocoder.cache_misses)?;
     Ok(tui.geocoded_count)
 }
 
-pub fn geocode_property_by_url(properties: &mut Vec<Property>, target_url: &str) -> Result<bool> {
+/// Geocoding runs with more properties than this show the scraper's sliding-window progress
+const LARGE_BATCH_SIZE: usize = 50;
+
+/// Geocode all properties missing coordinates with the `ScraperTUI` progress display
+///
+/// Each property is shown by the address or location it is geocoded from; a
+/// counter is appended where several properties share one. The output file of
+/// `options` is saved after every successful lookup. Returns the number of
+/// geocoded properties.
+pub fn batch_geocode_with_progress(properties: &mut [Property], geocoder: &mut Geocoder, options: &ScrapingOptions) -> Result<usize> {
+    let mut labels = HashSet::new();
+    let pending: Vec<(usize, String)> = properties.iter()
+        .enumerate()
+        .filter(|(_, p)| p.coordinates.is_none())
+        .filter(|(_, p)| p.address.is_some() || !p.location.is_unknown())
+        .map(|(i, p)| {
+            let address = p.address.clone().unwrap_or_else(|| p.location.to_string());
+            let label = (1..)
+                .map(|n| if n == 1 { address.clone() } else { format!("{} #{}", address, n) })
+                .find(|label| !labels.contains(label))
+                .unwrap_or(address);
+            labels.insert(label.clone());
+            (i, label)
+        })
+        .collect();
+
+    if pending.is_empty() {
+        println!("📍 No properties need geocoding");
+        return Ok(0);
+    }
+
+    let mut tui = ScraperTUI::for_options(options);
+    tui.set_title("Geocoding")?;
+    for (_, label) in &pending {
+        tui.add_property(label.clone())?;
+    }
+    tui.show_initial_progress_bar()?;
+
+    let mut geocoded_count = 0;
+    for (index, label) in &pending {
+        tui.start_scraping_property(label)?;
+        if geocoder.geocode_property(&mut properties[*index], false)? {
+            geocoded_count += 1;
+            crate::storage::save_properties(properties, &options.output_file, options.csv)?;
+            tui.complete_property(label)?;
+        } else {
+            tui.fail_property(label)?;
+        }
+    }
+
+    tui.show_final_summary(geocoded_count, properties.len())?;
+    GeocodingTUI::new(pending.len()).show_cache_stats(geocoder.cache_hits, geocoder.cache_misses)?;
+    Ok(geocoded_count)
+}
+
+/// Fill in missing addresses of properties that have coordinates
+///
+/// Saves to the output file of `options` after each found address, so an
+/// aborted run keeps its progress. A failed lookup is counted as not found and
+/// the next property is tried.
+pub fn geocode_properties_reverse(properties: &mut [Property], options: &ScrapingOptions) -> Result<usize> {
+    let mut geocoder = Geocoder::new()?;
+
+    let indices_needing_address: Vec<usize> = properties.iter()
+        .enumerate()
+        .filter(|(_, p)| p.coordinates.is_some() && p.address.is_none())
+        .map(|(i, _)| i)
+        .collect();
+
+    if indices_needing_address.is_empty() {
+        println!("📍 No properties need an address");
+        return Ok(0);
+    }
+
+    let mut tui = GeocodingTUI::new(indices_needing_address.len());
+    tui.start_geocoding()?;
+
+    for index in indices_needing_address {
+        let property = &mut properties[index];
+        let Some(coordinates) = property.coordinates else { continue };
+
+        let address = match geocoder.reverse_geocode((coordinates.lat, coordinates.lng)) {
+            Ok(address) => address,
+            Err(e) => {
+                debug_println!("Reverse geocoding failed for {}: {}", property.name, e);
+                None
+            }
+        };
+        let shown = address.clone().unwrap_or_default();
+        let found = address.is_some();
+        property.address = address;
+        let name = property.name.clone();
+
+        if found {
+            crate::storage::save_properties(properties, &options.output_file, options.csv)?;
+        }
+        tui.update_progress(found, &name, &shown)?;
+    }
+
+    tui.complete_geocoding()?;
+    tui.show_cache_stats(geocoder.cache_hits, geocoder.cache_misses)?;
+    Ok(tui.geocoded_count)
+}
+
+pub fn geocode_property_by_url(properties: &mut [Property], target_url: &str) -> Result<bool> {
     let mut geocoder = Geocoder::new()?;
     let tui = GeocodingTUI::new(1);
     
@@ -342,11 +636,42 @@ pub fn geocode_property_by_url(properties: &mut Vec<Property>, target_url: &str)
             return Ok(false);
         }
         
-        let geocoded = geocoder.geocode_property(property)?;
+        let geocoded = geocoder.geocode_property(property, false)?;
         tui.single_property_result(geocoded, &property.name)?;
         return Ok(geocoded);
     }
     
     debug_println!("Property not found in CSV: {}", target_url);
     Ok(false)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_response_to_address() {
+        let response: NominatimReverseResponse = serde_json::from_str(r#"{
+            "display_name": "5, Marktstraße, Dornbirn, Vorarlberg, 6850, Österreich",
+            "address": {"house_number": "5", "road": "Marktstraße", "town": "Dornbirn", "postcode": "6850"}
+        }"#).unwrap();
+        assert_eq!(response.to_address().as_deref(), Some("Marktstraße 5, 6850 Dornbirn"));
+
+        let response: NominatimReverseResponse = serde_json::from_str(r#"{
+            "display_name": "Bürserberg, Bezirk Bludenz, Vorarlberg, Österreich",
+            "address": {"village": "Bürserberg"}
+        }"#).unwrap();
+        assert_eq!(response.to_address().as_deref(), Some("Bürserberg, Bezirk Bludenz, Vorarlberg, Österreich"));
+
+        let response: NominatimReverseResponse = serde_json::from_str(r#"{"error": "Unable to geocode"}"#).unwrap();
+        assert_eq!(response.to_address(), None);
+    }
+
+    #[test]
+    fn test_implausible_location() {
+        let result = |lat, lng| GeocodeResult { lat, lng, quality_score: None };
+        assert_eq!(implausible_location(&result(47.4125, 9.7417)), None);
+        assert_eq!(implausible_location(&result(48.2082, 16.3738)), Some("outside Vorarlberg"));
+        assert_eq!(implausible_location(&result(52.52, 13.405)), Some("outside Austria"));
+    }
+}
@@ -0,0 +1,423 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use std::io::{Seek, SeekFrom, Write};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::debug::PerformanceCounter;
+use crate::debug_eprintln;
+
+/// Name and value of an HTTP header or query parameter
+#[derive(Debug, Clone, Serialize)]
+pub struct HarPair {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub headers: Vec<HarPair>,
+    pub query_string: Vec<HarPair>,
+    pub cookies: Vec<HarPair>,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    /// Response size from `Content-Length`, -1 if the server didn't send one
+    pub size: i64,
+    pub mime_type: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub headers: Vec<HarPair>,
+    pub cookies: Vec<HarPair>,
+    pub content: HarContent,
+    /// Final URL if redirects were followed, empty otherwise
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+/// Milliseconds spent per phase; only the wait for the response headers is measured
+#[derive(Debug, Clone, Serialize)]
+pub struct HarTimings {
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+}
+
+/// One request and its response as recorded in a HAR file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: DateTime<Utc>,
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: serde_json::Value,
+    pub timings: HarTimings,
+}
+
+/// Closes the entries array and the document after the last entry
+const HAR_TRAILER: &str = "\n]}}\n";
+
+/// Records HTTP exchanges and writes them to a HAR 1.2 file
+///
+/// Each entry is appended in place of the closing brackets, which are then
+/// written again, so the file is complete even if a scraper aborts halfway
+/// without rewriting earlier entries.
+#[derive(Debug, Clone)]
+pub struct HarLogger {
+    pub entry_count: usize,
+    pub output_path: String,
+}
+
+impl HarLogger {
+    pub fn new(output_path: &str) -> Self {
+        HarLogger { entry_count: 0, output_path: output_path.to_string() }
+    }
+
+    /// Start of the HAR document up to the opening bracket of its entries
+    fn har_prefix() -> String {
+        let creator = serde_json::json!({ "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") });
+        format!("{{\"log\":{{\"version\":\"1.2\",\"creator\":{},\"entries\":[\n", creator)
+    }
+
+    pub fn record(&mut self, entry: HarEntry) -> Result<()> {
+        let entry = serde_json::to_string(&entry)?;
+        let write = || -> std::io::Result<()> {
+            if self.entry_count == 0 {
+                return std::fs::write(&self.output_path, format!("{}{}{}", Self::har_prefix(), entry, HAR_TRAILER));
+            }
+            let mut file = std::fs::OpenOptions::new().write(true).open(&self.output_path)?;
+            file.seek(SeekFrom::End(-(HAR_TRAILER.len() as i64)))?;
+            file.write_all(format!(",\n{}{}", entry, HAR_TRAILER).as_bytes())
+        };
+        write().with_context(|| format!("Failed to write HAR file: {}", self.output_path))?;
+        self.entry_count += 1;
+        Ok(())
+    }
+}
+
+/// A redirect reqwest followed, as seen by `redirect_policy`
+#[derive(Debug, Clone)]
+struct RedirectHop {
+    /// URL of the request the redirect chain started with
+    origin: reqwest::Url,
+    from: reqwest::Url,
+    to: reqwest::Url,
+    status: reqwest::StatusCode,
+}
+
+/// Hops followed by requests in flight, taken by `send_logged` once they complete
+static REDIRECT_HOPS: Mutex<Vec<RedirectHop>> = Mutex::new(Vec::new());
+
+/// Redirect policy of all clients, following up to 10 redirects like reqwest's default
+///
+/// While requests are logged, it notes each hop so that it gets its own HAR entry.
+pub fn redirect_policy() -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(|attempt| {
+        if attempt.previous().len() >= 10 {
+            return attempt.error("too many redirects");
+        }
+        if let (Some(origin), Some(from)) = (attempt.previous().first(), attempt.previous().last()) {
+            if is_logging() {
+                let hop = RedirectHop {
+                    origin: origin.clone(),
+                    from: from.clone(),
+                    to: attempt.url().clone(),
+                    status: attempt.status(),
+                };
+                REDIRECT_HOPS.lock().unwrap().push(hop);
+            }
+        }
+        attempt.follow()
+    })
+}
+
+/// Remove and return the hops of the redirect chain that started at `origin`
+fn take_redirect_hops(origin: &reqwest::Url) -> Vec<RedirectHop> {
+    let mut hops = REDIRECT_HOPS.lock().unwrap();
+    let (taken, kept) = hops.drain(..).partition(|hop| &hop.origin == origin);
+    *hops = kept;
+    taken
+}
+
+/// HAR entry of a redirect response, whose headers and timing reqwest doesn't expose
+fn redirect_entry(method: &str, hop: &RedirectHop, request_headers: &HeaderMap, started: DateTime<Utc>) -> HarEntry {
+    HarEntry {
+        started_date_time: started,
+        time: 0.0,
+        request: HarRequest {
+            method: method.to_string(),
+            url: hop.from.to_string(),
+            http_version: String::new(),
+            headers: header_pairs(request_headers),
+            query_string: query_pairs(&hop.from),
+            cookies: Vec::new(),
+            headers_size: -1,
+            body_size: 0,
+        },
+        response: HarResponse {
+            status: hop.status.as_u16(),
+            status_text: hop.status.canonical_reason().unwrap_or_default().to_string(),
+            http_version: String::new(),
+            headers: Vec::new(),
+            cookies: Vec::new(),
+            content: HarContent { size: -1, mime_type: String::new() },
+            redirect_url: hop.to.to_string(),
+            headers_size: -1,
+            body_size: -1,
+        },
+        cache: serde_json::json!({}),
+        timings: HarTimings { send: 0.0, wait: 0.0, receive: 0.0 },
+    }
+}
+
+static HAR_LOGGER: Mutex<Option<HarLogger>> = Mutex::new(None);
+
+/// Record all following requests sent with `send_logged` into a HAR file at `path`
+pub fn set_har_output(path: &str) {
+    *HAR_LOGGER.lock().unwrap() = Some(HarLogger::new(path));
+}
+
+fn is_logging() -> bool {
+    HAR_LOGGER.lock().unwrap().is_some()
+}
+
+fn header_pairs(headers: &HeaderMap) -> Vec<HarPair> {
+    headers
+        .iter()
+        .map(|(name, value)| HarPair {
+            name: name.to_string(),
+            value: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+        })
+        .collect()
+}
+
+fn query_pairs(url: &reqwest::Url) -> Vec<HarPair> {
+    url.query_pairs()
+        .map(|(name, value)| HarPair { name: name.into_owned(), value: value.into_owned() })
+        .collect()
+}
+
+/// Build the HAR entry of a request and the response it got
+///
+/// `request_headers` are the headers set on the request itself; the client's
+/// default headers are added by reqwest only when sending.
+pub fn har_entry(
+    method: &str,
+    url: &reqwest::Url,
+    request_headers: &HeaderMap,
+    response: &Response,
+    started: DateTime<Utc>,
+    elapsed_ms: f64,
+) -> HarEntry {
+    let http_version = format!("{:?}", response.version());
+    let header_value = |name: reqwest::header::HeaderName| {
+        response.headers().get(name).and_then(|value| value.to_str().ok()).map(str::to_string)
+    };
+    let size = response.content_length().map_or(-1, |length| length as i64);
+    HarEntry {
+        started_date_time: started,
+        time: elapsed_ms,
+        request: HarRequest {
+            method: method.to_string(),
+            url: url.to_string(),
+            http_version: http_version.clone(),
+            headers: header_pairs(request_headers),
+            query_string: query_pairs(url),
+            cookies: Vec::new(),
+            headers_size: -1,
+            body_size: 0,
+        },
+        response: HarResponse {
+            status: response.status().as_u16(),
+            status_text: response.status().canonical_reason().unwrap_or_default().to_string(),
+            http_version,
+            headers: header_pairs(response.headers()),
+            cookies: Vec::new(),
+            content: HarContent {
+                size,
+                mime_type: header_value(reqwest::header::CONTENT_TYPE).unwrap_or_default(),
+            },
+            redirect_url: if response.url() != url { response.url().to_string() } else { String::new() },
+            headers_size: -1,
+            body_size: size,
+        },
+        cache: serde_json::json!({}),
+        timings: HarTimings { send: 0.0, wait: elapsed_ms, receive: 0.0 },
+    }
+}
+
+/// Send a request, recording it in the HAR file if `--log-requests` is set
+pub trait SendLogged {
+    fn send_logged(self) -> reqwest::Result<Response>;
+    /// Like `send_logged`, recording the outcome and response time in `performance`
+    ///
+    /// Non-2xx responses count as failures with their status code. The time
+    /// is measured until the response headers arrive, without reading the body.
+    fn send_counted(self, performance: &PerformanceCounter) -> reqwest::Result<Response>;
+}
+
+impl SendLogged for RequestBuilder {
+    fn send_logged(self) -> reqwest::Result<Response> {
+        if !is_logging() {
+            return self.send();
+        }
+        // Requests without a streaming body can be cloned to read what is sent
+        let Some(request) = self.try_clone().and_then(|builder| builder.build().ok()) else {
+            return self.send();
+        };
+
+        let started = Utc::now();
+        let timer = Instant::now();
+        let result = self.send();
+        let hops = take_redirect_hops(request.url());
+        let response = result?;
+
+        let method = request.method().as_str();
+        let mut entries: Vec<HarEntry> =
+            hops.iter().map(|hop| redirect_entry(method, hop, request.headers(), started)).collect();
+        // With the hops logged separately, the last entry is the request to the final URL
+        let final_url = hops.last().map_or(request.url(), |hop| &hop.to);
+        entries.push(har_entry(
+            method,
+            final_url,
+            request.headers(),
+            &response,
+            started,
+            timer.elapsed().as_secs_f64() * 1000.0,
+        ));
+
+        if let Some(logger) = HAR_LOGGER.lock().unwrap().as_mut() {
+            for entry in entries {
+                if let Err(e) = logger.record(entry) {
+                    debug_eprintln!("Failed to log request to {}: {}", request.url(), e);
+                }
+            }
+        }
+        Ok(response)
+    }
+
+    fn send_counted(self, performance: &PerformanceCounter) -> reqwest::Result<Response> {
+        let url = self.try_clone().and_then(|builder| builder.build().ok()).map(|request| request.url().clone());
+        let timer = Instant::now();
+        let result = self.send_logged();
+        let domain = |fallback: Option<&reqwest::Url>| {
+            url.as_ref().or(fallback).map(|url| crate::utils::domain_from_url(url.as_str()).to_string())
+        };
+        match &result {
+            Ok(response) => {
+                let domain = domain(Some(response.url())).unwrap_or_default();
+                if response.status().is_success() {
+                    performance.record_success(&domain, timer.elapsed());
+                } else {
+                    performance.record_failure(&domain, Some(response.status().as_u16()));
+                }
+            }
+            Err(e) => {
+                if let Some(domain) = domain(e.url()) {
+                    performance.record_failure(&domain, e.status().map(|status| status.as_u16()));
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_har_logger_writes_har_document() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-test-{}.har", std::process::id()));
+        let path = path.to_str().unwrap();
+        let entry = HarEntry {
+            started_date_time: Utc::now(),
+            time: 120.0,
+            request: HarRequest {
+                method: "GET".to_string(),
+                url: "https://www.vol.at/themen/grund-und-boden?page=2".to_string(),
+                http_version: "HTTP/1.1".to_string(),
+                headers: Vec::new(),
+                query_string: vec![HarPair { name: "page".to_string(), value: "2".to_string() }],
+                cookies: Vec::new(),
+                headers_size: -1,
+                body_size: 0,
+            },
+            response: HarResponse {
+                status: 200,
+                status_text: "OK".to_string(),
+                http_version: "HTTP/1.1".to_string(),
+                headers: Vec::new(),
+                cookies: Vec::new(),
+                content: HarContent { size: 512, mime_type: "text/html".to_string() },
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size: 512,
+            },
+            cache: serde_json::json!({}),
+            timings: HarTimings { send: 0.0, wait: 120.0, receive: 0.0 },
+        };
+
+        let hop = RedirectHop {
+            origin: "http://vol.at/themen/grund-und-boden".parse().unwrap(),
+            from: "http://vol.at/themen/grund-und-boden".parse().unwrap(),
+            to: "https://www.vol.at/themen/grund-und-boden?page=2".parse().unwrap(),
+            status: reqwest::StatusCode::MOVED_PERMANENTLY,
+        };
+
+        let mut logger = HarLogger::new(path);
+        logger.record(redirect_entry("GET", &hop, &HeaderMap::new(), Utc::now())).unwrap();
+        logger.record(entry).unwrap();
+        let har: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(har["log"]["entries"].as_array().unwrap().len(), 2);
+        let redirect = &har["log"]["entries"][0];
+        assert_eq!(redirect["request"]["url"], "http://vol.at/themen/grund-und-boden");
+        assert_eq!(redirect["response"]["status"], 301);
+        assert_eq!(redirect["response"]["redirectURL"], "https://www.vol.at/themen/grund-und-boden?page=2");
+        let entry = &har["log"]["entries"][1];
+        assert_eq!(entry["request"]["queryString"][0]["name"], "page");
+        assert_eq!(entry["response"]["content"]["mimeType"], "text/html");
+        assert_eq!(entry["response"]["redirectURL"], "");
+        assert!(entry["startedDateTime"].is_string());
+    }
+
+    #[test]
+    fn test_send_counted_records_status_codes() {
+        let performance = PerformanceCounter::new();
+        let client = crate::utils::http_client_builder(&[]).build().unwrap();
+        let (url, _) = crate::utils::serve_once("200 OK", "ok");
+        client.get(&url).send_counted(&performance).unwrap();
+        let (url, _) = crate::utils::serve_once("404 Not Found", "gone");
+        client.get(&url).send_counted(&performance).unwrap();
+
+        let stats = performance.stats();
+        let (domain, localhost) = &stats[0];
+        assert_eq!(domain, "127.0.0.1");
+        assert_eq!(localhost.total(), 2);
+        assert_eq!(localhost.success_rate(), 0.5);
+        assert_eq!(localhost.most_common_errors(), vec![(404, 1)]);
+    }
+}
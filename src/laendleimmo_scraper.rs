@@ -1,22 +1,53 @@
-use crate::models::{ListingType, Property, PropertyType};
+use crate::common_scraper::ScrapingContext;
+use crate::models::{Coordinates, ListingType, Location, Price, Property, PropertyKind, PropertyType, UnavailableReason};
+use crate::debug::Tracer;
+use crate::error::ScrapeError;
+use crate::http_logging::SendLogged;
 use crate::tui::ScraperTUI;
-use crate::utils::{sanitize_url, get_random_user_agent};
+use crate::utils::{self, sanitize_url};
 use crate::{debug_println, debug_eprintln};
 use anyhow::{Context, Result};
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
 use regex::Regex;
 use scraper::{Html, Selector};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
 
 const BASE_URL: &str = "https://www.laendleimmo.at/kaufobjekt";
 // const BASE_URL: &str = "https://www.laendleimmo.at/kaufobjekt/grundstuck/baugrundstuck";
 // const BASE_URL: &str = "https://www.laendleimmo.at/kaufobjekt/haus/einfamilienhaus";
 
-pub fn scrape_new_urls_until_no_new_found(mut tui: Option<&mut ScraperTUI>, existing_urls: &HashSet<String>) -> Result<Vec<String>> {
-    use std::collections::HashSet;
-    
+/// Categories below `/kaufobjekt/` whose listings the scraper classifies
+pub const KNOWN_CATEGORIES: &[&str] = &["wohnung", "haus", "grundstuck", "grundstueck", "gewerbe", "gewerbeobjekt"];
+
+/// Districts of Vorarlberg as they appear in laendleimmo.at URLs
+pub const VORARLBERG_DISTRICTS: &[&str] = &["bludenz", "bregenz", "dornbirn", "feldkirch"];
+
+/// Whether a property URL lies in one of the given districts
+///
+/// laendleimmo.at URLs encode the district as `/vorarlberg/{district}/`.
+pub fn url_matches_location(url: &str, districts: &[String]) -> bool {
+    let url = url.to_lowercase();
+    districts
+        .iter()
+        .any(|district| url.contains(&format!("/vorarlberg/{}/", district.to_lowercase())))
+}
+
+/// Whether a URL is a laendleimmo.at property page rather than a listing or info page
+pub fn is_property_url(url: &str) -> bool {
+    utils::domain_from_url(url) == "laendleimmo.at" && url.contains("/immobilien/") && url.contains("/vorarlberg/")
+}
+
+/// Gather property URLs matching `filters` until 5 consecutive listing pages yield nothing new
+pub fn scrape_new_urls_until_no_new_found(
+    context: &mut ScrapingContext,
+    filters: &ListingFilters,
+    mut tui: Option<&mut ScraperTUI>,
+    existing_urls: &HashSet<String>,
+) -> Result<Vec<String>> {
     let mut all_property_urls = Vec::new();
     let mut seen_urls = HashSet::new();
+    let mut previous_page_urls: Option<HashSet<String>> = None;
     let mut new_count = 0;
     let mut known_count = 0;
     let mut pages_without_new = 0;
@@ -27,21 +58,24 @@ pub fn scrape_new_urls_until_no_new_found(mut tui: Option<&mut ScraperTUI>, exis
     }
 
     loop {
-        let page_url = if current_page == 1 {
-            BASE_URL.to_string()
-        } else {
-            format!("{}?page={}", BASE_URL, current_page)
-        };
+        let page_url = listing_page_url(context, filters, current_page);
 
         debug_println!("Scraping listing page: {}", page_url);
 
-        match scrape_listing_page(&page_url) {
+        match scrape_listing_page(context, &page_url) {
             Ok(urls) => {
                 if urls.is_empty() {
                     debug_println!("No properties found on page {}, stopping", current_page);
                     break;
                 }
 
+                let page_urls: HashSet<String> = urls.iter().cloned().collect();
+                if previous_page_urls.as_ref() == Some(&page_urls) {
+                    debug_println!("⚠ Page {} is identical to the previous page, assuming end of listing", current_page);
+                    break;
+                }
+                previous_page_urls = Some(page_urls);
+
                 let mut new_urls_on_page = 0;
                 for url in urls {
                     if seen_urls.insert(url.clone()) {
@@ -91,6 +125,7 @@ pub fn scrape_new_urls_until_no_new_found(mut tui: Option<&mut ScraperTUI>, exis
         }
     }
 
+    filters.retain_property_kind(&mut all_property_urls);
     if let Some(tui) = tui.as_mut() {
         tui.finish_gathering(all_property_urls.len())?;
     }
@@ -98,34 +133,63 @@ pub fn scrape_new_urls_until_no_new_found(mut tui: Option<&mut ScraperTUI>, exis
     Ok(all_property_urls)
 }
 
-pub fn scrape_all_listing_pages(max_pages: usize, mut tui: Option<&mut ScraperTUI>, existing_urls: &HashSet<String>) -> Result<Vec<String>> {
-    use std::collections::HashSet;
-    
+/// Gather property URLs from the first `max_pages` listing pages matching `filters`
+///
+/// With a `filters.property_kind` only URLs classified as that kind are kept.
+pub fn scrape_all_listing_pages(
+    context: &mut ScrapingContext,
+    max_pages: usize,
+    filters: &ListingFilters,
+    tui: Option<&mut ScraperTUI>,
+    existing_urls: &HashSet<String>,
+) -> Result<Vec<String>> {
+    let mut urls = scrape_new_page_range(context, 1, max_pages, filters, tui, existing_urls)?;
+    filters.retain_property_kind(&mut urls);
+    Ok(urls)
+}
+
+/// Gather property URLs from listing pages `start_page..=end_page`
+///
+/// URLs are deduplicated across pages. laendleimmo.at keeps serving the last
+/// page for page numbers beyond the end of the listing, so gathering stops as
+/// soon as a page contains exactly the same URLs as the previous one.
+pub fn scrape_new_page_range(
+    context: &mut ScrapingContext,
+    start_page: usize,
+    end_page: usize,
+    filters: &ListingFilters,
+    mut tui: Option<&mut ScraperTUI>,
+    existing_urls: &HashSet<String>,
+) -> Result<Vec<String>> {
     let mut all_property_urls = Vec::new();
     let mut seen_urls = HashSet::new();
+    let mut previous_page_urls: Option<HashSet<String>> = None;
     let mut new_count = 0;
     let mut known_count = 0;
+    let total_pages = end_page.saturating_sub(start_page) + 1;
 
     if let Some(tui) = tui.as_mut() {
-        tui.start_gathering(max_pages)?;
+        tui.start_gathering(total_pages)?;
     }
 
-    for page in 1..=max_pages {
-        let page_url = if page == 1 {
-            BASE_URL.to_string()
-        } else {
-            format!("{}?page={}", BASE_URL, page)
-        };
+    for page in start_page..=end_page {
+        let page_url = listing_page_url(context, filters, page);
 
         debug_println!("Scraping listing page: {}", page_url);
 
-        match scrape_listing_page(&page_url) {
+        match scrape_listing_page(context, &page_url) {
             Ok(urls) => {
                 if urls.is_empty() {
                     debug_println!("No more properties found on page {}, stopping", page);
                     break;
                 }
-                
+
+                let page_urls: HashSet<String> = urls.iter().cloned().collect();
+                if previous_page_urls.as_ref() == Some(&page_urls) {
+                    debug_println!("⚠ Page {} is identical to the previous page, assuming end of listing", page);
+                    break;
+                }
+
                 let mut new_urls_added = 0;
                 for url in urls {
                     if seen_urls.insert(url.clone()) {
@@ -138,11 +202,12 @@ pub fn scrape_all_listing_pages(max_pages: usize, mut tui: Option<&mut ScraperTU
                         }
                     }
                 }
+                previous_page_urls = Some(page_urls);
                 
                 debug_println!("Page {}: added {} new URLs, {} total unique", page, new_urls_added, all_property_urls.len());
                 
                 if let Some(tui) = tui.as_mut() {
-                    tui.update_gathering_progress(page, max_pages, all_property_urls.len(), new_count, known_count)?;
+                    tui.update_gathering_progress(page - start_page + 1, total_pages, all_property_urls.len(), new_count, known_count)?;
                 }
             }
             Err(e) => {
@@ -157,7 +222,7 @@ pub fn scrape_all_listing_pages(max_pages: usize, mut tui: Option<&mut ScraperTU
         }
         
         // Add delay between listing page requests to avoid rate limiting
-        if page < max_pages {
+        if page < end_page {
             std::thread::sleep(std::time::Duration::from_millis(1500));
         }
     }
@@ -169,22 +234,170 @@ pub fn scrape_all_listing_pages(max_pages: usize, mut tui: Option<&mut ScraperTU
     Ok(all_property_urls)
 }
 
-pub fn scrape_listing_page(url: &str) -> Result<Vec<String>> {
+fn listing_page_url(context: &mut ScrapingContext, filters: &ListingFilters, page: usize) -> String {
+    detect_and_build_page_url(context, &build_search_url(BASE_URL, filters), page)
+}
+
+/// Search filters laendleimmo.at applies to its listing pages
+///
+/// The property kind is matched against the listing URLs. Sizes are left to `--min-size-living` and `--max-size-living` after
+/// scraping, the server would also drop listings without a stated size.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListingFilters {
+    /// Land has no rooms, so the room count is not sent for it
+    pub property_kind: Option<PropertyKind>,
+    pub min_rooms: Option<u32>,
+    pub max_price: Option<u64>,
+}
+
+impl ListingFilters {
+    /// Drop URLs not classified as `property_kind`, the listing pages mix all kinds
+    pub fn retain_property_kind(&self, urls: &mut Vec<String>) {
+        if let Some(kind) = self.property_kind {
+            urls.retain(|url| PropertyType::from_url(url).is_some_and(|classified| classified.kind == kind));
+            debug_println!("Kept {} URLs of type {}", urls.len(), kind);
+        }
+    }
+
+    /// URL query parameters of all set filters
+    fn query_parameters(&self) -> Vec<(&'static str, String)> {
+        let is_land = self.property_kind == Some(PropertyKind::Land);
+        let mut params = Vec::new();
+        if let Some(rooms) = self.min_rooms.filter(|_| !is_land) {
+            params.push(("zimmer_min", rooms.to_string()));
+        }
+        if let Some(price) = self.max_price {
+            params.push(("preismax", price.to_string()));
+        }
+        params
+    }
+}
+
+/// Append the URL-encoded query parameters of `filters` to a listing URL
+pub fn build_search_url(base: &str, filters: &ListingFilters) -> String {
+    let query = filters
+        .query_parameters()
+        .into_iter()
+        .map(|(name, value)| format!("{}={}", name, urlencoding::encode(&value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    if query.is_empty() {
+        base.to_string()
+    } else if base.contains('?') {
+        format!("{}&{}", base, query)
+    } else {
+        format!("{}?{}", base, query)
+    }
+}
+
+/// Pagination URL formats laendleimmo.at has used, most recent first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageFormat {
+    /// `{base}?page=N`
+    QueryParameter,
+    /// `{base}/p/N`
+    PathSegment,
+}
+
+const PAGE_FORMATS: [PageFormat; 2] = [PageFormat::QueryParameter, PageFormat::PathSegment];
+
+impl PageFormat {
+    /// Build the URL of `page`, keeping any search query of `base_url`
+    pub fn build(&self, base_url: &str, page: usize) -> String {
+        let (path, query) = match base_url.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (base_url, None),
+        };
+        match (self, query) {
+            (PageFormat::QueryParameter, Some(query)) => format!("{}?{}&page={}", path, query, page),
+            (PageFormat::QueryParameter, None) => format!("{}?page={}", path, page),
+            (PageFormat::PathSegment, Some(query)) => format!("{}/p/{}?{}", path.trim_end_matches('/'), page, query),
+            (PageFormat::PathSegment, None) => format!("{}/p/{}", path.trim_end_matches('/'), page),
+        }
+    }
+}
+
+/// Pagination format used for the rest of this run once it was detected
+static DETECTED_PAGE_FORMAT: OnceLock<PageFormat> = OnceLock::new();
+
+/// Build the URL of a listing page, detecting the pagination format on first use
+///
+/// The first page is the base URL itself. For later pages every known format
+/// is tried until one serves other listings than the first page; that format
+/// is then used for the rest of the run. If none does, the most recent format
+/// is used unchecked for the rest of the run instead of probing again.
+pub fn detect_and_build_page_url(context: &mut ScrapingContext, base_url: &str, page: usize) -> String {
+    if page <= 1 {
+        return base_url.to_string();
+    }
+    if let Some(format) = DETECTED_PAGE_FORMAT.get() {
+        return format.build(base_url, page);
+    }
+
+    let format = try_all_page_formats(context, base_url, page).unwrap_or_else(|| {
+        debug_println!("No pagination format worked for {}, using the default", base_url);
+        PAGE_FORMATS[0]
+    });
+    DETECTED_PAGE_FORMAT.get_or_init(|| format).build(base_url, page)
+}
+
+/// Return the first pagination format whose `page` links to other properties than the first page
+///
+/// The site may answer an unknown page URL with the first page or an empty
+/// page instead of a 404. All probes wait for the rate limiter of `context`.
+pub fn try_all_page_formats(context: &mut ScrapingContext, base_url: &str, page: usize) -> Option<PageFormat> {
+    context.wait_for_domain("laendleimmo.at");
+    let first_page: HashSet<String> = match scrape_listing_page(context, base_url) {
+        Ok(urls) => urls.into_iter().collect(),
+        Err(e) => {
+            debug_println!("Failed to fetch the first listing page to detect the pagination format: {}", e);
+            return None;
+        }
+    };
+
+    PAGE_FORMATS.into_iter().find(|format| {
+        let url = format.build(base_url, page);
+        let _t = Tracer::new("probe_page_format", 5000);
+        context.wait_for_domain("laendleimmo.at");
+        match scrape_listing_page(context, &url) {
+            Ok(urls) if is_other_listing_page(&first_page, &urls) => {
+                debug_println!("Pagination format {:?} works: {}", format, url);
+                true
+            }
+            Ok(_) => {
+                debug_println!("Pagination format {:?} serves no other listings than the first page: {}", format, url);
+                false
+            }
+            Err(e) => {
+                debug_println!("Pagination format {:?} failed: {}", format, e);
+                false
+            }
+        }
+    })
+}
+
+/// Whether a listing page links to properties and not just the ones of the first page
+fn is_other_listing_page(first_page: &HashSet<String>, urls: &[String]) -> bool {
+    !urls.is_empty() && urls.iter().any(|url| !first_page.contains(url))
+}
+
+pub fn scrape_listing_page(context: &mut ScrapingContext, url: &str) -> Result<Vec<String>> {
     debug_println!("Fetching listing page: {}", url);
 
-    let client = reqwest::blocking::Client::builder()
+    let client = context
+        .http_client_builder()
         .timeout(std::time::Duration::from_secs(30))
         .build()
         .context("Failed to create HTTP client")?;
 
+    let fetch_trace = Tracer::new("fetch_listing_page", 5000);
     let response = client
         .get(url)
-        .header("User-Agent", get_random_user_agent())
+        .header("User-Agent", context.next_user_agent())
         .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,*/*;q=0.8")
-        .header("Accept-Language", "en-US,en;q=0.5")
         .header("Connection", "keep-alive")
         .header("Upgrade-Insecure-Requests", "1")
-        .send()
+        .send_counted(context.performance())
         .context("Failed to fetch listing page")?;
 
     // Check for rate limiting using HTTP status code (more reliable)
@@ -206,6 +419,7 @@ pub fn scrape_listing_page(url: &str) -> Result<Vec<String>> {
     }
 
     let body = response.text().context("Failed to read response body")?;
+    drop(fetch_trace);
     
     // Fallback: check body content for rate limiting indicators
     if is_rate_limited(&body) {
@@ -213,7 +427,9 @@ pub fn scrape_listing_page(url: &str) -> Result<Vec<String>> {
         return Err(anyhow::anyhow!("Rate limiting detected in response content"));
     }
     
+    let parse_trace = Tracer::new("parse_listing_page", 1000);
     let document = Html::parse_document(&body);
+    drop(parse_trace);
 
     // Look for property links in the listing page
     // Based on the URL structure: /immobilien/{type}/{subtype}/vorarlberg/{district}/{id}
@@ -246,27 +462,187 @@ pub fn scrape_listing_page(url: &str) -> Result<Vec<String>> {
     Ok(property_urls)
 }
 
-pub fn scrape_property_page(url: &str) -> Result<Property> {
+/// Check that the listing page responds in time and still links to properties
+///
+/// Listings carry no JSON data node; the property links are what the listing
+/// scraper depends on.
+pub fn check_listing_page(context: &mut ScrapingContext) -> Result<()> {
+    let body = crate::common_scraper::fetch_for_health_check(context, BASE_URL)?;
+    let document = Html::parse_document(&body);
+    let link_selector = Selector::parse("a[href*='/immobilien/']").unwrap();
+    let has_property_links = document
+        .select(&link_selector)
+        .filter_map(|link| link.value().attr("href"))
+        .any(|href| href.contains("/vorarlberg/"));
+    if !has_property_links {
+        return Err(anyhow::anyhow!("No property links found on {}", BASE_URL));
+    }
+    warn_about_new_categories(&find_new_categories(&body, BASE_URL));
+    Ok(())
+}
+
+/// URLs of `/kaufobjekt/` categories in the navigation of a page that aren't in `KNOWN_CATEGORIES`
+///
+/// Each category is returned once, as the URL of its top level.
+pub fn find_new_categories(body: &str, base_url: &str) -> Vec<String> {
+    let Ok(base) = reqwest::Url::parse(base_url) else {
+        return Vec::new();
+    };
+    let document = Html::parse_document(body);
+    let nav_selector = Selector::parse("nav a[href], header a[href]").unwrap();
+
+    let mut categories = Vec::new();
+    for href in document.select(&nav_selector).filter_map(|link| link.value().attr("href")) {
+        let Ok(url) = base.join(href) else { continue };
+        let Some((_, path)) = url.path().split_once("/kaufobjekt/") else { continue };
+        let category = path.split('/').next().unwrap_or_default().to_lowercase();
+        if category.is_empty() || KNOWN_CATEGORIES.contains(&category.as_str()) {
+            continue;
+        }
+        let category_url = format!("{}://{}/kaufobjekt/{}", url.scheme(), url.host_str().unwrap_or_default(), category);
+        if !categories.contains(&category_url) {
+            categories.push(category_url);
+        }
+    }
+    categories
+}
+
+/// Fetch a laendleimmo.at page and return the unknown `/kaufobjekt/` categories in its navigation
+pub fn discover_all_categories(context: &mut ScrapingContext, base_url: &str) -> Result<Vec<String>> {
+    let body = crate::common_scraper::fetch_for_health_check(context, base_url)?;
+    let categories = find_new_categories(&body, base_url);
+    warn_about_new_categories(&categories);
+    Ok(categories)
+}
+
+fn warn_about_new_categories(categories: &[String]) {
+    for category in categories {
+        eprintln!("⚠️  laendleimmo.at has a category the scraper doesn't know yet: {}", category);
+    }
+}
+
+/// Scrape a property page with the next user agent and the HTTP headers of `context`
+pub fn scrape_property_page(context: &mut ScrapingContext, url: &str) -> Result<Property> {
+    let user_agent = context.next_user_agent().to_string();
+    scrape_property_page_checked(context, url, &user_agent, None, None)
+        .map(|property| property.expect("pages are always parsed without a known fingerprint"))
+}
+
+/// Like `scrape_property_page`, but None without parsing if the page still has the fingerprint of `stored`
+///
+/// A listing first seen within the last `context.min_freshness_days()` days, or
+/// not seen before at all, is expected to have a page at least that fresh. If
+/// its page is older, it is assumed to come from a cache and fetched again
+/// bypassing caches, after waiting for the rate limiter.
+pub fn scrape_property_page_if_changed(
+    context: &mut ScrapingContext,
+    url: &str,
+    stored: Option<&Property>,
+) -> Result<Option<Property>> {
+    let user_agent = context.next_user_agent().to_string();
+    let known_fingerprint = stored.and_then(|p| p.page_fingerprint.as_deref());
+    let today = chrono::Utc::now().naive_utc().date();
+    let min_freshness_days = context
+        .min_freshness_days()
+        .filter(|days| expected_to_be_fresh(stored.and_then(|p| p.first_seen), *days, today));
+    scrape_property_page_checked(context, url, &user_agent, known_fingerprint, min_freshness_days)
+}
+
+/// Whether the page of a listing first seen on `first_seen` should be at most `days` old on `today`
+///
+/// Listings not seen before are first seen today.
+fn expected_to_be_fresh(first_seen: Option<NaiveDate>, days: u32, today: NaiveDate) -> bool {
+    first_seen
+        .map(|first_seen| (today - first_seen).num_days() <= days as i64)
+        .unwrap_or(true)
+}
+
+/// Scrape a property page, fetching it again without caches if it is older than `min_freshness_days`
+///
+/// The page's age comes from the `Last-Modified` header or its `<meta name="date">`.
+pub fn scrape_property_page_with_freshness_check(
+    context: &mut ScrapingContext,
+    url: &str,
+    min_freshness_days: u32,
+) -> Result<Property> {
+    let user_agent = context.next_user_agent().to_string();
+    scrape_property_page_checked(context, url, &user_agent, None, Some(min_freshness_days))
+        .map(|property| property.expect("pages are always parsed without a known fingerprint"))
+}
+
+/// When a page was last modified, from the `Last-Modified` header or else `<meta name="date">`
+pub fn page_date(last_modified: Option<&str>, body: &str) -> Option<NaiveDate> {
+    last_modified
+        .and_then(|header| DateTime::parse_from_rfc2822(header.trim()).ok())
+        .map(|date| date.date_naive())
+        .or_else(|| {
+            let document = Html::parse_document(body);
+            let selector = Selector::parse(r#"meta[name="date"], meta[name="Date"]"#).unwrap();
+            let content = document.select(&selector).next()?.value().attr("content")?;
+            parse_date_string(content)
+        })
+}
+
+/// Fetch a page bypassing server and proxy caches, returning its final URL and body
+fn fetch_uncached(context: &ScrapingContext, url: &str, user_agent: &str) -> Result<(String, String)> {
+    let response = context
+        .http_client_builder()
+        .build()?
+        .get(url)
+        .header("User-Agent", user_agent)
+        .header("Cache-Control", "no-cache")
+        .header("Pragma", "no-cache")
+        .send_counted(context.performance())
+        .context("Failed to fetch property page without cache")?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("HTTP error status: {}", response.status()));
+    }
+    let final_url = response.url().to_string();
+    Ok((final_url, response.text().context("Failed to read response body")?))
+}
+
+fn scrape_property_page_checked(
+    context: &mut ScrapingContext,
+    url: &str,
+    user_agent: &str,
+    known_fingerprint: Option<&str>,
+    min_freshness_days: Option<u32>,
+) -> Result<Option<Property>> {
     debug_println!("Scraping property page: {}", url);
 
-    let response = reqwest::blocking::Client::new()
+    let fetch_trace = Tracer::new("fetch_property_page", 5000);
+    let response = context
+        .http_client_builder()
+        .build()?
         .get(url)
-        .header("User-Agent", get_random_user_agent())
-        .send()
+        .header("User-Agent", user_agent)
+        .send_counted(context.performance())
         .context("Failed to fetch property page")?;
 
-    // Check for rate limiting using HTTP status code
+    // Check for bot challenges and rate limiting using HTTP status code
     let status = response.status();
-    if status == 429 || status == 503 || status == 403 {
+    // A 403 is only a CAPTCHA if it serves a challenge page, otherwise a plain error
+    if status == 403 {
+        if response.text().is_ok_and(|body| utils::detect_captcha(&body)) {
+            return Err(ScrapeError::Captcha { url: url.to_string() }.into());
+        }
+        return Err(anyhow::anyhow!("HTTP error status: {}", status));
+    }
+    if let Some(reason) = unavailable_reason_for_status(status.as_u16()) {
+        debug_println!("Property page returned HTTP {}, marking as unavailable ({})", status.as_u16(), reason);
+        let body = response.text().unwrap_or_default();
+        return create_unavailable_property(url, &body, &Html::parse_document(&body), None, reason).map(Some);
+    }
+    if status == 429 || status == 503 {
         let retry_after = response.headers()
             .get("retry-after")
             .and_then(|h| h.to_str().ok())
             .and_then(|s| s.parse::<u64>().ok());
         
         let retry_msg = if let Some(seconds) = retry_after {
-            format!("Rate limited. Please wait {} seconds before retrying.", seconds)
+            format!("Rate limited (HTTP {}). Please wait {} seconds before retrying.", status.as_u16(), seconds)
         } else {
-            "Rate limited by server. Please wait before retrying.".to_string()
+            format!("Rate limited by server (HTTP {}). Please wait before retrying.", status.as_u16())
         };
         
         return Err(anyhow::anyhow!("{}", retry_msg));
@@ -275,44 +651,129 @@ pub fn scrape_property_page(url: &str) -> Result<Property> {
     // Check for archived/unavailable properties
     let final_url = response.url().to_string();
     debug_println!("Final URL after redirects: {}", final_url);
+    let last_modified = response
+        .headers()
+        .get("last-modified")
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
     
     let body = response.text().context("Failed to read response body")?;
     
+    // Some caches serve pages that are days old, fetch those again
+    let today = chrono::Utc::now().naive_utc().date();
+    let stale_date = min_freshness_days.and_then(|days| {
+        page_date(last_modified.as_deref(), &body).filter(|date| (today - *date).num_days() > days as i64)
+    });
+    let (final_url, body) = match stale_date {
+        Some(date) => {
+            debug_println!("Page from {} looks cached, fetching again without cache: {}", date, url);
+            context.wait_for_domain("laendleimmo.at");
+            fetch_uncached(context, url, user_agent).unwrap_or_else(|e| {
+                debug_println!("Uncached fetch failed, using the cached page: {}", e);
+                (final_url, body)
+            })
+        }
+        None => (final_url, body),
+    };
+    drop(fetch_trace);
+    
+    if utils::detect_captcha(&body) {
+        return Err(ScrapeError::Captcha { url: url.to_string() }.into());
+    }
+    
     // Fallback: check body content for rate limiting indicators
     if is_rate_limited(&body) {
         return Err(anyhow::anyhow!("Rate limiting detected in response content"));
     }
     
+    let fingerprint = utils::page_fingerprint(&body);
+    if known_fingerprint == Some(fingerprint.as_str()) {
+        debug_println!("Page unchanged since the last scrape: {}", url);
+        return Ok(None);
+    }
+    
+    let parse_trace = Tracer::new("parse_property_page", 1000);
     let document = Html::parse_document(&body);
+    drop(parse_trace);
     
-    // Detect if property is unavailable (archived)
-    let is_unavailable = final_url.contains("archiviert") || 
-                        body.contains("Diese Anzeige ist leider nicht mehr aktiv");
+    // Detect if property is unavailable (sold or archived) before extracting any fields
+    let unavailable_reason = detect_unavailable_message(&document)
+        .or_else(|| final_url.contains("archiviert").then_some(UnavailableReason::Removed));
     
-    if is_unavailable {
-        debug_println!("Property is unavailable/archived");
-        return create_unavailable_property(url, &body, &document, None);
+    if let Some(reason) = unavailable_reason {
+        debug_println!("Property is unavailable ({})", reason);
+        return create_unavailable_property(url, &body, &document, None, reason).map(Some);
     }
 
+    let mut property = parse_property_page(&body, &document, url)?;
+    property.page_fingerprint = Some(fingerprint);
+    Ok(Some(property))
+}
+
+/// Why a property page answering with `status` is gone, None if it isn't
+fn unavailable_reason_for_status(status: u16) -> Option<UnavailableReason> {
+    match status {
+        404 => Some(UnavailableReason::NotFound),
+        410 => Some(UnavailableReason::Removed),
+        451 => Some(UnavailableReason::Blocked),
+        _ => None,
+    }
+}
+
+/// Notices laendleimmo.at shows in place of a listing that was sold
+const SOLD_MESSAGES: [&str; 2] = ["bereits verkauft", "wurde verkauft"];
+
+/// Notices laendleimmo.at shows in place of a listing that was taken down
+const UNAVAILABLE_MESSAGES: [&str; 5] = [
+    "nicht mehr verfügbar",
+    "nicht mehr aktiv",
+    "nicht mehr online",
+    "wurde deaktiviert",
+    "existiert nicht mehr",
+];
+
+/// Why a detail page says the property is no longer available, None if it doesn't
+///
+/// Sold notices give `Sold`, all others `Removed`. Only headings and notice
+/// boxes are checked, since descriptions of available properties may well
+/// contain phrases like "nicht mehr verfügbar".
+pub fn detect_unavailable_message(document: &Html) -> Option<UnavailableReason> {
+    let selector = Selector::parse("h1, h2, h3, .alert, [role='alert'], .notice, [class*='unavailable'], [class*='archiv']").unwrap();
+    let notices: Vec<String> = document.select(&selector).map(|element| element.text().collect::<String>().to_lowercase()).collect();
+    if notices.iter().any(|text| SOLD_MESSAGES.iter().any(|message| text.contains(message))) {
+        return Some(UnavailableReason::Sold);
+    }
+    let removed = notices.iter().any(|text| UNAVAILABLE_MESSAGES.iter().any(|message| text.contains(message)))
+        || document.root_element().text().any(|text| text.contains("Diese Anzeige ist leider nicht mehr aktiv"));
+    removed.then_some(UnavailableReason::Removed)
+}
+
+/// Extract an available property from a fetched laendleimmo.at detail page
+///
+/// JSON-LD is the most reliable source; pages without valid JSON-LD are parsed
+/// from their HTML.
+pub fn parse_property_page(body: &str, document: &Html, url: &str) -> Result<Property> {
     // Try to extract from JSON-LD first (most reliable)
-    if let Ok(json_data) = extract_from_json_ld(&body, url) {
+    if let Ok(json_data) = extract_from_json_ld(body, url) {
         debug_println!("Successfully extracted from JSON-LD");
         return Ok(json_data);
     }
 
     // Fallback to HTML parsing
     debug_println!("JSON-LD extraction failed, falling back to HTML parsing");
-    let name = extract_title(&document)?;
-    let price = extract_price(&document)?;
-    let location = extract_location(&document, url)?;
-    let property_type = extract_property_type(&document, url);
-    let address = extract_address_from_location(&document);
-    let size_living = extract_living_size(&document);
-    let size_ground = extract_ground_size(&document);
+    let name = extract_title(document)?;
+    let price = extract_price(document)?;
+    let location = extract_location(document, url)?;
+    let property_type = extract_property_type(document, &name, url);
+    let address = extract_address_from_location(document);
+    let (table_living, table_ground) = detail_table_sizes(&extract_from_detail_table(document));
+    let size_living = table_living.or_else(|| extract_living_size(document));
+    let size_ground = table_ground.or_else(|| extract_ground_size(document));
     debug_println!("HTML fallback extracted living size: {:?}", size_living);
     debug_println!("HTML fallback extracted ground size: {:?}", size_ground);
-    let coordinates = extract_coordinates_from_map(&body);
-    let date = extract_date_from_html(&body);
+    let coordinates = extract_coordinates_from_map(body);
+    let date = extract_date_from_html(body);
+    let (agency_name, agency_phone) = extract_contact_info(document);
 
     debug_println!(
         "Extracted data: price={}, location={}, type={}, name={}, date={:?}",
@@ -323,13 +784,27 @@ pub fn scrape_property_page(url: &str) -> Result<Property> {
     
     Ok(Property {
         url: sanitize_url(url),
+        internal_id: None,
+        page_fingerprint: None,
+        completeness_score: None,
         name,
-        price,
+        price: Price::from_str(&price),
         location,
         property_type,
         listing_type: ListingType::Available,
+        unavailable_reason: None,
         date,
         coordinates,
+        quality_score: None,
+        plot_polygon: extract_plot_polygon(body),
+        description: extract_description(document),
+        features: extract_features_list(document),
+        driving_distance_km: None,
+        rooms: None,
+        floor_level: None,
+        construction_year: None,
+        agency_name,
+        agency_phone,
         address,
         size_living,
         size_ground,
@@ -364,13 +839,13 @@ fn extract_price(document: &Html) -> Result<String> {
         ".kaufpreis",
     ];
 
+    let price_regex = Regex::new(r"[\d,.]+").unwrap();
     for selector_str in &price_selectors {
         if let Ok(selector) = Selector::parse(selector_str) {
             for element in document.select(&selector) {
                 let text = element.text().collect::<Vec<_>>().join(" ");
                 if text.contains("€") || text.contains("EUR") {
                     // Clean up the price text
-                    let price_regex = Regex::new(r"[\d,.]+").unwrap();
                     if let Some(price_match) = price_regex.find(&text) {
                         return Ok(price_match.as_str().replace(".", "").replace(",", ""));
                     }
@@ -391,17 +866,17 @@ fn extract_price(document: &Html) -> Result<String> {
     Ok("Unknown".to_string())
 }
 
-fn extract_location(document: &Html, url: &str) -> Result<String> {
-    if let Ok(loc) = extract_location_from_breadcrumbs(document) {
-        return Ok(loc);
+fn extract_location(document: &Html, url: &str) -> Result<Location> {
+    let district = extract_district_from_url(url);
+    let region = district.as_ref().map(|_| "Vorarlberg".to_string());
+
+    if let Ok(municipality) = extract_location_from_breadcrumbs(document) {
+        return Ok(Location { municipality, district, region });
     }
 
-    // Try to extract from URL first
-    let url_regex = Regex::new(r"/vorarlberg/([^/]+)/").unwrap();
-    if let Some(captures) = url_regex.captures(url) {
-        if let Some(location) = captures.get(1) {
-            return Ok(location.as_str().to_string());
-        }
+    // The URL only tells us the district, so don't look further when it is known
+    if district.is_some() {
+        return Ok(Location { municipality: String::new(), district, region });
     }
 
     // Look for location in document
@@ -423,87 +898,99 @@ fn extract_location(document: &Html, url: &str) -> Result<String> {
                     .trim()
                     .to_string();
                 if !text.is_empty() && text != "Vorarlberg" {
-                    return Ok(text);
+                    return Ok(Location::from_str(&text));
                 }
             }
         }
     }
 
-    Ok("Unknown".to_string())
+    Ok(Location::unknown())
 }
 
-fn extract_property_type(document: &Html, url: &str) -> PropertyType {
-    // Try to extract from URL first - look at the full path for better classification
-    if let Some(classified) = classify_property_type_from_url(url) {
-        return classified;
-    }
+/// Extract the district from a laendleimmo.at URL (`/vorarlberg/{district}/`), capitalized
+fn extract_district_from_url(url: &str) -> Option<String> {
+    let url_regex = Regex::new(r"/vorarlberg/([^/]+)/").unwrap();
+    let district = url_regex.captures(url)?.get(1)?.as_str();
+    let mut chars = district.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
 
-    // Look for property type in document
+fn extract_property_type(document: &Html, title: &str, url: &str) -> PropertyType {
+    // Prefer the property type field of the document over the title
     let type_selectors = [".property-type", ".objektart", "[class*='type']"];
+    let type_text = type_selectors.iter().find_map(|selector_str| {
+        let selector = Selector::parse(selector_str).ok()?;
+        let text = document.select(&selector).next()?.text().collect::<Vec<_>>().join(" ");
+        let text = text.trim();
+        (!text.is_empty() && !PropertyType::from_string(text).is_unknown()).then(|| text.to_string())
+    });
+
+    let classified = PropertyType::from_title_and_url(type_text.as_deref().unwrap_or(title), url);
+    if !classified.is_unknown() {
+        return classified;
+    }
 
-    for selector_str in &type_selectors {
-        if let Ok(selector) = Selector::parse(selector_str) {
-            if let Some(element) = document.select(&selector).next() {
-                let text = element
-                    .text()
-                    .collect::<Vec<_>>()
-                    .join(" ")
-                    .trim()
-                    .to_string();
-                if !text.is_empty() {
-                    let classified = PropertyType::from_string(&text);
-                    if !matches!(classified, PropertyType::Unknown) {
-                        return classified;
-                    }
-                }
-            }
-        }
+    // Descriptions mention all kinds of rooms, so only look for commercial terms there
+    let description = extract_description(document).unwrap_or_default().to_lowercase();
+    if COMMERCIAL_DESCRIPTION_TERMS.iter().any(|term| description.contains(term)) {
+        return PropertyType::new(PropertyKind::Commercial, 0.7);
     }
 
-    PropertyType::Unknown
+    PropertyType::unknown()
 }
 
-/// Enhanced URL-based property type classification for laendleimmo.at URLs
-fn classify_property_type_from_url(url: &str) -> Option<PropertyType> {
-    // Laendleimmo URLs follow pattern: /immobilien/{main_type}/{sub_type}/vorarlberg/{district}/{id}
-    let url_regex = Regex::new(r"/immobilien/([^/]+)/([^/]+)/").unwrap();
-    if let Some(captures) = url_regex.captures(url) {
-        let main_type = captures.get(1)?.as_str().to_lowercase();
-        let sub_type = captures.get(2)?.as_str().to_lowercase();
-        
-        // Combine main and sub type for better classification
-        let combined = format!("{} {}", main_type, sub_type);
-        
-        // Direct mapping for known patterns
-        match main_type.as_str() {
-            "grundstuck" | "grundstueck" => return Some(PropertyType::Land),
-            "wohnung" => return Some(PropertyType::Apartment),
-            "haus" => return Some(PropertyType::House),
-            _ => {}
-        }
-        
-        // Check sub-type patterns for more specific classification
-        if sub_type.contains("grundstuck") || sub_type.contains("grundstueck") || 
-           sub_type.contains("baugrund") || sub_type.contains("bauplatz") {
-            return Some(PropertyType::Land);
-        }
-        
-        if sub_type.contains("wohnung") || sub_type.contains("apartment") {
-            return Some(PropertyType::Apartment);
-        }
-        
-        if sub_type.contains("haus") || sub_type.contains("villa") {
-            return Some(PropertyType::House);
-        }
-        
-        // Fall back to using the existing PropertyType::from_string logic on combined text
-        let classified = PropertyType::from_string(&combined);
-        if !matches!(classified, PropertyType::Unknown) {
-            return Some(classified);
+/// Description terms that mark a listing as commercial when nothing else classifies it
+const COMMERCIAL_DESCRIPTION_TERMS: [&str; 6] = [
+    "gewerbeobjekt",
+    "bürofläche",
+    "gewerbefläche",
+    "geschäftsfläche",
+    "ladenlokal",
+    "gastronomiebetrieb",
+];
+
+/// Label/value pairs of the property detail tables, e.g. "Wohnfläche" -> "120 m²"
+///
+/// Every `<tr>` with a `<th>` label and a `<td>` value contributes one entry;
+/// labels lose a trailing colon and whitespace is collapsed. The first
+/// occurrence of a label wins.
+pub fn extract_from_detail_table(document: &Html) -> HashMap<String, String> {
+    let row_selector = Selector::parse("tr").unwrap();
+    let label_selector = Selector::parse("th").unwrap();
+    let value_selector = Selector::parse("td").unwrap();
+    let collapse = |text: String| text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut details = HashMap::new();
+    for row in document.select(&row_selector) {
+        let (Some(label), Some(value)) = (row.select(&label_selector).next(), row.select(&value_selector).next()) else {
+            continue;
+        };
+        let label = collapse(label.text().collect());
+        let label = label.trim_end_matches(':').trim_end();
+        let value = collapse(value.text().collect());
+        if !label.is_empty() && !value.is_empty() {
+            details.entry(label.to_string()).or_insert(value);
         }
     }
-    
-    None
+    debug_println!("Found {} detail table entries", details.len());
+    details
+}
+
+/// Living and ground size in m² from the detail table entries
+fn detail_table_sizes(details: &HashMap<String, String>) -> (Option<String>, Option<String>) {
+    let number = Regex::new(r"(\d+(?:[.,]\d+)?)").unwrap();
+    let size = |labels: &[&str]| {
+        labels.iter().find_map(|label| {
+            let (_, value) = details.iter().find(|(key, _)| key.eq_ignore_ascii_case(label))?;
+            let size = number.captures(value)?[1].replace(',', ".");
+            Some(size)
+        })
+    };
+    (
+        size(&["Wohnfläche", "Nutzfläche"]),
+        size(&["Grundstücksfläche", "Grundstücksgröße", "Grundstück"]),
+    )
 }
 
 fn extract_living_size(document: &Html) -> Option<String> {
@@ -552,7 +1039,8 @@ fn extract_ground_size(document: &Html) -> Option<String> {
     None
 }
 
-fn extract_from_json_ld(body: &str, url: &str) -> Result<Property> {
+/// Extract a property from the JSON-LD script of a detail page
+pub fn extract_from_json_ld(body: &str, url: &str) -> Result<Property> {
     // Look for JSON-LD script tag
     let json_start = body
         .find(r#"<script type="application/ld+json">"#)
@@ -568,8 +1056,10 @@ fn extract_from_json_ld(body: &str, url: &str) -> Result<Property> {
         + json_content_start;
 
     let json_str = &body[json_content_start..json_content_end];
+    let json_trace = Tracer::new("parse_json_ld", 1000);
     let json: serde_json::Value =
         serde_json::from_str(json_str).context("Failed to parse JSON-LD")?;
+    drop(json_trace);
 
     // Extract data from JSON-LD structure
     let name = match json["name"].as_str() {
@@ -583,15 +1073,24 @@ fn extract_from_json_ld(body: &str, url: &str) -> Result<Property> {
         return Err(anyhow::anyhow!("Price not found in JSON-LD"));
     };
 
-    // Extract location from address
-    let location = json["location"]["address"]["addressLocality"]
-        .as_str()
-        .unwrap_or("Unknown")
-        .to_string();
+    // Extract location from address, the district is encoded in the URL
+    let district = extract_district_from_url(url);
+    let location = match json["location"]["address"]["addressLocality"].as_str() {
+        Some(locality) => Location {
+            municipality: locality.to_string(),
+            region: district.as_ref().map(|_| "Vorarlberg".to_string()),
+            district,
+        },
+        None if district.is_some() => Location {
+            municipality: String::new(),
+            district,
+            region: Some("Vorarlberg".to_string()),
+        },
+        None => Location::unknown(),
+    };
 
     // Extract property type from URL first, then fall back to name classification
-    let property_type = classify_property_type_from_url(url)
-        .unwrap_or_else(|| PropertyType::from_string(name));
+    let property_type = PropertyType::from_title_and_url(name, url);
 
     // Extract address
     let street = json["location"]["address"]["streetAddress"]
@@ -608,30 +1107,18 @@ fn extract_from_json_ld(body: &str, url: &str) -> Result<Property> {
         None
     };
 
-    // Extract coordinates if available in JSON-LD
-    let mut coordinates = if let (Some(lat), Some(lng)) = (
-        json["location"]["geo"]["latitude"].as_f64(),
-        json["location"]["geo"]["longitude"].as_f64(),
-    ) {
-        Some((lat, lng))
-    } else {
-        None
-    };
-
-    // If coordinates not in JSON-LD, try map data as fallback
-    if coordinates.is_none() {
-        coordinates = extract_coordinates_from_map(body);
-    }
+    // JSON-LD coordinates are structured data, the map loader attribute is only a fallback
+    let coordinates = extract_coordinates_from_json_ld(&json).or_else(|| extract_coordinates_from_map(body));
 
-    // Extract living size and ground size from description
+    // Extract living size and ground size from the detail table, then the description
     let description = json["description"].as_str().unwrap_or("");
-    let mut size_living = extract_living_size_from_text(description);
-    let mut size_ground = extract_ground_size_from_text(description);
+    let document = Html::parse_document(body);
+    let (table_living, table_ground) = detail_table_sizes(&extract_from_detail_table(&document));
+    let mut size_living = table_living.or_else(|| extract_living_size_from_text(description));
+    let mut size_ground = table_ground.or_else(|| extract_ground_size_from_text(description));
     
     // If sizes not found in description, try extracting from full HTML body
     if size_living.is_none() || size_ground.is_none() {
-        let document = Html::parse_document(body);
-        
         if size_living.is_none() {
             size_living = extract_living_size(&document);
             debug_println!("Living size not in JSON-LD description, tried HTML extraction: {:?}", size_living);
@@ -644,13 +1131,17 @@ fn extract_from_json_ld(body: &str, url: &str) -> Result<Property> {
     }
     
     debug_println!("JSON-LD description for size extraction: {}", description);
+    let stored_description = clean_description(description)
+        .or_else(|| extract_description(&document));
+    let features = extract_features_list(&document);
+    let (agency_name, agency_phone) = extract_contact_info(&document);
     debug_println!("JSON-LD extracted living size: {:?}, ground size: {:?}", size_living, size_ground);
 
     // Extract date from datePublished or dateCreated in JSON-LD
     let date = json["datePublished"]
         .as_str()
         .or_else(|| json["dateCreated"].as_str())
-        .and_then(|d| parse_date_string(d))
+        .and_then(parse_date_string)
         .or_else(|| extract_date_from_html(body)); // Fallback to HTML parsing
 
     debug_println!(
@@ -662,13 +1153,27 @@ fn extract_from_json_ld(body: &str, url: &str) -> Result<Property> {
     
     Ok(Property {
         url: sanitize_url(url),
+        internal_id: extract_property_id_from_json_ld(&json),
+        page_fingerprint: None,
+        completeness_score: None,
         name: name.to_string(),
-        price,
+        price: Price::from_str(&price),
         location,
         property_type,
         listing_type: ListingType::Available,
+        unavailable_reason: None,
         date,
         coordinates,
+        quality_score: None,
+        plot_polygon: extract_plot_polygon(body),
+        description: stored_description,
+        features,
+        driving_distance_km: None,
+        rooms: None,
+        floor_level: None,
+        construction_year: None,
+        agency_name,
+        agency_phone,
         address,
         size_living,
         size_ground,
@@ -677,13 +1182,38 @@ fn extract_from_json_ld(body: &str, url: &str) -> Result<Property> {
     })
 }
 
+/// The listing's own ID from the JSON-LD `identifier`, or else the numeric last segment of its `@id` URL
+///
+/// Unlike the URL path this stays the same when laendleimmo.at reclassifies a listing.
+/// A non-numeric `@id` segment is a slug that changes with the URL, so it is no ID.
+pub fn extract_property_id_from_json_ld(json: &serde_json::Value) -> Option<String> {
+    let identifier = match &json["identifier"] {
+        serde_json::Value::String(id) => Some(id.trim().to_string()),
+        serde_json::Value::Number(id) => Some(id.to_string()),
+        // schema.org PropertyValue
+        serde_json::Value::Object(value) => value.get("value").and_then(|v| match v {
+            serde_json::Value::String(id) => Some(id.trim().to_string()),
+            serde_json::Value::Number(id) => Some(id.to_string()),
+            _ => None,
+        }),
+        _ => None,
+    };
+    identifier
+        .or_else(|| {
+            let id = json["@id"].as_str()?;
+            let path = id.split(['#', '?']).next().unwrap_or_default().trim_end_matches('/');
+            path.rsplit('/').next().filter(|id| id.chars().all(|c| c.is_ascii_digit())).map(str::to_string)
+        })
+        .filter(|id| !id.is_empty())
+}
+
 fn extract_location_from_breadcrumbs(document: &Html) -> Result<String> {
     // Look for breadcrumb navigation
     let breadcrumb_selector =
         Selector::parse("a[href*='feldkirch'], a[href*='bregenz'], a[href*='dornbirn']")
             .map_err(|e| anyhow::anyhow!("Failed to parse breadcrumb selector: {:?}", e))?;
 
-    if let Some(element) = document.select(&breadcrumb_selector).last() {
+    if let Some(element) = document.select(&breadcrumb_selector).next_back() {
         let text = element
             .text()
             .collect::<Vec<_>>()
@@ -716,7 +1246,16 @@ fn extract_address_from_location(document: &Html) -> Option<String> {
     None
 }
 
-fn extract_coordinates_from_map(body: &str) -> Option<(f64, f64)> {
+/// Read `location.geo.latitude/longitude` from JSON-LD, given as numbers or strings
+pub fn extract_coordinates_from_json_ld(json: &serde_json::Value) -> Option<Coordinates> {
+    let number = |value: &serde_json::Value| {
+        value.as_f64().or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+    };
+    let geo = &json["location"]["geo"];
+    Coordinates::new(number(&geo["latitude"])?, number(&geo["longitude"])?).ok()
+}
+
+fn extract_coordinates_from_map(body: &str) -> Option<Coordinates> {
     // Look for coordinates in map data
     if let Some(start) = body
         .find("data-content-loader-url-value=\"/load-template/organisms/detail_page/map.html.twig")
@@ -725,12 +1264,7 @@ fn extract_coordinates_from_map(body: &str) -> Option<(f64, f64)> {
             let coords_start = start + params_start + 12; // length of "lat_long%5D="
             if let Some(coords_end) = body[coords_start..].find('"') {
                 let coords_str = &body[coords_start..coords_start + coords_end];
-                let parts: Vec<&str> = coords_str.split(',').collect();
-                if parts.len() == 2 {
-                    if let (Ok(lat), Ok(lng)) = (parts[0].parse::<f64>(), parts[1].parse::<f64>()) {
-                        return Some((lat, lng));
-                    }
-                }
+                return coords_str.parse().ok();
             }
         }
     }
@@ -738,6 +1272,169 @@ fn extract_coordinates_from_map(body: &str) -> Option<(f64, f64)> {
     None
 }
 
+/// Plot boundary from the map on the detail page, as (lat, lng) points
+///
+/// Land listings may carry the plot as a GeoJSON Polygon (bare, or as a
+/// Feature) in an HTML-escaped `data-*` attribute of the map element. Only the
+/// outer ring is kept; points outside Austria make the polygon invalid.
+pub fn extract_plot_polygon(body: &str) -> Option<Vec<(f64, f64)>> {
+    let attribute_regex = Regex::new(r#"data-[\w-]+="([^"]*Polygon[^"]*)""#).unwrap();
+    let polygon = attribute_regex.captures_iter(body).find_map(|captures| {
+        let value = captures[1].replace("&quot;", "\"").replace("&#34;", "\"").replace("&amp;", "&");
+        let json: serde_json::Value = serde_json::from_str(&value).ok()?;
+        let geometry = if json["type"] == "Feature" { &json["geometry"] } else { &json };
+        if geometry["type"] != "Polygon" {
+            return None;
+        }
+        let points = geometry["coordinates"][0]
+            .as_array()?
+            .iter()
+            .map(|point| {
+                let (lng, lat) = (point.get(0)?.as_f64()?, point.get(1)?.as_f64()?);
+                Coordinates::new(lat, lng).ok().map(|_| (lat, lng))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (points.len() >= 3).then_some(points)
+    });
+    polygon
+}
+
+/// German feature names of the "Merkmale" section and their English equivalents
+const FEATURE_TRANSLATIONS: [(&str, &str); 24] = [
+    ("garage", "garage"),
+    ("tiefgarage", "underground parking"),
+    ("carport", "carport"),
+    ("stellplatz", "parking space"),
+    ("terrasse", "terrace"),
+    ("dachterrasse", "roof terrace"),
+    ("balkon", "balcony"),
+    ("loggia", "loggia"),
+    ("wintergarten", "winter garden"),
+    ("garten", "garden"),
+    ("pool", "pool"),
+    ("schwimmbad", "pool"),
+    ("sauna", "sauna"),
+    ("keller", "cellar"),
+    ("kellerabteil", "cellar"),
+    ("lift", "elevator"),
+    ("aufzug", "elevator"),
+    ("kamin", "fireplace"),
+    ("klimaanlage", "air conditioning"),
+    ("fußbodenheizung", "underfloor heating"),
+    ("photovoltaik", "solar panels"),
+    ("einbauküche", "fitted kitchen"),
+    ("barrierefrei", "accessible"),
+    ("abstellraum", "storage room"),
+];
+
+/// Normalise a feature name to lowercase English, e.g. "Terrasse" -> "terrace"
+///
+/// Unknown features are kept as lowercase German.
+pub fn normalize_feature(feature: &str) -> String {
+    let translations: HashMap<&str, &str> = FEATURE_TRANSLATIONS.into_iter().collect();
+    let key = feature.trim().to_lowercase();
+    translations
+        .get(key.as_str())
+        .map(|english| english.to_string())
+        .unwrap_or(key)
+}
+
+/// Extract the features of the "Merkmale" grid, normalised to lowercase English
+pub fn extract_features_list(document: &Html) -> Vec<String> {
+    let selectors = [
+        ".features li",
+        ".property-features li",
+        "[class*='merkmal'] li",
+        "[class*='feature'] li",
+    ];
+
+    let mut features: Vec<String> = Vec::new();
+    for selector_str in &selectors {
+        if let Ok(selector) = Selector::parse(selector_str) {
+            for element in document.select(&selector) {
+                let text = element.text().collect::<Vec<_>>().join(" ");
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if text.is_empty() {
+                    continue;
+                }
+                let feature = normalize_feature(&text);
+                if !features.contains(&feature) {
+                    features.push(feature);
+                }
+            }
+        }
+        if !features.is_empty() {
+            break;
+        }
+    }
+
+    features
+}
+
+/// Maximum number of characters of a listing description stored per property
+const MAX_DESCRIPTION_CHARS: usize = 2000;
+
+/// Extract the listing text from the description block of a property page
+pub fn extract_description(document: &Html) -> Option<String> {
+    let selector = Selector::parse("div.property-description").ok()?;
+    let text = document
+        .select(&selector)
+        .next()?
+        .text()
+        .collect::<Vec<_>>()
+        .join(" ");
+    clean_description(&text)
+}
+
+/// Containers of the agent card on a property page
+const CONTACT_CARD_SELECTORS: [&str; 4] = [".contact-card", ".agent-card", "[class*='contact']", "[class*='makler']"];
+
+/// Extract the listing agency's name and phone number from the agent card
+///
+/// The phone number is taken from the card's `tel:` link, with spaces removed.
+pub fn extract_contact_info(document: &Html) -> (Option<String>, Option<String>) {
+    // Most specific first, headings often only say "Ihr Ansprechpartner"
+    let name_selectors: Vec<Selector> = [".company", ".agency", "[class*='company']", "[class*='firma']", "strong"]
+        .iter()
+        .filter_map(|s| Selector::parse(s).ok())
+        .collect();
+    let phone_selector = Selector::parse("a[href^='tel:']").unwrap();
+    let text_of = |element: scraper::ElementRef| {
+        let text = element.text().collect::<Vec<_>>().join(" ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!text.is_empty()).then_some(text)
+    };
+
+    for selector_str in &CONTACT_CARD_SELECTORS {
+        let Ok(selector) = Selector::parse(selector_str) else { continue };
+        for card in document.select(&selector) {
+            let name = name_selectors
+                .iter()
+                .find_map(|selector| card.select(selector).find_map(text_of));
+            let phone = card
+                .select(&phone_selector)
+                .filter_map(|link| link.value().attr("href"))
+                .map(|href| href.trim_start_matches("tel:").replace([' ', '/', '-'], ""))
+                .find(|phone| !phone.is_empty());
+            if name.is_some() || phone.is_some() {
+                debug_println!("Found contact info in {}: {:?}, {:?}", selector_str, name, phone);
+                return (name, phone);
+            }
+        }
+    }
+
+    (None, None)
+}
+
+/// Collapse whitespace and truncate a description to `MAX_DESCRIPTION_CHARS`
+fn clean_description(text: &str) -> Option<String> {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if normalized.is_empty() {
+        return None;
+    }
+    Some(normalized.chars().take(MAX_DESCRIPTION_CHARS).collect())
+}
+
 fn extract_living_size_from_text(text: &str) -> Option<String> {
     // Look for various German living area patterns
     let patterns = [
@@ -879,6 +1576,21 @@ fn extract_date_from_html(body: &str) -> Option<NaiveDate> {
 }
 
 fn parse_date_string(date_str: &str) -> Option<NaiveDate> {
+    let date_str = date_str.trim();
+
+    // ISO 8601 timestamps with timezone, e.g. 2025-07-25T14:30:00+02:00.
+    // The date is taken in the timezone given by the offset.
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(date_str) {
+        return Some(datetime.date_naive());
+    }
+
+    // Timestamps without timezone, e.g. 2025-07-25T14:30:00 or 2025-07-25 14:30:00
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(date_str, format) {
+            return Some(datetime.date());
+        }
+    }
+
     // Try common date formats
     let formats = [
         "%Y-%m-%d", // 2025-07-25
@@ -893,7 +1605,38 @@ fn parse_date_string(date_str: &str) -> Option<NaiveDate> {
         }
     }
 
-    None
+    parse_german_long_date(date_str)
+}
+
+/// Parse German long-form dates like "25. Juli 2025" or "1. Jänner 2025"
+fn parse_german_long_date(date_str: &str) -> Option<NaiveDate> {
+    let re = Regex::new(r"^(\d{1,2})\.\s*([A-Za-zÄÖÜäöü]+)\.?\s+(\d{4})$").ok()?;
+    let caps = re.captures(date_str)?;
+
+    let day: u32 = caps[1].parse().ok()?;
+    let month = german_month_number(&caps[2])?;
+    let year: i32 = caps[3].parse().ok()?;
+
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+fn german_month_number(name: &str) -> Option<u32> {
+    let month = match name.to_lowercase().as_str() {
+        "januar" | "jänner" | "jaenner" | "jan" | "jän" => 1,
+        "februar" | "feber" | "feb" => 2,
+        "märz" | "maerz" | "mär" | "mrz" => 3,
+        "april" | "apr" => 4,
+        "mai" => 5,
+        "juni" | "jun" => 6,
+        "juli" | "jul" => 7,
+        "august" | "aug" => 8,
+        "september" | "sep" | "sept" => 9,
+        "oktober" | "okt" => 10,
+        "november" | "nov" => 11,
+        "dezember" | "dez" => 12,
+        _ => return None,
+    };
+    Some(month)
 }
 
 fn is_rate_limited(body: &str) -> bool {
@@ -911,7 +1654,7 @@ fn is_rate_limited(body: &str) -> bool {
     body.len() < 1000 && (body.contains("Checking") || body.contains("Please wait"))
 }
 
-fn create_unavailable_property(original_url: &str, body: &str, document: &Html, existing_property: Option<&Property>) -> Result<Property> {
+fn create_unavailable_property(original_url: &str, body: &str, document: &Html, existing_property: Option<&Property>, reason: UnavailableReason) -> Result<Property> {
     debug_println!("Creating unavailable property for URL: {}", original_url);
     
     let now = chrono::Utc::now().naive_utc().date();
@@ -921,13 +1664,27 @@ fn create_unavailable_property(original_url: &str, body: &str, document: &Html,
         debug_println!("Preserving existing property data, only updating to unavailable status");
         return Ok(Property {
             url: sanitize_url(original_url),
+            internal_id: existing.internal_id.clone(),
+            page_fingerprint: existing.page_fingerprint.clone(),
+            completeness_score: existing.completeness_score,
             name: existing.name.clone(),
             price: existing.price.clone(),
             location: existing.location.clone(),
-            property_type: existing.property_type.clone(),
+            property_type: existing.property_type,
             listing_type: ListingType::Unavailable,
+            unavailable_reason: Some(reason),
             date: existing.date,
             coordinates: existing.coordinates,
+            quality_score: existing.quality_score,
+            plot_polygon: existing.plot_polygon.clone(),
+            description: existing.description.clone(),
+            features: existing.features.clone(),
+            driving_distance_km: existing.driving_distance_km,
+            rooms: existing.rooms,
+            floor_level: existing.floor_level.clone(),
+            construction_year: existing.construction_year,
+            agency_name: existing.agency_name.clone(),
+            agency_phone: existing.agency_phone.clone(),
             address: existing.address.clone(),
             size_living: existing.size_living.clone(),
             size_ground: existing.size_ground.clone(),
@@ -938,8 +1695,11 @@ fn create_unavailable_property(original_url: &str, body: &str, document: &Html,
     
     // If no existing property data, extract what we can from the archived page
     let name = extract_title(document).unwrap_or_else(|_| "Unavailable Property".to_string());
-    let location = extract_location(document, original_url).unwrap_or_else(|_| "Unknown".to_string());
-    let property_type = extract_property_type(document, original_url);
+    let location = extract_location(document, original_url).unwrap_or_else(|_| Location::unknown());
+    // The archived page may still carry the type; if not, note that the page is gone
+    let property_type = Some(extract_property_type(document, &name, original_url))
+        .filter(|property_type| !property_type.is_unknown())
+        .unwrap_or_else(PropertyType::unavailable);
     
     // Try to extract date when it was archived/made unavailable
     let date = extract_date_from_html(body);
@@ -951,13 +1711,27 @@ fn create_unavailable_property(original_url: &str, body: &str, document: &Html,
     
     Ok(Property {
         url: sanitize_url(original_url),
+        internal_id: None,
+        page_fingerprint: None,
+        completeness_score: None,
         name,
-        price: "Unavailable".to_string(),
+        price: Price::from_str("Unavailable"),
         location,
         property_type,
         listing_type: ListingType::Unavailable,
+        unavailable_reason: Some(reason),
         date,
         coordinates: None,
+        quality_score: None,
+        plot_polygon: None,
+        description: None,
+        features: Vec::new(),
+        driving_distance_km: None,
+        rooms: None,
+        floor_level: None,
+        construction_year: None,
+        agency_name: None,
+        agency_phone: None,
         address: None,
         size_living: None,
         size_ground: None,
@@ -965,3 +1739,311 @@ fn create_unavailable_property(original_url: &str, body: &str, document: &Html,
         last_seen: Some(now),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    #[test]
+    fn test_json_ld_coordinates_take_priority_over_map() {
+        let body = r#"<html><head><script type="application/ld+json">{
+            "name": "Haus in Rankweil",
+            "offers": { "price": 650000 },
+            "location": {
+                "address": { "addressLocality": "Rankweil" },
+                "geo": { "latitude": "47.2712", "longitude": 9.6431 }
+            }
+        }</script></head><body>
+            <div data-content-loader-url-value="/load-template/organisms/detail_page/map.html.twig?params%5Blat_long%5D=47.4000,9.7400"></div>
+        </body></html>"#;
+
+        let url = "https://www.laendleimmo.at/immobilien/haus/einfamilienhaus/vorarlberg/feldkirch/1";
+        let property = extract_from_json_ld(body, url).unwrap();
+        assert_eq!(property.coordinates, Some(Coordinates::new(47.2712, 9.6431).unwrap()));
+
+        assert_eq!(extract_coordinates_from_map(body), Some(Coordinates::new(47.4, 9.74).unwrap()));
+    }
+
+    #[test]
+    fn test_extract_property_id_from_json_ld() {
+        let id = |json: &str| extract_property_id_from_json_ld(&serde_json::from_str(json).unwrap());
+        assert_eq!(id(r#"{"identifier": "123456"}"#), Some("123456".to_string()));
+        assert_eq!(id(r#"{"identifier": 123456}"#), Some("123456".to_string()));
+        assert_eq!(id(r#"{"identifier": {"@type": "PropertyValue", "value": "123456"}}"#), Some("123456".to_string()));
+        assert_eq!(
+            id(r#"{"@id": "https://www.laendleimmo.at/immobilien/wohnung/vorarlberg/dornbirn/123456/#listing"}"#),
+            Some("123456".to_string())
+        );
+        assert_eq!(id(r#"{"identifier": "654321", "@id": "https://www.laendleimmo.at/123456"}"#), Some("654321".to_string()));
+        assert_eq!(id(r#"{"identifier": "", "name": "Wohnung"}"#), None);
+        assert_eq!(id(r#"{"@id": "https://www.laendleimmo.at/immobilien/wohnung/vorarlberg/dornbirn/wohnen-am-see"}"#), None);
+        assert_eq!(id(r#"{"@id": "https://www.laendleimmo.at/#organization"}"#), None);
+    }
+
+    #[test]
+    fn test_page_date() {
+        let page = r#"<html><head><meta name="date" content="2026-10-01"></head><body></body></html>"#;
+        assert_eq!(page_date(None, page), date(2026, 10, 1));
+        assert_eq!(page_date(Some("Wed, 14 Oct 2026 08:30:00 GMT"), page), date(2026, 10, 14));
+        assert_eq!(page_date(Some("gestern"), page), date(2026, 10, 1));
+        assert_eq!(page_date(None, "<html><body>Wohnung</body></html>"), None);
+    }
+
+    #[test]
+    fn test_expected_to_be_fresh() {
+        let today = date(2026, 10, 16).unwrap();
+        assert!(expected_to_be_fresh(None, 7, today));
+        assert!(expected_to_be_fresh(date(2026, 10, 9), 7, today));
+        assert!(!expected_to_be_fresh(date(2026, 9, 1), 7, today));
+    }
+
+    #[test]
+    fn test_page_format_build() {
+        assert_eq!(PageFormat::QueryParameter.build(BASE_URL, 3), format!("{}?page=3", BASE_URL));
+        assert_eq!(PageFormat::PathSegment.build(BASE_URL, 3), format!("{}/p/3", BASE_URL));
+        assert_eq!(PageFormat::PathSegment.build("https://example.com/list/", 2), "https://example.com/list/p/2");
+        let mut context = ScrapingContext::new(&crate::common_scraper::ScrapingOptions::default());
+        assert_eq!(detect_and_build_page_url(&mut context, BASE_URL, 1), BASE_URL);
+
+        let search = format!("{}?preismax=500000", BASE_URL);
+        assert_eq!(PageFormat::QueryParameter.build(&search, 2), format!("{}?preismax=500000&page=2", BASE_URL));
+        assert_eq!(PageFormat::PathSegment.build(&search, 2), format!("{}/p/2?preismax=500000", BASE_URL));
+    }
+
+    #[test]
+    fn test_is_other_listing_page() {
+        let first_page: HashSet<String> = ["https://www.laendleimmo.at/immobilien/haus/vorarlberg/dornbirn/1".to_string()].into();
+        let same = vec!["https://www.laendleimmo.at/immobilien/haus/vorarlberg/dornbirn/1".to_string()];
+        let other = vec!["https://www.laendleimmo.at/immobilien/haus/vorarlberg/bregenz/2".to_string()];
+        assert!(is_other_listing_page(&first_page, &other));
+        assert!(!is_other_listing_page(&first_page, &same));
+        assert!(!is_other_listing_page(&first_page, &[]));
+    }
+
+    #[test]
+    fn test_build_search_url() {
+        assert_eq!(build_search_url(BASE_URL, &ListingFilters::default()), BASE_URL);
+
+        let filters = ListingFilters { min_rooms: Some(3), max_price: Some(500_000), ..Default::default() };
+        assert_eq!(build_search_url(BASE_URL, &filters), format!("{}?zimmer_min=3&preismax=500000", BASE_URL));
+        assert_eq!(
+            build_search_url("https://www.laendleimmo.at/suche?q=haus", &filters),
+            "https://www.laendleimmo.at/suche?q=haus&zimmer_min=3&preismax=500000"
+        );
+
+        let land = ListingFilters { property_kind: Some(PropertyKind::Land), ..filters };
+        assert_eq!(build_search_url(BASE_URL, &land), format!("{}?preismax=500000", BASE_URL));
+    }
+
+    #[test]
+    fn test_retain_property_kind() {
+        let office = "https://www.laendleimmo.at/immobilien/gewerbe/buero/vorarlberg/dornbirn/123".to_string();
+        let house = "https://www.laendleimmo.at/immobilien/haus/einfamilienhaus/vorarlberg/bregenz/124".to_string();
+        let mut urls = vec![office.clone(), house.clone()];
+        ListingFilters::default().retain_property_kind(&mut urls);
+        assert_eq!(urls, vec![office.clone(), house]);
+
+        let commercial = ListingFilters { property_kind: Some(PropertyKind::Commercial), ..Default::default() };
+        commercial.retain_property_kind(&mut urls);
+        assert_eq!(urls, vec![office]);
+    }
+
+    #[test]
+    fn test_classify_commercial_properties() {
+        let kind = |url: &str| PropertyType::from_url(url).map(|t| t.kind);
+        assert_eq!(kind("https://www.laendleimmo.at/immobilien/gewerbe/buero/vorarlberg/dornbirn/123"), Some(PropertyKind::Commercial));
+        assert_eq!(kind("https://www.laendleimmo.at/immobilien/sonstige/gastro/vorarlberg/bregenz/124"), Some(PropertyKind::Commercial));
+        assert_eq!(kind("https://www.laendleimmo.at/immobilien/haus/einfamilienhaus/vorarlberg/bregenz/125"), Some(PropertyKind::House));
+
+        let page = Html::parse_document(r#"<div class="property-description">Gut gelegenes Gewerbeobjekt mit 200 m² Bürofläche.</div>"#);
+        assert_eq!(extract_property_type(&page, "Objekt in Dornbirn", "https://www.laendleimmo.at/immobilien/sonstige/objekt/x/1").kind, PropertyKind::Commercial);
+    }
+
+    #[test]
+    fn test_detect_unavailable_message() {
+        let page = |html: &str| Html::parse_document(html);
+        assert_eq!(detect_unavailable_message(&page("<h1>Objekt nicht mehr verfügbar</h1>")), Some(UnavailableReason::Removed));
+        assert_eq!(
+            detect_unavailable_message(&page(r#"<div class="alert alert-info">Diese Immobilie ist bereits verkauft.</div>"#)),
+            Some(UnavailableReason::Sold)
+        );
+        assert_eq!(detect_unavailable_message(&page("<p>Diese Anzeige ist leider nicht mehr aktiv.</p>")), Some(UnavailableReason::Removed));
+        assert_eq!(detect_unavailable_message(&page("<h1>Haus in Dornbirn</h1><p>Der Garten ist nicht mehr verfügbar.</p>")), None);
+    }
+
+    #[test]
+    fn test_unavailable_reason_for_status() {
+        assert_eq!(unavailable_reason_for_status(404), Some(UnavailableReason::NotFound));
+        assert_eq!(unavailable_reason_for_status(410), Some(UnavailableReason::Removed));
+        assert_eq!(unavailable_reason_for_status(451), Some(UnavailableReason::Blocked));
+        assert_eq!(unavailable_reason_for_status(200), None);
+        assert_eq!(unavailable_reason_for_status(500), None);
+    }
+
+    #[test]
+    fn test_extract_from_detail_table() {
+        let document = Html::parse_document(r#"<html><body>
+            <p>Die Wohnfläche von 300 m² im Nachbarhaus ist nicht gemeint.</p>
+            <table>
+                <tr><th>Wohnfläche</th><td>120,5 m²</td></tr>
+                <tr><th>Grundstücksfläche:</th><td> 650 m² </td></tr>
+                <tr><th>Zimmer</th><td>4</td></tr>
+                <tr><td>Nur Wert</td></tr>
+                <tr><th>Leer</th><td></td></tr>
+            </table>
+            <table><tr><th>Wohnfläche</th><td>99 m²</td></tr></table>
+        </body></html>"#);
+
+        let details = extract_from_detail_table(&document);
+        assert_eq!(details.len(), 3);
+        assert_eq!(details["Wohnfläche"], "120,5 m²");
+        assert_eq!(details["Grundstücksfläche"], "650 m²");
+        assert_eq!(details["Zimmer"], "4");
+        assert_eq!(detail_table_sizes(&details), (Some("120.5".to_string()), Some("650".to_string())));
+
+        let without_table = Html::parse_document("<html><body><p>Wohnfläche 80 m²</p></body></html>");
+        assert_eq!(detail_table_sizes(&extract_from_detail_table(&without_table)), (None, None));
+    }
+
+    #[test]
+    fn test_url_matches_location() {
+        let url = "https://www.laendleimmo.at/immobilien/haus/einfamilienhaus/vorarlberg/feldkirch/12345";
+        assert!(url_matches_location(url, &["feldkirch".to_string()]));
+        assert!(url_matches_location(url, &["bregenz".to_string(), "Feldkirch".to_string()]));
+        assert!(!url_matches_location(url, &["bregenz".to_string()]));
+        assert!(!url_matches_location(url, &[]));
+    }
+
+    #[test]
+    fn test_extract_description() {
+        let document = Html::parse_document(
+            r#"<html><body><div class="property-description">
+                <p>Schönes Haus mit Sauna</p>
+                <p>und   großer Terrasse.</p>
+            </div></body></html>"#,
+        );
+        assert_eq!(
+            extract_description(&document).as_deref(),
+            Some("Schönes Haus mit Sauna und großer Terrasse.")
+        );
+        assert_eq!(extract_description(&Html::parse_document("<html></html>")), None);
+    }
+
+    #[test]
+    fn test_extract_features_list() {
+        let document = Html::parse_document(
+            r#"<html><body><ul class="property-features">
+                <li>Garage</li><li> Terrasse </li><li>Sauna</li><li>Weinkeller</li><li>Aufzug</li><li>Lift</li>
+            </ul></body></html>"#,
+        );
+        assert_eq!(
+            extract_features_list(&document),
+            vec!["garage", "terrace", "sauna", "weinkeller", "elevator"]
+        );
+    }
+
+    #[test]
+    fn test_clean_description_truncates_on_char_boundary() {
+        let long = "ä".repeat(MAX_DESCRIPTION_CHARS + 10);
+        let cleaned = clean_description(&long).unwrap();
+        assert_eq!(cleaned.chars().count(), MAX_DESCRIPTION_CHARS);
+        assert_eq!(clean_description("   "), None);
+    }
+
+    #[test]
+    fn test_parse_date_string_numeric_formats() {
+        assert_eq!(parse_date_string("2025-07-25"), date(2025, 7, 25));
+        assert_eq!(parse_date_string("25.07.2025"), date(2025, 7, 25));
+        assert_eq!(parse_date_string("5.7.2025"), date(2025, 7, 5));
+        assert_eq!(parse_date_string("25/07/2025"), date(2025, 7, 25));
+        assert_eq!(parse_date_string("2025/07/25"), date(2025, 7, 25));
+        assert_eq!(parse_date_string("  2025-07-25  "), date(2025, 7, 25));
+    }
+
+    #[test]
+    fn test_parse_date_string_iso_timestamps() {
+        assert_eq!(parse_date_string("2025-07-25T14:30:00+02:00"), date(2025, 7, 25));
+        assert_eq!(parse_date_string("2025-07-25T14:30:00Z"), date(2025, 7, 25));
+        assert_eq!(parse_date_string("2025-07-25T14:30:00.123+02:00"), date(2025, 7, 25));
+        assert_eq!(parse_date_string("2025-07-25T14:30:00"), date(2025, 7, 25));
+        assert_eq!(parse_date_string("2025-07-25 14:30:00"), date(2025, 7, 25));
+    }
+
+    #[test]
+    fn test_parse_date_string_keeps_date_of_offset() {
+        // Just after midnight local time is still the previous day in UTC
+        assert_eq!(parse_date_string("2025-08-01T00:30:00+02:00"), date(2025, 8, 1));
+        assert_eq!(parse_date_string("2025-07-31T23:30:00-05:00"), date(2025, 7, 31));
+    }
+
+    #[test]
+    fn test_parse_date_string_german_long_form() {
+        assert_eq!(parse_date_string("25. Juli 2025"), date(2025, 7, 25));
+        assert_eq!(parse_date_string("1. Jänner 2025"), date(2025, 1, 1));
+        assert_eq!(parse_date_string("1. Januar 2025"), date(2025, 1, 1));
+        assert_eq!(parse_date_string("3. März 2024"), date(2024, 3, 3));
+        assert_eq!(parse_date_string("31. Dezember 2024"), date(2024, 12, 31));
+        assert_eq!(parse_date_string("15. Okt. 2025"), date(2025, 10, 15));
+        assert_eq!(parse_date_string("7.Mai 2025"), date(2025, 5, 7));
+    }
+
+    #[test]
+    fn test_parse_date_string_month_boundaries_and_leap_years() {
+        assert_eq!(parse_date_string("29.02.2024"), date(2024, 2, 29));
+        assert_eq!(parse_date_string("29. Februar 2024"), date(2024, 2, 29));
+        assert_eq!(parse_date_string("29.02.2025"), None);
+        assert_eq!(parse_date_string("29. Feber 2025"), None);
+        assert_eq!(parse_date_string("31.04.2025"), None);
+        assert_eq!(parse_date_string("30. April 2025"), date(2025, 4, 30));
+    }
+
+    #[test]
+    fn test_parse_date_string_invalid() {
+        assert_eq!(parse_date_string(""), None);
+        assert_eq!(parse_date_string("gestern"), None);
+        assert_eq!(parse_date_string("25. Foo 2025"), None);
+        assert_eq!(parse_date_string("2025-13-01"), None);
+    }
+
+    #[test]
+    fn test_find_new_categories() {
+        let body = r#"<html><body>
+            <header><nav>
+                <a href="/kaufobjekt/wohnung">Wohnungen</a>
+                <a href="/kaufobjekt/haus/einfamilienhaus">Häuser</a>
+                <a href="/kaufobjekt/anlageimmobilien">Anlage</a>
+                <a href="https://www.laendleimmo.at/kaufobjekt/projekte/neubau">Projekte</a>
+                <a href="/kaufobjekt/projekte">Projekte</a>
+                <a href="/mietobjekt/buero">Miete</a>
+            </nav></header>
+            <main><a href="/kaufobjekt/sonstiges">Sonstiges</a></main>
+        </body></html>"#;
+
+        assert_eq!(
+            find_new_categories(body, BASE_URL),
+            [
+                "https://www.laendleimmo.at/kaufobjekt/anlageimmobilien",
+                "https://www.laendleimmo.at/kaufobjekt/projekte",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_plot_polygon() {
+        let body = r#"<div class="map" data-controller="map"
+            data-map-plot-value="{&quot;type&quot;:&quot;Feature&quot;,&quot;geometry&quot;:{&quot;type&quot;:&quot;Polygon&quot;,&quot;coordinates&quot;:[[[9.741,47.412],[9.743,47.412],[9.743,47.414],[9.741,47.412]]]}}"></div>"#;
+        assert_eq!(
+            extract_plot_polygon(body),
+            Some(vec![(47.412, 9.741), (47.412, 9.743), (47.414, 9.743), (47.412, 9.741)])
+        );
+
+        // A point marker is no plot boundary
+        let point = r#"<div data-map-geometry-value="{&quot;type&quot;:&quot;Point&quot;,&quot;coordinates&quot;:[9.74,47.41]}"></div>"#;
+        assert_eq!(extract_plot_polygon(point), None);
+        assert_eq!(extract_plot_polygon("<div data-map-polygon-value=\"Polygon\"></div>"), None);
+    }
+}
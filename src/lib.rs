@@ -1,4 +1,5 @@
 pub mod models;
+pub mod filter;
 pub mod parser;
 pub mod scraper;
 pub mod utils;
@@ -7,4 +8,13 @@ pub mod common_scraper;
 pub mod scrapers;
 pub mod tui;
 pub mod debug;
-pub mod geocoding;
\ No newline at end of file
+pub mod geocoding;
+pub mod cookies;
+pub mod ratelimit;
+pub mod storage;
+pub mod report;
+pub mod analysis;
+pub mod error;
+pub mod scraping_session;
+pub mod http_logging;
+pub mod config;
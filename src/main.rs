@@ -1,68 +1,812 @@
-use anyhow::Result;
-use clap::Parser;
-use laendlefinder::common_scraper::{ScrapingOptions, run_scraper_with_options, scrape_single_url};
-use laendlefinder::scrapers::{VolScraper, LaendleimmoScraper};
-use laendlefinder::{debug, utils, geocoding};
+use anyhow::{Context, Result};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use laendlefinder::common_scraper::{backfill_driving_distances, PlatformScraper, PostScrapeHook, ScrapingContext, ScrapingOptions, batch_scrape_urls, deduplicate_properties_by_internal_id, run_scraper_with_options, scrape_input_csv, scrape_single_url, test_scraper};
+use laendlefinder::scrapers::{self, VolScraper, VolGarageScraper, LaendleimmoScraper};
+use laendlefinder::cookies::{self, CookieValidationResult};
+use laendlefinder::filter::PropertyQuery;
+use laendlefinder::models::{Coordinates, Locale, Property, PropertyFilter, PropertyKind, PropertyType};
+use laendlefinder::scraping_session::{RunRecord, Session};
+use laendlefinder::tui::{IconMode, ScraperTUI};
+use laendlefinder::{analysis, common_scraper, config, debug, http_logging, utils, geocoding, laendleimmo_scraper, report, storage};
+use laendlefinder::debug_println;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about = "Laendlefinder - Property Scraper for Vorarlberg")]
 struct Args {
-    /// Path to output CSV file
-    #[clap(short, long, default_value = "properties.csv")]
+    /// Path to the output file, in the format of its extension (.csv, .geojson or .db)
+    #[clap(short, long, default_value = "properties.csv", env = "LAENDLEFINDER_OUTPUT")]
     output: String,
     
+    /// Read option defaults from this TOML file instead of ~/.config/laendlefinder/config.toml
+    #[clap(long, value_name = "FILE", conflicts_with = "no_config")]
+    config: Option<String>,
+    
+    /// Don't load the default config file
+    #[clap(long)]
+    no_config: bool,
+    
+    /// Write to an SQLite database instead of the CSV file
+    #[clap(long, value_name = "DB", conflicts_with = "output")]
+    output_sqlite: Option<String>,
+    
+    /// Export all properties of an SQLite database to the --output CSV file and exit
+    #[clap(long, value_name = "DB", conflicts_with_all = ["output_sqlite", "csv_to_sqlite"])]
+    sqlite_to_csv: Option<String>,
+    
+    /// Import all properties of the --output CSV file into an SQLite database and exit
+    #[clap(long, value_name = "DB", conflicts_with = "output_sqlite")]
+    csv_to_sqlite: Option<String>,
+    
+    /// Scrape one current property per enabled platform, report PASS/FAIL and exit
+    #[clap(long)]
+    test_scraper: bool,
+    
+    /// Rewrite the --output file with all fields in their canonical form and exit
+    #[clap(long)]
+    normalize_csv: bool,
+    
+    /// Export all properties of the --output file as a GeoJSON FeatureCollection and exit
+    #[clap(long, value_name = "FILE")]
+    export_geojson: Option<String>,
+    
+    /// Write an HTML report with a monthly median price chart of the --output file and exit
+    #[clap(long, value_name = "FILE")]
+    html_report: Option<String>,
+    
+    /// Export all properties of the --output file as a JSON array and exit
+    #[clap(long, value_name = "FILE")]
+    export_json: Option<String>,
+    
+    /// Export all properties of the --output file as a Parquet file and exit (needs the `parquet` feature)
+    #[clap(long, value_name = "FILE")]
+    export_parquet: Option<String>,
+    
+    /// Show agencies of the --output file ranked by listing count with their average price and exit
+    #[clap(long)]
+    stats_by_agency: bool,
+    
+    /// Show municipalities of the --output file ranked by listing count with their price range and exit
+    #[clap(long)]
+    stats_by_location: bool,
+    
+    /// Also write the --stats-by-location results as location_stats.json into DIR
+    #[clap(long, value_name = "DIR", requires = "stats_by_location")]
+    stats_output: Option<String>,
+    
+    /// List properties whose price per m² is more than Z standard deviations off their type's mean (default 3) and exit
+    #[clap(long, value_name = "Z", num_args = 0..=1, default_missing_value = "3")]
+    analyze_outliers: Option<f64>,
+    
+    /// Show property counts and data completeness of the --output file and exit
+    #[clap(long)]
+    stats: bool,
+    
+    /// Merge all CSV files in DIR matching --merge-pattern into the --output file and exit
+    #[clap(long, value_name = "DIR")]
+    merge_dir: Option<String>,
+    
+    /// File name pattern of the CSV files merged by --merge-dir, with `*` and `?` wildcards
+    #[clap(long, value_name = "GLOB", default_value = "*.csv", requires = "merge_dir")]
+    merge_pattern: String,
+    
+    /// Merge properties from a GeoJSON FeatureCollection into the --output file and exit
+    #[clap(long, value_name = "FILE", conflicts_with = "export_geojson")]
+    import_geojson: Option<String>,
+    
     /// Optional cookies for vol.at authenticated requests
-    #[clap(short, long, default_value = "cookies.txt")]
+    #[clap(short, long, default_value = "cookies.txt", env = "LAENDLEFINDER_COOKIES")]
     cookies: Option<String>,
     
     /// Maximum number of pages to scrape per platform
-    #[clap(short, long)]
+    #[clap(short, long, env = "LAENDLEFINDER_MAX_PAGES")]
     max_pages: Option<usize>,
     
     /// Maximum number of items to scrape per platform (if not set, scrape all available items)
-    #[clap(short = 'i', long)]
+    #[clap(short = 'i', long, env = "LAENDLEFINDER_MAX_ITEMS")]
     max_items: Option<usize>,
     
     /// Re-scrape already known URLs to refresh data older than N days (default: 1 day)
-    #[clap(short, long, value_name = "DAYS", num_args = 0..=1, default_missing_value = "1")]
+    #[clap(short, long, value_name = "DAYS", num_args = 0..=1, default_missing_value = "1", env = "LAENDLEFINDER_REFRESH")]
     refresh: Option<u32>,
     
     /// Scrape new URLs until no new ones found in 5 consecutive pages (default mode unless other options specified)
-    #[clap(short, long)]
+    #[clap(short, long, env = "LAENDLEFINDER_NEW")]
     new: bool,
     
+    /// Gather new vol.at URLs from the RSS feed instead of paging through the topic
+    #[clap(long, env = "LAENDLEFINDER_USE_RSS")]
+    use_rss: bool,
+    
     /// Skip vol.at scraper
-    #[clap(long)]
+    #[clap(long, env = "LAENDLEFINDER_SKIP_VOL")]
     skip_vol: bool,
     
+    /// Also scrape sold garages and parking spaces from vol.at
+    #[clap(long, env = "LAENDLEFINDER_INCLUDE_VOL_GARAGES")]
+    include_vol_garages: bool,
+    
     /// Skip laendleimmo.at scraper
-    #[clap(long)]
+    #[clap(long, env = "LAENDLEFINDER_SKIP_LAENDLEIMMO")]
     skip_laendleimmo: bool,
     
     /// Enable debug output
-    #[clap(short, long)]
+    #[clap(short, long, env = "LAENDLEFINDER_DEBUG")]
     debug: bool,
     
+    /// Use ASCII status icons instead of Unicode ones (auto-detected by default)
+    #[clap(long)]
+    no_unicode: bool,
+    
+    /// Show the properties being scraped in a grid of N per row (default 3) instead of one per line
+    #[clap(long, value_name = "N", num_args = 0..=1, default_missing_value = "3")]
+    compact_tui: Option<usize>,
+    
+    /// Write the output file after every N scraped properties instead of after each one
+    #[clap(long, value_name = "N", default_value_t = 1, env = "LAENDLEFINDER_SAVE_EVERY")]
+    save_every: usize,
+    
+    /// Record all HTTP requests and responses in this HAR file for debugging
+    #[clap(long, value_name = "FILE", env = "LAENDLEFINDER_LOG_REQUESTS")]
+    log_requests: Option<String>,
+    
+    /// Log fetch and parse calls that take longer than N milliseconds (with --debug)
+    #[clap(long, value_name = "N", env = "LAENDLEFINDER_TRACE_SLOW_MS")]
+    trace_slow_ms: Option<u64>,
+    
     /// Scrape a specific URL and update only that entry in the database
     #[clap(short = 'u', long)]
     url: Option<String>,
     
+    /// Scrape all URLs listed in a text file (one per line) and merge them into the database
+    #[clap(long, value_name = "FILE", conflicts_with = "url")]
+    input_urls: Option<String>,
+    
+    /// Re-scrape all properties of an existing CSV file without listing discovery (honours --refresh)
+    #[clap(long, value_name = "FILE", conflicts_with_all = ["url", "input_urls"])]
+    input_csv: Option<String>,
+    
     /// Fill in missing coordinates using address/location geocoding
     #[clap(short, long)]
     locate: bool,
+    
+    /// Fill in missing addresses of properties with coordinates using reverse geocoding
+    #[clap(long)]
+    fill_missing_addresses: bool,
+    
+    /// Skip scraped properties with a living size below this value (m²)
+    #[clap(long, value_name = "SQM", env = "LAENDLEFINDER_MIN_SIZE_LIVING")]
+    min_size_living: Option<f64>,
+    
+    /// Skip scraped properties with a living size above this value (m²)
+    #[clap(long, value_name = "SQM", env = "LAENDLEFINDER_MAX_SIZE_LIVING")]
+    max_size_living: Option<f64>,
+    
+    /// Only gather laendleimmo.at listings with at least N rooms, filtered by its search
+    #[clap(long, value_name = "N", env = "LAENDLEFINDER_MIN_ROOMS")]
+    min_rooms: Option<u32>,
+    
+    /// Only gather laendleimmo.at listings up to this price in euros, filtered by its search
+    #[clap(long, value_name = "EUR", env = "LAENDLEFINDER_MAX_PRICE")]
+    max_price: Option<u64>,
+    
+    /// Only gather laendleimmo.at listings of TYPE (apartment, house, land, garage, commercial), classified by their URL
+    #[clap(long, value_name = "TYPE", value_parser = parse_property_kind, env = "LAENDLEFINDER_GATHER_PROPERTY_TYPE")]
+    gather_property_type: Option<PropertyKind>,
+    
+    /// Only scrape laendleimmo.at properties in this district (can be repeated)
+    #[clap(long, value_name = "DISTRICT", env = "LAENDLEFINDER_REGION", value_delimiter = ',')]
+    region: Vec<String>,
+    
+    /// Write the property scraped via --url as a Markdown card to this file
+    #[clap(long, value_name = "FILE", requires = "url")]
+    output_markdown: Option<String>,
+    
+    /// Append Markdown cards of all newly scraped properties to this file
+    #[clap(long, value_name = "FILE")]
+    append_markdown: Option<String>,
+    
+    /// Also write the properties added or changed by this run to this CSV file
+    #[clap(long, value_name = "FILE", env = "LAENDLEFINDER_DIFF_OUTPUT")]
+    diff_output: Option<String>,
+    
+    /// Write URLs discovered for scraping to this file, one per line
+    #[clap(long, value_name = "FILE", env = "LAENDLEFINDER_EXPORT_URLS")]
+    export_urls: Option<String>,
+    
+    /// Gather URLs but don't scrape property pages or modify the database; on its own, list the newest stored properties
+    #[clap(long, env = "LAENDLEFINDER_DRY_RUN")]
+    dry_run: bool,
+    
+    /// Discover property URLs from the sites' sitemaps instead of their listing pages
+    #[clap(long, env = "LAENDLEFINDER_BOOTSTRAP_FROM_SITEMAP")]
+    bootstrap_from_sitemap: bool,
+    
+    /// Fetch laendleimmo.at pages again without caches if they were last modified more than N days ago
+    #[clap(long, value_name = "N", env = "LAENDLEFINDER_MIN_FRESHNESS_DAYS")]
+    min_freshness_days: Option<u32>,
+    
+    /// Maximum number of properties to scrape in total across all scrapers
+    #[clap(long, value_name = "N", env = "LAENDLEFINDER_MAX_ITEMS_PER_RUN")]
+    max_items_per_run: Option<usize>,
+    
+    /// Re-geocode these property URLs even if they already have coordinates (comma-separated)
+    #[clap(long, value_delimiter = ',', value_name = "URLS")]
+    force_geocode_urls: Vec<String>,
+    
+    /// Re-geocode all properties even if they already have coordinates
+    #[clap(long)]
+    force_geocode_all: bool,
+    
+    /// When forcing, only re-geocode properties with a geocoding quality score below N (0-100)
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u8).range(0..=100))]
+    min_geocode_quality: Option<u8>,
+    
+    /// Maximum property page requests per second and platform
+    #[clap(long, value_name = "F", default_value_t = 0.5, env = "LAENDLEFINDER_REQUESTS_PER_SECOND")]
+    requests_per_second: f64,
+    
+    /// Rotate through the user agents in this file (one per line) in addition to the built-in ones
+    #[clap(long, value_name = "FILE", env = "LAENDLEFINDER_ADDITIONAL_UA")]
+    additional_ua: Option<String>,
+    
+    /// Retry failed property page requests up to N times
+    #[clap(long, value_name = "N", default_value_t = 0, env = "LAENDLEFINDER_MAX_RETRIES")]
+    max_retries: u8,
+    
+    /// Per-domain retry counts overriding --max-retries, e.g. --retry-override vol.at=5 laendleimmo.at=1
+    #[clap(long, value_name = "DOMAIN=N", num_args = 1.., value_parser = parse_retry_override, value_delimiter = ',', env = "LAENDLEFINDER_RETRY_OVERRIDE")]
+    retry_override: Vec<(String, u8)>,
+    
+    /// Send this HTTP header with every request to the scraped sites, e.g. --header "Accept-Language: en" (repeatable, `|`-separated in the environment)
+    #[clap(long, value_name = "KEY: VALUE", value_parser = parse_http_header, value_delimiter = '|', env = "LAENDLEFINDER_HEADER")]
+    header: Vec<(String, String)>,
+    
+    /// Record each scraper run in this JSON session file, starting a new session if it does not exist
+    #[clap(long, value_name = "FILE", env = "LAENDLEFINDER_SESSION_FILE")]
+    session_file: Option<String>,
+    
+    /// Print a summary of all runs recorded in the --session-file and exit
+    #[clap(long, requires = "session_file")]
+    session_report: bool,
+    
+    /// Run this shell command with the output file path as argument after each scraper finishes
+    #[clap(long, value_name = "COMMAND", env = "LAENDLEFINDER_POST_HOOK")]
+    post_hook: Option<String>,
+    
+    /// Number of property page requests allowed in a burst
+    #[clap(long, value_name = "N", default_value_t = 1, env = "LAENDLEFINDER_BURST")]
+    burst: u32,
+    
+    /// Search stored properties whose description contains TERM (case-insensitive) and exit
+    #[clap(long, value_name = "TERM")]
+    search_description: Option<String>,
+    
+    /// Search stored properties having FEATURE, e.g. "sauna" or "Terrasse" (repeatable) and exit
+    #[clap(long, value_name = "FEATURE")]
+    has_feature: Vec<String>,
+    
+    /// Search stored properties of TYPE (apartment, house, land, garage, commercial, unknown, unavailable; repeatable) and exit; to scrape only one type use --gather-property-type
+    #[clap(long, value_name = "TYPE", value_parser = parse_search_property_kind)]
+    property_type: Vec<PropertyKind>,
+    
+    /// Search stored properties in MUNICIPALITY, e.g. "Dornbirn" (repeatable) and exit
+    #[clap(long, value_name = "MUNICIPALITY")]
+    location_municipality: Vec<String>,
+    
+    /// Search stored properties in DISTRICT, e.g. "Feldkirch" (repeatable) and exit
+    #[clap(long, value_name = "DISTRICT")]
+    location_district: Vec<String>,
+    
+    /// Search stored properties built at least N years ago and exit
+    #[clap(long, value_name = "N")]
+    min_age_years: Option<u32>,
+    
+    /// Search stored properties built at most N years ago and exit
+    #[clap(long, value_name = "N", alias = "filter-age-years-max")]
+    max_age_years: Option<u32>,
+    
+    /// Search stored properties matching a query like "type:house OR type:land price:<500000" and exit
+    #[clap(long, value_name = "QUERY")]
+    query: Option<String>,
+    
+    /// Search stored properties first listed within the last N days and exit
+    #[clap(long, value_name = "N")]
+    fresh_listings: Option<u32>,
+    
+    /// List stored properties first seen within the last N days, newest first, and exit
+    #[clap(long, value_name = "DAYS")]
+    report_new_since: Option<u32>,
+    
+    /// Ignore properties without coordinates in search, the --stats-by-* tables and --html-report
+    #[clap(long)]
+    coordinates_only: bool,
+    
+    /// Search stored properties within N km driving distance of --commute-from and exit, computing missing distances first
+    #[clap(long, value_name = "N", env = "LAENDLEFINDER_MAX_COMMUTE_KM")]
+    max_commute_km: Option<f64>,
+    
+    /// Compute the driving distance from this point for each scraped property, and for stored ones with --max-commute-km
+    #[clap(long, value_name = "LAT,LNG", env = "LAENDLEFINDER_COMMUTE_FROM")]
+    commute_from: Option<Coordinates>,
+    
+    /// Save the CSV by writing a temporary file and renaming it, so a crash never corrupts it
+    #[clap(long, default_value_t = true, action = clap::ArgAction::Set, value_name = "BOOL", env = "LAENDLEFINDER_ATOMIC_WRITE")]
+    atomic_write: bool,
+    
+    /// Skip malformed CSV rows with a warning instead of failing; they are kept in <file>.rejected.csv and dropped from the CSV when it is saved again
+    #[clap(long, env = "LAENDLEFINDER_LENIENT_CSV")]
+    lenient_csv: bool,
+    
+    /// Remove sold/unavailable properties dated more than DAYS ago from the database (use with --dry-run to preview)
+    #[clap(long, value_name = "DAYS")]
+    prune_sold_older_than: Option<u32>,
+    
+    /// Verify the enabled sites respond in time with the expected data before scraping
+    #[clap(long)]
+    health_check: bool,
+    
+    /// Check whether the vol.at cookies still work and explain how to refresh them
+    #[clap(long)]
+    check_cookies: bool,
+}
+
+/// Load the properties of `path` for stats and reports, optionally dropping those without coordinates
+fn load_analysis_properties(path: &str, csv: storage::CsvOptions, coordinates_only: bool) -> Result<Vec<Property>> {
+    let properties = storage::load_properties(path, csv)?;
+    let missing = report::missing_coordinates_count(&properties);
+    if !coordinates_only {
+        if missing > 0 {
+            println!("{} of {} properties have no coordinates (exclude them with --coordinates-only)", missing, properties.len());
+        }
+        return Ok(properties);
+    }
+    println!("Excluding {} of {} properties without coordinates", missing, properties.len());
+    let filter = PropertyFilter::new().with_coordinates_only(true);
+    Ok(utils::filter_properties(&properties, &filter).into_iter().cloned().collect())
+}
+
+/// Parse a `DOMAIN=N` retry override
+fn parse_retry_override(s: &str) -> std::result::Result<(String, u8), String> {
+    let (domain, retries) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected DOMAIN=N, got '{}'", s))?;
+    let retries = retries
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid retry count '{}'", retries))?;
+    Ok((utils::domain_from_url(domain.trim()).to_string(), retries))
+}
+
+/// Parse a property type that laendleimmo.at listing URLs can be classified as
+fn parse_property_kind(s: &str) -> std::result::Result<PropertyKind, String> {
+    match PropertyType::from_string(s).kind {
+        PropertyKind::Unknown | PropertyKind::Unavailable => Err(format!("unknown property type '{}'", s)),
+        kind => Ok(kind),
+    }
+}
+
+/// Parse a property type to search for, which unlike `parse_property_kind` may also be unknown or unavailable
+fn parse_search_property_kind(s: &str) -> std::result::Result<PropertyKind, String> {
+    if s.trim().eq_ignore_ascii_case("unknown") {
+        return Ok(PropertyKind::Unknown);
+    }
+    match PropertyType::from_string(s).kind {
+        PropertyKind::Unknown => Err(format!("unknown property type '{}'", s)),
+        kind => Ok(kind),
+    }
 }
 
+/// Parse a `KEY: VALUE` HTTP header
+fn parse_http_header(s: &str) -> std::result::Result<(String, String), String> {
+    let (name, value) = s
+        .split_once(':')
+        .ok_or_else(|| format!("expected KEY: VALUE, got '{}'", s))?;
+    let (name, value) = (name.trim(), value.trim());
+    reqwest::header::HeaderName::from_bytes(name.as_bytes())
+        .map_err(|_| format!("invalid header name '{}'", name))?;
+    reqwest::header::HeaderValue::from_str(value)
+        .map_err(|_| format!("invalid value for header '{}'", name))?;
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Set the environment variables of the options in the config file, returning the file's path
+///
+/// The default config file is only read if it exists. Options already set in
+/// the environment keep their value, and command line flags override both.
+fn apply_config_file(command: &clap::Command, args: &Args) -> Result<Option<PathBuf>> {
+    let path = match &args.config {
+        Some(path) => PathBuf::from(path),
+        None if args.no_config => return Ok(None),
+        None => {
+            let path = config::default_config_path();
+            if !path.exists() {
+                return Ok(None);
+            }
+            path
+        }
+    };
+
+    for (key, values) in config::load_config(&path)? {
+        let arg = command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(key.as_str()))
+            .ok_or_else(|| anyhow::anyhow!("Unknown option '{}' in config file {}", key, path.display()))?;
+        let env = arg.get_env().ok_or_else(|| {
+            anyhow::anyhow!(
+                "--{} can't be set in config file {}, only options with an environment variable can",
+                key,
+                path.display()
+            )
+        })?;
+        let value = match (values.as_slice(), arg.get_value_delimiter()) {
+            ([value], _) => value.clone(),
+            (values, Some(delimiter)) => values.join(&delimiter.to_string()),
+            (_, None) => {
+                return Err(anyhow::anyhow!("--{} takes a single value, not a list, in config file {}", key, path.display()));
+            }
+        };
+        if std::env::var_os(env).is_none() {
+            std::env::set_var(env, value);
+        }
+    }
+    Ok(Some(path))
+}
+
+/// Number of properties listed by a bare --dry-run
+const DRY_RUN_NEWEST_COUNT: usize = 20;
+
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let mut command = Args::command();
+    let mut arg_matches = command.clone().get_matches();
+    let mut args = Args::from_arg_matches(&arg_matches).unwrap_or_else(|e| e.exit());
+    let loaded_config = apply_config_file(&command, &args)?;
+    if loaded_config.is_some() {
+        // clap reads the environment when building the command, so build it again
+        command = Args::command();
+        arg_matches = command.clone().get_matches();
+        args = Args::from_arg_matches(&arg_matches).unwrap_or_else(|e| e.exit());
+    }
+    // --dry-run without any other command line flag but --output lists the newest properties instead of scraping
+    let dry_run_only = args.dry_run
+        && command.get_arguments().map(|arg| arg.get_id().as_str()).all(|id| {
+            matches!(id, "dry_run" | "output" | "config" | "no_config") || arg_matches.value_source(id) != Some(ValueSource::CommandLine)
+        });
+    
+    let csv = storage::CsvOptions { lenient: args.lenient_csv, atomic_write: args.atomic_write };
+    
+    // Forcing re-geocoding implies --locate
+    args.locate |= args.force_geocode_all || !args.force_geocode_urls.is_empty();
+    
+    // All storage access picks the backend by file extension
+    if let Some(db) = args.output_sqlite.take() {
+        if !storage::is_sqlite_path(&db) {
+            return Err(anyhow::anyhow!("--output-sqlite expects a .db, .sqlite or .sqlite3 file, got {}", db));
+        }
+        args.output = db;
+    }
+    
+    let force_geocode = geocoding::ForceGeocodeOptions {
+        urls: args.force_geocode_urls.iter().map(|url| utils::sanitize_url(url)).collect(),
+        all: args.force_geocode_all,
+        min_quality: args.min_geocode_quality,
+    };
     
     // Set debug flag early
     debug::set_debug(args.debug);
+    if let Some(path) = &loaded_config {
+        debug_println!("Loaded config from {}", path.display());
+    }
+    let icon_mode = if args.no_unicode || !ScraperTUI::detect_unicode_support() {
+        IconMode::PlainAsciiMode
+    } else {
+        IconMode::Unicode
+    };
+    common_scraper::install_interrupt_handler()?;
+    if let Some(threshold_ms) = args.trace_slow_ms {
+        debug::set_trace_slow_ms(threshold_ms);
+    }
+    if let Some(path) = &args.log_requests {
+        http_logging::set_har_output(path);
+    }
+    // Settings of every mode, scraping adds its own options on top
+    let base_options = || {
+        ScrapingOptions::builder()
+            .output_file(&args.output)
+            .http_headers(args.header.clone())
+            .use_rss(args.use_rss)
+            .csv(csv)
+            .icon_mode(icon_mode)
+            .compact_columns(args.compact_tui.unwrap_or(1))
+    };
     
     if !args.debug {
         println!("Laendlefinder - Property Scraper for Vorarlberg");
         println!("===============================================");
     }
     
+    if args.check_cookies {
+        let cookie_file = args.cookies.clone().unwrap_or_default();
+        let cookie_str = if cookie_file.is_empty() {
+            String::new()
+        } else {
+            cookies::load_cookie_string(&cookie_file)?
+        };
+        
+        let options = base_options().build();
+        match cookies::validate_cookies(&mut ScrapingContext::new(&options), &cookie_str)? {
+            CookieValidationResult::Valid => {
+                println!("✅ Cookies are valid");
+                return Ok(());
+            }
+            CookieValidationResult::Expired => println!("❌ Cookies are expired or invalid\n"),
+            CookieValidationResult::Missing => println!("❌ No cookies provided\n"),
+        }
+        cookies::print_cookie_instructions(&cookie_file);
+        return Err(anyhow::anyhow!("vol.at cookies need to be refreshed"));
+    }
+    
+    if let Some(db) = &args.sqlite_to_csv {
+        let count = storage::migrate(&storage::SqliteStorage::new(db), &storage::CsvStorage::new(&args.output, csv))?;
+        println!("Exported {} properties from {} to {}", count, db, args.output);
+        return Ok(());
+    }
+    
+    if let Some(db) = &args.csv_to_sqlite {
+        let count = storage::migrate(&storage::CsvStorage::new(&args.output, csv), &storage::SqliteStorage::new(db))?;
+        println!("Imported {} properties from {} into {}", count, args.output, db);
+        return Ok(());
+    }
+    
+    if args.normalize_csv {
+        let properties = storage::load_properties(&args.output, csv)?;
+        storage::save_properties(&properties, &args.output, csv)?;
+        println!("Normalized {} properties in {}", properties.len(), args.output);
+        return Ok(());
+    }
+    
+    if let Some(path) = &args.export_geojson {
+        let properties = storage::load_properties(&args.output, csv)?;
+        utils::save_properties_to_geojson(&properties, path)?;
+        println!("Exported {} properties from {} to {}", properties.len(), args.output, path);
+        return Ok(());
+    }
+    
+    if let Some(path) = &args.export_json {
+        let properties = storage::load_properties(&args.output, csv)?;
+        utils::save_properties_to_json(&properties, path)?;
+        println!("Exported {} properties from {} to {}", properties.len(), args.output, path);
+        return Ok(());
+    }
+    
+    if let Some(path) = &args.export_parquet {
+        let properties = storage::load_properties(&args.output, csv)?;
+        utils::save_properties_to_parquet(&properties, path)?;
+        println!("Exported {} properties from {} to {}", properties.len(), args.output, path);
+        return Ok(());
+    }
+    
+    let locale = Locale::austrian();
+    
+    if args.stats_by_agency {
+        let properties = load_analysis_properties(&args.output, csv, args.coordinates_only)?;
+        let stats = report::compute_agency_stats(&properties);
+        println!("{:<4} {:<40} {:>8} {:>14}", "#", "Agency", "Listings", "Avg. price");
+        for (rank, agency) in stats.iter().enumerate() {
+            let average = agency.average_price.map_or("-".to_string(), |p| locale.format_euros(p.round()));
+            println!("{:<4} {:<40} {:>8} {:>14}", rank + 1, agency.agency, agency.listings, average);
+        }
+        println!("\n{} agencies, {} of {} properties have an agency", stats.len(),
+            stats.iter().map(|a| a.listings).sum::<usize>(), properties.len());
+        return Ok(());
+    }
+    
+    if args.stats_by_location {
+        let properties = load_analysis_properties(&args.output, csv, args.coordinates_only)?;
+        let density = report::compute_location_density(&properties);
+        println!("{:<4} {:<30} {:>8} {:>14} {:>14} {:>14}", "#", "Municipality", "Listings", "Mean price", "Min price", "Max price");
+        for (rank, (location, stats)) in report::rank_locations(&density).iter().enumerate() {
+            let euros = |price: Option<f64>| price.map_or("-".to_string(), |price| locale.format_euros(price.round()));
+            println!("{:<4} {:<30} {:>8} {:>14} {:>14} {:>14}", rank + 1, location, stats.count,
+                euros(stats.mean_price), euros(stats.min_price), euros(stats.max_price));
+        }
+        if let Some(dir) = &args.stats_output {
+            let path = std::path::Path::new(dir).join("location_stats.json");
+            std::fs::write(&path, serde_json::to_string_pretty(&density)?)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("\nWrote location stats to {}", path.display());
+        }
+        return Ok(());
+    }
+    
+    if let Some(z_threshold) = args.analyze_outliers {
+        let properties = load_analysis_properties(&args.output, csv, args.coordinates_only)?;
+        let stats = analysis::price_per_sqm_stats(&properties);
+        let outliers = analysis::find_price_per_sqm_outliers(&properties, z_threshold);
+        for (property, z) in &outliers {
+            let kind = property.property_type.kind;
+            let (mean, std_dev) = stats[&kind];
+            println!("{} | {} | {}", property.name, property.display_price(locale.clone()), property.url);
+            println!("    {}/m² (z = {:+.1}), expected for {} {} – {}/m²",
+                locale.format_euros(analysis::price_per_sqm(property).unwrap_or_default().round()), z, kind,
+                locale.format_euros((mean - z_threshold * std_dev).max(0.0).round()),
+                locale.format_euros((mean + z_threshold * std_dev).round()));
+        }
+        println!("\n{} of {} properties are price per m² outliers (|z| > {})", outliers.len(), properties.len(), z_threshold);
+        return Ok(());
+    }
+    
+    if args.session_report {
+        let path = args.session_file.as_deref().unwrap_or_default();
+        println!("{}", Session::load(path)?.report());
+        return Ok(());
+    }
+    
+    if args.stats {
+        let properties = load_analysis_properties(&args.output, csv, args.coordinates_only)?;
+        let count = |listed: fn(&Property) -> bool| properties.iter().filter(|p| listed(p)).count();
+        println!("{} properties: {} available, {} sold, {} unavailable",
+            properties.len(), count(Property::is_available), count(Property::is_sold), count(Property::is_unavailable));
+        let unavailable = report::count_unavailable_by_reason(&properties);
+        if !unavailable.is_empty() {
+            let reasons: Vec<String> = unavailable.iter().map(|(reason, count)| format!("{} {}", count, reason)).collect();
+            println!("Unavailable by reason: {}", reasons.join(", "));
+        }
+        if let Some((mean, median)) = report::completeness_stats(&properties) {
+            println!("Data completeness: mean {:.0}%, median {:.0}%", mean * 100.0, median * 100.0);
+        }
+        let ages = report::age_histogram(&properties);
+        if let Some(max_count) = ages.iter().map(|(_, count)| *count).max() {
+            println!("\nProperty age (years):");
+            for (bucket, count) in &ages {
+                println!("{:>7} {:>6} {}", format!("{}-{}", bucket, bucket + 9), count, "#".repeat((count * 40).div_ceil(max_count)));
+            }
+        }
+        let confidences = report::confidence_distribution(&properties);
+        if !confidences.is_empty() {
+            println!("\nProperty type confidence:");
+            for (confidence, count) in &confidences {
+                println!("{:>7.1} {:>6}", confidence, count);
+            }
+        }
+        return Ok(());
+    }
+    
+    if let Some(path) = &args.html_report {
+        let properties = load_analysis_properties(&args.output, csv, args.coordinates_only)?;
+        report::generate_html_report(&properties, path)?;
+        println!("Wrote price report of {} properties to {}", properties.len(), path);
+        return Ok(());
+    }
+    
+    if let Some(path) = &args.import_geojson {
+        let imported = utils::load_properties_from_geojson(path)?;
+        let count = imported.len();
+        let mut properties = storage::load_properties(&args.output, csv)?;
+        properties.extend(imported);
+        let properties = deduplicate_properties_by_internal_id(properties);
+        storage::save_properties(&properties, &args.output, csv)?;
+        println!("Imported {} properties from {} into {}", count, path, args.output);
+        return Ok(());
+    }
+    
+    if let Some(dir) = &args.merge_dir {
+        let output = std::fs::canonicalize(&args.output).ok();
+        let files: Vec<_> = utils::auto_discover_csv_files(dir, &args.merge_pattern)?
+            .into_iter()
+            .filter(|file| std::fs::canonicalize(file).ok() != output)
+            .collect();
+        // The output takes its place among the files by age, so newer data always wins
+        let mut sources = files.clone();
+        sources.extend(output.clone());
+        let mut properties = Vec::new();
+        for file in utils::sort_by_modified(sources)? {
+            let path = file.to_string_lossy();
+            let merged = if Some(&file) == output.as_ref() {
+                storage::load_properties(&args.output, csv)?
+            } else {
+                utils::load_properties_from_csv(&path, csv.lenient)?
+            };
+            println!("Merging {} properties from {}", merged.len(), path);
+            properties.extend(merged);
+        }
+        let properties = deduplicate_properties_by_internal_id(properties);
+        storage::save_properties(&properties, &args.output, csv)?;
+        println!("Merged {} files into {} ({} properties)", files.len(), args.output, properties.len());
+        return Ok(());
+    }
+    
+    // Search mode: list stored properties matching the search flags
+    if args.search_description.is_some()
+        || !args.has_feature.is_empty()
+        || !args.property_type.is_empty()
+        || !args.location_municipality.is_empty()
+        || !args.location_district.is_empty()
+        || args.max_commute_km.is_some()
+        || args.min_age_years.is_some()
+        || args.max_age_years.is_some()
+        || args.query.is_some()
+        || args.fresh_listings.is_some()
+        || args.report_new_since.is_some()
+        || dry_run_only
+    {
+        let query = args.query.as_deref().map(PropertyQuery::parse).transpose()?;
+        let mut properties = storage::load_properties(&args.output, csv)?;
+        if let (Some(_), Some(from)) = (args.max_commute_km, args.commute_from) {
+            let options = base_options().commute_from(from).build();
+            let added = backfill_driving_distances(&mut properties, &options);
+            if added > 0 {
+                storage::save_properties(&properties, &args.output, csv)?;
+                println!("Computed {} missing driving distances", added);
+            }
+        }
+        let mut filter = PropertyFilter::new()
+            .features(args.has_feature.iter().map(|f| laendleimmo_scraper::normalize_feature(f)).collect())
+            .property_types(args.property_type.clone())
+            .municipalities(args.location_municipality.clone())
+            .districts(args.location_district.clone())
+            .with_coordinates_only(args.coordinates_only);
+        if let Some(term) = &args.search_description {
+            filter = filter.description_contains(term);
+        }
+        if let Some(max_km) = args.max_commute_km {
+            filter = filter.max_driving_distance_km(max_km);
+        }
+        if let Some(years) = args.min_age_years {
+            filter = filter.min_age_years(years);
+        }
+        if let Some(years) = args.max_age_years {
+            filter = filter.max_age_years(years);
+        }
+        let mut matches = utils::filter_properties(&properties, &filter);
+        if let Some(query) = &query {
+            matches.retain(|property| query.matches(property));
+        }
+        if let Some(days) = args.fresh_listings {
+            matches.retain(|property| property.is_fresh(days));
+        }
+        if args.report_new_since.is_some() || dry_run_only {
+            let today = chrono::Utc::now().naive_utc().date();
+            let since = args.report_new_since.map(|days| today - chrono::Duration::days(days as i64));
+            matches = report::newest_first(matches, since);
+        }
+        if dry_run_only {
+            matches.truncate(DRY_RUN_NEWEST_COUNT);
+        }
+        
+        for property in &matches {
+            println!("{} | {} | {} | {}", property.name, property.display_price(locale.clone()), property.location, property.url);
+        }
+        println!("\n{} of {} properties match", matches.len(), properties.len());
+        return Ok(());
+    }
+    
+    if let Some(days) = args.prune_sold_older_than {
+        let properties = storage::load_properties(&args.output, csv)?;
+        let (remaining, pruned_count) = utils::prune_properties(properties, days, args.dry_run);
+        
+        if args.dry_run {
+            println!("Dry run: {} sold/unavailable properties older than {} days would be removed", pruned_count, days);
+        } else {
+            // Pruning deletes data for good, so keep a copy of the file in any format
+            if let Some(backup) = utils::backup_file(&args.output)? {
+                println!("Backed up {} to {}", args.output, backup.display());
+            }
+            storage::save_properties(&remaining, &args.output, csv)?;
+            println!("Removed {} sold/unavailable properties older than {} days ({} remaining)", pruned_count, days, remaining.len());
+        }
+        return Ok(());
+    }
+    
+    if args.fill_missing_addresses {
+        let mut properties = storage::load_properties(&args.output, csv)?;
+        let filled = geocoding::geocode_properties_reverse(&mut properties, &base_options().build())?;
+        println!("Filled in {} missing addresses in {}", filled, args.output);
+        return Ok(());
+    }
+    
     // If only --locate is specified, skip all scraping and just geocode
     if args.locate && args.url.is_none() && !args.new && args.max_items.is_none() && args.max_pages.is_none() && args.refresh.is_none() {
         if !args.debug {
@@ -70,10 +814,10 @@ fn main() -> Result<()> {
         }
         
         // Load properties from CSV
-        let mut properties = utils::load_properties_from_csv(&args.output)?;
+        let mut properties = storage::load_properties(&args.output, csv)?;
         
         // Geocode properties missing coordinates (saves automatically after each success)
-        let _geocoded_count = geocoding::geocode_properties(&mut properties, &args.output)?;
+        let _geocoded_count = geocoding::geocode_properties_with_options(&mut properties, &base_options().build(), &force_geocode)?;
         
         if !args.debug {
             println!("\n=== Geocoding completed ===");
@@ -83,6 +827,24 @@ fn main() -> Result<()> {
         return Ok(());
     }
     
+    if let Some(region) = args.region.iter().find(|r| {
+        !laendleimmo_scraper::VORARLBERG_DISTRICTS.contains(&r.to_lowercase().as_str())
+    }) {
+        return Err(anyhow::anyhow!(
+            "Unknown --region '{}', expected one of: {}",
+            region,
+            laendleimmo_scraper::VORARLBERG_DISTRICTS.join(", ")
+        ));
+    }
+    
+    if args.save_every == 0 {
+        return Err(anyhow::anyhow!("--save-every must be at least 1"));
+    }
+    
+    if args.requests_per_second <= 0.0 {
+        return Err(anyhow::anyhow!("--requests-per-second must be greater than 0"));
+    }
+    
     // Create scraping options
     // Use new mode by default, unless other flags are provided
     let use_new_mode = if args.max_items.is_some() || args.max_pages.is_some() || args.refresh.is_some() {
@@ -91,16 +853,74 @@ fn main() -> Result<()> {
         true // Default to new mode when no specific options provided
     };
     
-    let options = ScrapingOptions {
-        output_file: args.output.clone(),
-        max_pages: args.max_pages,
-        max_items: args.max_items,
-        refresh_days: args.refresh,
-        new: use_new_mode,
-        cookies: args.cookies.clone(),
-        debug: args.debug,
+    let additional_user_agents = match &args.additional_ua {
+        Some(path) => utils::load_user_agents(path)?,
+        None => Vec::new(),
     };
     
+    let options = base_options()
+        .max_pages(args.max_pages)
+        .max_items(args.max_items)
+        .refresh_days(args.refresh)
+        .new_mode(use_new_mode)
+        .cookies(args.cookies.clone())
+        .debug(args.debug)
+        .min_size_living(args.min_size_living)
+        .max_size_living(args.max_size_living)
+        .export_urls(args.export_urls.clone())
+        .dry_run(args.dry_run)
+        .requests_per_second(args.requests_per_second)
+        .burst(args.burst)
+        .total_max_items(args.max_items_per_run)
+        .regions(args.region.iter().map(|r| r.to_lowercase()).collect())
+        .commute_from(args.commute_from)
+        .save_interval(args.save_every)
+        .additional_user_agents(additional_user_agents)
+        .max_retries(args.max_retries)
+        .retry_overrides(args.retry_override.iter().cloned().collect())
+        .bootstrap_from_sitemap(args.bootstrap_from_sitemap)
+        .min_freshness_days(args.min_freshness_days)
+        .listing_filters(laendleimmo_scraper::ListingFilters {
+            property_kind: args.gather_property_type,
+            min_rooms: args.min_rooms,
+            max_price: args.max_price,
+        })
+        .post_scrape_hook(args.post_hook.clone().map(|command| {
+            let output = args.output.clone();
+            PostScrapeHook::new(move |_| utils::run_post_hook_command(&command, &output))
+        }))
+        .build();
+    
+    if args.test_scraper {
+        let mut platforms: Vec<&dyn PlatformScraper> = Vec::new();
+        if !args.skip_vol {
+            platforms.push(&VolScraper);
+        }
+        if !args.skip_laendleimmo {
+            platforms.push(&LaendleimmoScraper);
+        }
+        
+        let mut failed = 0;
+        for scraper in platforms {
+            match test_scraper(scraper, &options) {
+                Ok(url) => println!("PASS {} ({})", scraper.base_url(), url),
+                Err(reason) => {
+                    println!("FAIL {}: {}", scraper.base_url(), reason);
+                    failed += 1;
+                }
+            }
+        }
+        if failed > 0 {
+            return Err(anyhow::anyhow!("{} scraper(s) failed the test", failed));
+        }
+        return Ok(());
+    }
+    
+    // Start with an empty URL export, scrapers append to it
+    if let Some(export_path) = &args.export_urls {
+        std::fs::File::create(export_path)?;
+    }
+    
     // If a specific URL is provided, scrape only that URL
     if let Some(url) = args.url {
         if !args.debug {
@@ -108,29 +928,34 @@ fn main() -> Result<()> {
         }
         
         // Determine which scraper to use based on the URL domain
-        if url.contains("vol.at") {
-            let vol_scraper = VolScraper;
-            scrape_single_url(&vol_scraper, &url, &options)?;
-        } else if url.contains("laendleimmo.at") {
-            let laendleimmo_scraper = LaendleimmoScraper;
-            scrape_single_url(&laendleimmo_scraper, &url, &options)?;
-        } else {
-            return Err(anyhow::anyhow!("Unsupported URL domain. Only vol.at and laendleimmo.at are supported."));
-        }
+        let scraper = scrapers::scraper_for_url(&url)
+            .ok_or_else(|| anyhow::anyhow!("Unsupported URL domain. Only vol.at and laendleimmo.at are supported."))?;
+        scrape_single_url(scraper, &url, &options)?;
         
         // Handle --locate flag for single URL if specified
         if args.locate {
             // Load properties from CSV (includes the just-scraped property)
-            let mut properties = utils::load_properties_from_csv(&args.output)?;
+            let mut properties = storage::load_properties(&args.output, csv)?;
             
             // Geocode only the specific property that was just scraped
             let geocoded = geocoding::geocode_property_by_url(&mut properties, &url)?;
             
             if geocoded {
                 // Save updated properties back to CSV
-                utils::save_properties_to_csv(&properties, &args.output)?;
+                storage::save_properties(&properties, &args.output, csv)?;
             }
         }
+        
+        if let Some(path) = &args.output_markdown {
+            let url = utils::sanitize_url(&url);
+            let properties = storage::load_properties(&args.output, csv)?;
+            let property = properties
+                .iter()
+                .find(|p| p.url == url)
+                .ok_or_else(|| anyhow::anyhow!("Scraped property {} not found in {}", url, args.output))?;
+            std::fs::write(path, property.to_markdown())?;
+            println!("Property card written to: {}", path);
+        }
 
         if !args.debug {
             println!("URL scraping completed. Results saved to: {}", args.output);
@@ -138,39 +963,121 @@ fn main() -> Result<()> {
         return Ok(());
     }
     
-    // Run vol.at scraper (sold properties)
-    if !args.skip_vol {
+    // If a file of URLs or an existing CSV is provided, scrape only those URLs
+    if args.input_urls.is_some() || args.input_csv.is_some() {
+        if let Some(path) = &args.input_urls {
+            let urls = utils::load_urls_from_file(path)?;
+            batch_scrape_urls(&urls, &options)?;
+        }
+        if let Some(path) = &args.input_csv {
+            scrape_input_csv(path, &options)?;
+        }
+        
+        if args.locate && !args.dry_run {
+            let mut properties = storage::load_properties(&args.output, csv)?;
+            geocoding::geocode_properties_with_options(&mut properties, &options, &force_geocode)?;
+        }
+        
         if !args.debug {
-            println!("\n--- Vol.at Scraper ---");
+            println!("Batch scraping completed. Results saved to: {}", args.output);
+        }
+        return Ok(());
+    }
+    
+    if args.health_check {
+        let mut platforms: Vec<&dyn PlatformScraper> = Vec::new();
+        if !args.skip_vol {
+            platforms.push(&VolScraper);
+        }
+        if args.include_vol_garages {
+            platforms.push(&VolGarageScraper);
         }
+        if !args.skip_laendleimmo {
+            platforms.push(&LaendleimmoScraper);
+        }
+        let mut context = ScrapingContext::new(&options);
+        for scraper in platforms {
+            scraper
+                .health_check(&mut context)
+                .with_context(|| format!("Health check of {} failed", scraper.base_url()))?;
+            if !args.debug {
+                println!("✅ {} is healthy", scraper.base_url());
+            }
+        }
+    }
+    
+    let mut runs = Vec::new();
+    let known_urls: HashSet<String> = if args.append_markdown.is_some() {
+        storage::load_properties(&args.output, csv)?.into_iter().map(|p| p.url).collect()
+    } else {
+        HashSet::new()
+    };
+    
+    let properties_before = match &args.diff_output {
+        Some(_) => storage::load_properties(&args.output, csv)?,
+        None => Vec::new(),
+    };
+    
+    // Run vol.at scraper (sold properties)
+    if !args.skip_vol {
         let vol_scraper = VolScraper;
-        run_scraper_with_options(&vol_scraper, &options)?;
+        runs.push(run_scraper_with_options(&vol_scraper, &options)?);
     } else if !args.debug {
         println!("Skipping vol.at scraper");
     }
     
+    // Run vol.at garage scraper (sold garages and parking spaces)
+    if args.include_vol_garages && !options.total_items_exhausted() {
+        let vol_garage_scraper = VolGarageScraper;
+        runs.push(run_scraper_with_options(&vol_garage_scraper, &options)?);
+    }
+
+    
     // Run laendleimmo.at scraper (available properties) 
-    if !args.skip_laendleimmo {
-        if !args.debug {
-            println!("\n--- Laendleimmo.at Scraper ---");
-        }
+    if options.total_items_exhausted() && !args.skip_laendleimmo {
+        println!("Skipping laendleimmo.at scraper: --max-items-per-run limit reached");
+    } else if !args.skip_laendleimmo {
         let laendleimmo_scraper = LaendleimmoScraper;
-        run_scraper_with_options(&laendleimmo_scraper, &options)?;
+        runs.push(run_scraper_with_options(&laendleimmo_scraper, &options)?);
     } else if !args.debug {
         println!("Skipping laendleimmo.at scraper");
     }
     
+    if runs.len() > 1 {
+        ScraperTUI::for_options(&options).show_combined_summary(&runs)?;
+    }
+    
+    if let Some(path) = &args.session_file {
+        let mut session = Session::load_or_new(path)?;
+        session.runs.extend(runs.iter().map(RunRecord::from_run));
+        session.save(path)?;
+    }
+    
     // Handle --locate flag to geocode properties without coordinates (only when combined with scraping)
-    if args.locate {
+    if args.locate && !args.dry_run {
         if !args.debug {
             println!("\n--- Geocoding Properties ---");
         }
         
         // Load properties from CSV
-        let mut properties = utils::load_properties_from_csv(&args.output)?;
+        let mut properties = storage::load_properties(&args.output, csv)?;
         
         // Geocode properties missing coordinates (saves automatically after each success)
-        let _geocoded_count = geocoding::geocode_properties(&mut properties, &args.output)?;
+        let _geocoded_count = geocoding::geocode_properties_with_options(&mut properties, &options, &force_geocode)?;
+    }
+    
+    if let Some(path) = &args.append_markdown {
+        let properties = storage::load_properties(&args.output, csv)?;
+        let new_properties: Vec<_> = properties.iter().filter(|p| !known_urls.contains(&p.url)).collect();
+        utils::append_properties_markdown(&new_properties, path)?;
+        println!("Appended {} property cards to: {}", new_properties.len(), path);
+    }
+
+    if let Some(path) = &args.diff_output {
+        let delta = utils::compute_property_delta(&properties_before, &storage::load_properties(&args.output, csv)?);
+        utils::export_properties_to_csv(&delta.changed(), path)?;
+        println!("Wrote {} added and {} updated properties to {} ({} removed)",
+            delta.added.len(), delta.updated.len(), path, delta.removed.len());
     }
 
     if !args.debug {
@@ -1,7 +1,10 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use crate::debug_println;
+use std::str::FromStr;
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ListingType {
@@ -10,12 +13,42 @@ pub enum ListingType {
     Unavailable,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum PropertyType {
+/// Why a listing became unavailable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnavailableReason {
+    /// The page answered with 404
+    NotFound,
+    /// The listing says the property was sold
+    Sold,
+    /// The listing was archived or deactivated
+    Removed,
+    /// The page is withheld from us, e.g. with 451
+    Blocked,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyKind {
     Apartment,
     House,
     Land,
+    Garage,
+    /// Offices, shops, restaurants and industrial premises
+    Commercial,
+    /// The page was readable but its type could not be classified
     Unknown,
+    /// The type is unknown because the page is gone, e.g. 404 or removed
+    Unavailable,
+}
+
+/// Classified kind of a property and how certain the classification is
+///
+/// `confidence` is 1.0 for exact keyword matches, lower for generic or fuzzy
+/// matches and 0.0 for `Unknown` and `Unavailable`. Only the kind is stored in the CSV.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PropertyType {
+    pub kind: PropertyKind,
+    pub confidence: f32,
 }
 
 impl fmt::Display for ListingType {
@@ -28,17 +61,38 @@ impl fmt::Display for ListingType {
     }
 }
 
-impl fmt::Display for PropertyType {
+impl fmt::Display for UnavailableReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnavailableReason::NotFound => write!(f, "not_found"),
+            UnavailableReason::Sold => write!(f, "sold"),
+            UnavailableReason::Removed => write!(f, "removed"),
+            UnavailableReason::Blocked => write!(f, "blocked"),
+            UnavailableReason::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl fmt::Display for PropertyKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            PropertyType::Apartment => write!(f, "apartment"),
-            PropertyType::House => write!(f, "house"),
-            PropertyType::Land => write!(f, "land"),
-            PropertyType::Unknown => write!(f, "unknown"),
+            PropertyKind::Apartment => write!(f, "apartment"),
+            PropertyKind::House => write!(f, "house"),
+            PropertyKind::Land => write!(f, "land"),
+            PropertyKind::Garage => write!(f, "garage"),
+            PropertyKind::Commercial => write!(f, "commercial"),
+            PropertyKind::Unknown => write!(f, "unknown"),
+            PropertyKind::Unavailable => write!(f, "unavailable"),
         }
     }
 }
 
+impl fmt::Display for PropertyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
 impl Serialize for ListingType {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -54,19 +108,25 @@ impl<'de> Deserialize<'de> for ListingType {
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        match s.as_str() {
-            "available" => Ok(ListingType::Available),
-            "sold" => Ok(ListingType::Sold),
-            "unavailable" => Ok(ListingType::Unavailable),
-            _ => Err(serde::de::Error::custom(format!(
-                "Invalid listing type: {}",
-                s
-            ))),
+        let listing_type = match s.trim().to_lowercase().as_str() {
+            "available" => ListingType::Available,
+            "sold" => ListingType::Sold,
+            "unavailable" | "gone" | "404" | "deleted" => ListingType::Unavailable,
+            _ => {
+                return Err(serde::de::Error::custom(format!(
+                    "Invalid listing type: {}",
+                    s
+                )))
+            }
+        };
+        if s != listing_type.to_string() {
+            debug_println!("Warning: non-canonical listing type '{}' read as '{}'", s, listing_type);
         }
+        Ok(listing_type)
     }
 }
 
-impl Serialize for PropertyType {
+impl Serialize for UnavailableReason {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -75,168 +135,1961 @@ impl Serialize for PropertyType {
     }
 }
 
+impl<'de> Deserialize<'de> for UnavailableReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(match s.trim().to_lowercase().as_str() {
+            "not_found" | "404" => UnavailableReason::NotFound,
+            "sold" => UnavailableReason::Sold,
+            "removed" => UnavailableReason::Removed,
+            "blocked" => UnavailableReason::Blocked,
+            _ => UnavailableReason::Unknown,
+        })
+    }
+}
+
+impl Serialize for PropertyType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.kind.to_string())
+    }
+}
+
 impl<'de> Deserialize<'de> for PropertyType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        match s.as_str() {
-            "apartment" => Ok(PropertyType::Apartment),
-            "house" => Ok(PropertyType::House),
-            "land" => Ok(PropertyType::Land),
-            "unknown" => Ok(PropertyType::Unknown),
-            _ => Ok(PropertyType::Unknown), // Default to Unknown for unrecognized types
-        }
+        let kind = match s.trim().to_lowercase().as_str() {
+            "apartment" => PropertyKind::Apartment,
+            "house" => PropertyKind::House,
+            "land" => PropertyKind::Land,
+            "garage" => PropertyKind::Garage,
+            "commercial" => PropertyKind::Commercial,
+            "unknown" | "" => PropertyKind::Unknown,
+            "unavailable" => PropertyKind::Unavailable,
+            // Other tools' exports, e.g. "Haus" or "Eigentumswohnung"
+            _ => return Ok(PropertyType::from_string(&s)),
+        };
+        Ok(kind.into())
+    }
+}
+
+/// An explicitly assigned kind is fully certain, except for Unknown and Unavailable
+impl From<PropertyKind> for PropertyType {
+    fn from(kind: PropertyKind) -> Self {
+        let confidence = if matches!(kind, PropertyKind::Unknown | PropertyKind::Unavailable) { 0.0 } else { 1.0 };
+        PropertyType { kind, confidence }
     }
 }
 
+/// Confidence of a known kind loaded from a file written before the confidence was stored
+///
+/// As low as the weakest keyword match, so a fresh classification still replaces
+/// it on merge like it did before confidences were compared.
+const LEGACY_TYPE_CONFIDENCE: f32 = 0.5;
+
+/// Keywords per kind in classification order, with the confidence of a match
+///
+/// Specific compound words are exact matches; short generic stems also match
+/// unrelated words (e.g. "grund" in "Grundriss") and get a lower confidence.
+const CLASSIFICATION_KEYWORDS: [(PropertyKind, &[(&str, f32)]); 5] = [
+    (
+        PropertyKind::Apartment,
+        &[("eigentumswohnung", 1.0), ("wohnung", 0.9), ("apartment", 0.9), ("flat", 0.6)],
+    ),
+    (
+        PropertyKind::House,
+        &[
+            ("einfamilienhaus", 1.0),
+            ("reihenhaus", 1.0),
+            ("doppelhaus", 1.0),
+            ("villa", 0.9),
+            ("house", 0.9),
+            ("haus", 0.7),
+        ],
+    ),
+    (
+        PropertyKind::Land,
+        &[
+            ("grundstück", 1.0),
+            ("bauland", 1.0),
+            ("bauplatz", 1.0),
+            ("plot", 0.8),
+            ("grund", 0.6),
+            ("land", 0.5),
+        ],
+    ),
+    (
+        PropertyKind::Garage,
+        &[("garage", 1.0), ("stellplatz", 1.0), ("carport", 1.0), ("parkplatz", 0.9)],
+    ),
+    (
+        PropertyKind::Commercial,
+        &[
+            ("gewerbeobjekt", 1.0),
+            ("bürofläche", 1.0),
+            ("geschäftslokal", 1.0),
+            ("ladenlokal", 1.0),
+            ("lagerhalle", 1.0),
+            ("gastronomie", 0.9),
+            ("gewerbe", 0.9),
+            ("büro", 0.8),
+            ("industrie", 0.8),
+            ("commercial", 0.8),
+        ],
+    ),
+];
+
 impl PropertyType {
+    pub fn new(kind: PropertyKind, confidence: f32) -> Self {
+        PropertyType { kind, confidence }
+    }
+
+    pub fn unknown() -> Self {
+        PropertyKind::Unknown.into()
+    }
+
+    /// Type of a property whose page is gone, so it could not be classified
+    pub fn unavailable() -> Self {
+        PropertyKind::Unavailable.into()
+    }
+
+    /// Whether there is no classification, either from a valid page or because the page is gone
+    pub fn is_unknown(&self) -> bool {
+        matches!(self.kind, PropertyKind::Unknown | PropertyKind::Unavailable)
+    }
+
+    /// Pick the classification with the higher confidence, keeping `self` on ties
+    pub fn more_confident(self, other: PropertyType) -> PropertyType {
+        if other.confidence > self.confidence {
+            other
+        } else {
+            self
+        }
+    }
+
     /// Classify a property type from a string (case-insensitive)
+    ///
+    /// Kinds are checked in order apartment, house, land, garage, commercial; the confidence
+    /// is that of the most specific matching keyword of the first matching kind.
+    /// A plain "unavailable" is the stored form of `PropertyKind::Unavailable`.
     pub fn from_string(input: &str) -> Self {
         let normalized = input.to_lowercase();
+        if normalized.trim() == "unavailable" {
+            return PropertyType::unavailable();
+        }
 
-        // Check for apartment/flat keywords
-        if normalized.contains("wohnung")
-            || normalized.contains("apartment")
-            || normalized.contains("flat")
-            || normalized.contains("eigentumswohnung")
-        {
-            return PropertyType::Apartment;
+        for (kind, keywords) in CLASSIFICATION_KEYWORDS {
+            let confidence = keywords
+                .iter()
+                .filter(|(keyword, _)| normalized.contains(keyword))
+                .map(|(_, confidence)| *confidence)
+                .fold(None, |best: Option<f32>, c| Some(best.map_or(c, |b| b.max(c))));
+            if let Some(confidence) = confidence {
+                return PropertyType::new(kind, confidence);
+            }
         }
 
-        // Check for house keywords
-        if normalized.contains("haus")
-            || normalized.contains("house")
-            || normalized.contains("einfamilienhaus")
-            || normalized.contains("reihenhaus")
-            || normalized.contains("villa")
-            || normalized.contains("doppelhaus")
-        {
-            return PropertyType::House;
+        PropertyType::unknown()
+    }
+
+    /// Classify a laendleimmo.at listing URL, `/immobilien/{main_type}/{sub_type}/...`
+    ///
+    /// Returns None for URLs without type segments, e.g. vol.at articles.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let (_, path) = url.split_once("/immobilien/")?;
+        let mut segments = path.split('/');
+        let main_type = segments.next()?.to_lowercase();
+        let sub_type = segments.next()?.to_lowercase();
+        if main_type.is_empty() || sub_type.is_empty() {
+            return None;
         }
 
-        // Check for land/plot keywords
-        if normalized.contains("grundstück")
-            || normalized.contains("grund")
-            || normalized.contains("land")
-            || normalized.contains("bauland")
-            || normalized.contains("plot")
-            || normalized.contains("bauplatz")
-        {
-            return PropertyType::Land;
+        // Direct mapping for known patterns
+        match main_type.as_str() {
+            "grundstuck" | "grundstueck" => return Some(PropertyKind::Land.into()),
+            "wohnung" => return Some(PropertyKind::Apartment.into()),
+            "haus" => return Some(PropertyKind::House.into()),
+            "gewerbe" | "gewerbeobjekt" => return Some(PropertyKind::Commercial.into()),
+            _ => {}
+        }
+
+        if COMMERCIAL_URL_SEGMENTS.iter().any(|segment| sub_type.contains(segment)) {
+            return Some(PropertyKind::Commercial.into());
+        }
+
+        // Check sub-type patterns for more specific classification
+        if ["grundstuck", "grundstueck", "baugrund", "bauplatz"].iter().any(|s| sub_type.contains(s)) {
+            return Some(PropertyKind::Land.into());
+        }
+        if sub_type.contains("wohnung") || sub_type.contains("apartment") {
+            return Some(PropertyKind::Apartment.into());
+        }
+        if sub_type.contains("haus") || sub_type.contains("villa") {
+            return Some(PropertyKind::House.into());
+        }
+
+        // Fall back to the keywords on main and sub type combined
+        let classified = PropertyType::from_string(&format!("{} {}", main_type, sub_type));
+        (!classified.is_unknown()).then_some(classified)
+    }
+
+    /// Classify a listing from both its title and its URL
+    ///
+    /// A confident URL classification (above 0.8) wins outright. Otherwise the
+    /// more confident of URL and title is used, the URL on ties. If neither
+    /// classifies, the title and the words of the URL path are classified together.
+    pub fn from_title_and_url(title: &str, url: &str) -> Self {
+        let from_url = Self::from_url(url);
+        if let Some(classified) = from_url.filter(|classified| classified.confidence > 0.8) {
+            return classified;
+        }
+
+        let from_title = Self::from_string(title);
+        let best = from_url.map_or(from_title, |classified| classified.more_confident(from_title));
+        if !best.is_unknown() {
+            return best;
+        }
+
+        let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let path = path.split_once('/').map_or("", |(_, path)| path);
+        Self::from_string(&format!("{} {}", title, path.replace(['/', '-', '_'], " ")))
+    }
+}
+
+/// Sub-type URL segments of commercial listings, e.g. `/immobilien/gewerbe/buero/`
+const COMMERCIAL_URL_SEGMENTS: [&str; 5] = ["gewerbe", "buero", "laden", "gastro", "industrie"];
+
+/// Austrian federal states, used to recognise the region part of a location string
+const AUSTRIAN_STATES: &[&str] = &[
+    "Vorarlberg",
+    "Tirol",
+    "Salzburg",
+    "Kärnten",
+    "Steiermark",
+    "Oberösterreich",
+    "Niederösterreich",
+    "Wien",
+    "Burgenland",
+];
+
+/// Structured location of a property, e.g. "Rankweil, Bezirk Feldkirch, Vorarlberg"
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Location {
+    pub municipality: String,
+    pub district: Option<String>,
+    pub region: Option<String>,
+}
+
+impl Location {
+    /// Location with only a municipality
+    pub fn new(municipality: &str) -> Self {
+        Location {
+            municipality: municipality.to_string(),
+            district: None,
+            region: None,
+        }
+    }
+
+    /// Sentinel used when a scraper could not determine the location
+    pub fn unknown() -> Self {
+        Location::new("Unknown")
+    }
+
+    /// Parse a free-form location string
+    ///
+    /// Comma-separated parts are assigned in order municipality, district; parts
+    /// prefixed with "Bezirk" are always the district and Austrian state names
+    /// are the region. Examples: "Feldkirch", "Bezirk Feldkirch",
+    /// "Rankweil, Bezirk Feldkirch, Vorarlberg".
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        let mut location = Location::default();
+
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            if part.get(..7).is_some_and(|prefix| prefix.eq_ignore_ascii_case("bezirk ")) {
+                location.district = Some(part[7..].trim().to_string());
+            } else if AUSTRIAN_STATES.iter().any(|state| state.to_lowercase() == part.to_lowercase()) {
+                location.region = Some(part.to_string());
+            } else if location.municipality.is_empty() {
+                location.municipality = part.to_string();
+            } else if location.district.is_none() {
+                location.district = Some(part.to_string());
+            }
         }
 
-        PropertyType::Unknown
+        location
+    }
+
+    /// True if neither a municipality nor a district is known
+    pub fn is_unknown(&self) -> bool {
+        (self.municipality.is_empty() || self.municipality == "Unknown") && self.district.is_none()
     }
 }
 
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.municipality.is_empty() {
+            parts.push(self.municipality.clone());
+        }
+        if let Some(district) = &self.district {
+            parts.push(format!("Bezirk {}", district));
+        }
+        if let Some(region) = &self.region {
+            parts.push(region.clone());
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Price as scraped, together with its parsed value in euros
+///
+/// Only the raw string is stored in the CSV; the euro amount is parsed on
+/// construction via `Price::from_str` or lazily on first access after loading.
 #[derive(Debug, Clone)]
-pub struct Property {
-    pub url: String,
-    pub name: String,
-    pub price: String,
-    pub location: String,
-    pub property_type: PropertyType,
-    pub listing_type: ListingType,
-    pub date: Option<NaiveDate>,
-    pub coordinates: Option<(f64, f64)>,
-    pub address: Option<String>,
-    pub size_living: Option<String>,
-    pub size_ground: Option<String>,
-    pub first_seen: Option<NaiveDate>,
-    pub last_seen: Option<NaiveDate>,
+pub struct Price {
+    pub raw: String,
+    euros: OnceLock<Option<f64>>,
 }
 
-// Custom serialization for Property to handle the coordinates tuple
-impl Serialize for Property {
+impl Price {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        let price = Self::lazy(s);
+        let _ = price.euros.set(parse_euros(s));
+        price
+    }
+
+    /// Price whose euro amount is only parsed when first needed
+    fn lazy(s: &str) -> Self {
+        Price {
+            raw: s.to_string(),
+            euros: OnceLock::new(),
+        }
+    }
+
+    /// Sentinel used when a scraper could not determine the price
+    pub fn unknown() -> Self {
+        Price::from_str("Unknown")
+    }
+
+    /// The price in euros, None if the raw string is not a number (e.g. "Unknown")
+    pub fn euros(&self) -> Option<f64> {
+        *self.euros.get_or_init(|| parse_euros(&self.raw))
+    }
+
+    /// True for the placeholders scrapers use when no price was found
+    pub fn is_unknown(&self) -> bool {
+        self.raw.is_empty() || self.raw == "Unknown" || self.raw == "Unavailable"
+    }
+}
+
+/// How amounts of money are written for display
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    pub thousands_sep: char,
+    pub decimal_sep: char,
+    pub currency_symbol: String,
+    /// "€ 450.000" rather than "450.000 €"
+    pub symbol_before: bool,
+}
+
+impl Locale {
+    /// Austrian notation, "€ 450.000,50"
+    pub fn austrian() -> Self {
+        Locale { thousands_sep: '.', decimal_sep: ',', currency_symbol: "€".to_string(), symbol_before: true }
+    }
+
+    /// English notation, "€ 450,000.50"
+    pub fn international() -> Self {
+        Locale { thousands_sep: ',', decimal_sep: '.', currency_symbol: "€".to_string(), symbol_before: true }
+    }
+
+    /// Format an amount in euros, with cents only if it has any
+    pub fn format_euros(&self, amount: f64) -> String {
+        let cents = (amount.abs() * 100.0).round() as u64;
+        let digits = (cents / 100).to_string();
+        let mut number = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                number.push(self.thousands_sep);
+            }
+            number.push(digit);
+        }
+        if cents % 100 != 0 {
+            number.push_str(&format!("{}{:02}", self.decimal_sep, cents % 100));
+        }
+        if amount < 0.0 && cents > 0 {
+            number.insert(0, '-');
+        }
+        if self.symbol_before {
+            format!("{} {}", self.currency_symbol, number)
+        } else {
+            format!("{} {}", number, self.currency_symbol)
+        }
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::austrian()
+    }
+}
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl Serialize for Price {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Property", 13)?;
-        state.serialize_field("url", &self.url)?;
-        state.serialize_field("name", &self.name)?;
-        state.serialize_field("price", &self.price)?;
-        state.serialize_field("location", &self.location)?;
-        state.serialize_field("property_type", &self.property_type)?;
-        state.serialize_field("listing_type", &self.listing_type)?;
-        state.serialize_field("date", &self.date)?;
+        serializer.serialize_str(&self.raw)
+    }
+}
 
-        // Serialize coordinates as a single string field
-        let coords_str = match &self.coordinates {
-            Some((lat, lng)) => format!("{},{}", lat, lng),
-            None => String::new(),
-        };
-        state.serialize_field("coordinates", &coords_str)?;
+impl<'de> Deserialize<'de> for Price {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Price::lazy(&s))
+    }
+}
 
-        state.serialize_field("address", &self.address)?;
-        state.serialize_field("size_living", &self.size_living)?;
-        state.serialize_field("size_ground", &self.size_ground)?;
-        state.serialize_field("first_seen", &self.first_seen)?;
-        state.serialize_field("last_seen", &self.last_seen)?;
+/// An area in square metres, e.g. a living or plot size
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct PropertySize {
+    pub sqm: f64,
+}
 
-        state.end()
+/// Reads sizes like "120.5", "120,5" or "120.5 m²"
+impl FromStr for PropertySize {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let number = s.trim().trim_end_matches("m²").trim_end_matches("m2").trim().replace(',', ".");
+        let sqm: f64 = number.parse().map_err(|_| anyhow::anyhow!("Invalid size: {}", s))?;
+        if !sqm.is_finite() || sqm < 0.0 {
+            return Err(anyhow::anyhow!("Invalid size: {}", s));
+        }
+        Ok(PropertySize { sqm })
     }
 }
 
-// Custom deserialization for Property to handle the coordinates string
-impl<'de> Deserialize<'de> for Property {
+impl fmt::Display for PropertySize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} m²", self.sqm)
+    }
+}
+
+/// Stored as the bare number, the way sizes appear in the CSV
+impl Serialize for PropertySize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.sqm.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for PropertySize {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        #[derive(Deserialize)]
-        struct PropertyHelper {
-            url: String,
-            #[serde(default)]
-            name: String,
-            price: String,
-            location: String,
-            property_type: PropertyType,
-            listing_type: ListingType,
-            date: Option<NaiveDate>,
-            coordinates: String,
-            address: Option<String>,
-            size_living: Option<String>,
-            size_ground: Option<String>,
-            #[serde(default)]
-            first_seen: Option<NaiveDate>,
-            #[serde(default)]
-            last_seen: Option<NaiveDate>,
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parse a price string like "450000", "450.000" or "€ 450.000,50" into euros
+fn parse_euros(raw: &str) -> Option<f64> {
+    let cleaned: String = raw
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+    let cleaned = cleaned.trim_matches(|c| c == '.' || c == ',');
+    if cleaned.is_empty() {
+        return None;
+    }
+
+    let normalized = if cleaned.contains(',') {
+        match cleaned.rsplit_once(',') {
+            // "450,000" uses the comma as thousands separator
+            Some((_, fraction)) if fraction.len() == 3 && !cleaned.contains('.') => cleaned.replace(',', ""),
+            // German notation with decimal comma, e.g. "450.000,50"
+            _ => cleaned.replace('.', "").replace(',', "."),
         }
+    } else if is_thousands_grouped(cleaned) {
+        cleaned.replace('.', "")
+    } else {
+        cleaned.to_string()
+    };
 
-        let helper = PropertyHelper::deserialize(deserializer)?;
+    normalized.parse().ok()
+}
 
-        // Parse coordinates from string
-        let coordinates = if helper.coordinates.is_empty() {
-            None
-        } else {
-            let parts: Vec<&str> = helper.coordinates.split(',').collect();
-            if parts.len() == 2 {
-                match (parts[0].parse::<f64>(), parts[1].parse::<f64>()) {
-                    (Ok(lat), Ok(lng)) => Some((lat, lng)),
-                    _ => None,
-                }
-            } else {
-                None
-            }
-        };
+/// True for numbers grouped with dots like "450.000" or "1.250.000"
+fn is_thousands_grouped(s: &str) -> bool {
+    let mut groups = s.split('.');
+    let first_ok = groups.next().is_some_and(|g| (1..=3).contains(&g.len()));
+    let rest: Vec<&str> = groups.collect();
+    first_ok && !rest.is_empty() && rest.iter().all(|g| g.len() == 3)
+}
 
-        Ok(Property {
-            url: helper.url,
-            name: helper.name,
-            price: helper.price,
-            location: helper.location,
-            property_type: helper.property_type,
-            listing_type: helper.listing_type,
-            date: helper.date,
-            coordinates,
-            address: helper.address,
-            size_living: helper.size_living,
-            size_ground: helper.size_ground,
-            first_seen: helper.first_seen,
-            last_seen: helper.last_seen,
-        })
+/// Latitude/longitude pair, validated to lie within Austria
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+impl Coordinates {
+    /// Create coordinates, failing if they are outside of Austria
+    pub fn new(lat: f64, lng: f64) -> anyhow::Result<Self> {
+        if !crate::utils::is_coordinate_in_austria(lat, lng) {
+            anyhow::bail!("Coordinates ({}, {}) are outside of Austria", lat, lng);
+        }
+        Ok(Coordinates { lat, lng })
+    }
+
+    /// Whether the coordinates lie in Vorarlberg or within about 10 km of it
+    pub fn is_in_vorarlberg(&self) -> bool {
+        crate::utils::is_coordinate_in_vorarlberg(self.lat, self.lng)
+    }
+}
+
+impl fmt::Display for Coordinates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.lat, self.lng)
+    }
+}
+
+/// Parse the "lat,lng" format used in the CSV
+impl FromStr for Coordinates {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let (lat, lng) = s
+            .split_once(',')
+            .ok_or_else(|| anyhow::anyhow!("Invalid coordinates: {}", s))?;
+        Coordinates::new(lat.trim().parse()?, lng.trim().parse()?)
+    }
+}
+
+impl Serialize for Coordinates {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Coordinates {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serde helper writing `Option<Coordinates>` as `{"lat": 47.1, "lng": 9.4}` or `null`
+///
+/// Meant for JSON output, the CSV keeps the "lat,lng" string of `Coordinates`.
+/// Deserializing accepts both forms.
+pub mod coords_as_object {
+    use super::Coordinates;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct LatLng {
+        lat: f64,
+        lng: f64,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AnyCoordinates {
+        Object(LatLng),
+        Text(String),
+    }
+
+    pub fn serialize<S>(coordinates: &Option<Coordinates>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        coordinates
+            .map(|c| LatLng { lat: c.lat, lng: c.lng })
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Coordinates>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<AnyCoordinates>::deserialize(deserializer)? {
+            Some(AnyCoordinates::Object(LatLng { lat, lng })) => {
+                Coordinates::new(lat, lng).map(Some).map_err(serde::de::Error::custom)
+            }
+            Some(AnyCoordinates::Text(s)) if s.trim().is_empty() => Ok(None),
+            Some(AnyCoordinates::Text(s)) => s.parse().map(Some).map_err(serde::de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Property {
+    pub url: String,
+    /// The platform's own ID of the listing, stays the same when the URL changes
+    pub internal_id: Option<String>,
+    /// `utils::page_fingerprint` of the page this was scraped from, to skip unchanged pages on refresh
+    pub page_fingerprint: Option<String>,
+    /// `utils::data_completeness_score` as of the last save
+    pub completeness_score: Option<f64>,
+    pub name: String,
+    pub price: Price,
+    pub location: Location,
+    pub property_type: PropertyType,
+    pub listing_type: ListingType,
+    /// Why the listing is gone, only set for `ListingType::Unavailable`
+    pub unavailable_reason: Option<UnavailableReason>,
+    pub date: Option<NaiveDate>,
+    pub coordinates: Option<Coordinates>,
+    /// Plot boundary as (lat, lng) points of a closed ring, from the laendleimmo.at map
+    pub plot_polygon: Option<Vec<(f64, f64)>>,
+    /// Geocoding quality (0-100) derived from the Nominatim importance, None for scraped coordinates
+    pub quality_score: Option<u8>,
+    /// Listing text, truncated to keep the CSV manageable
+    pub description: Option<String>,
+    /// Normalised English feature names like "garage" or "sauna", pipe-delimited in the CSV
+    pub features: Vec<String>,
+    /// Driving distance by road from the --commute-from reference point
+    pub driving_distance_km: Option<f64>,
+    /// Number of rooms, may be fractional like 3.5
+    pub rooms: Option<f64>,
+    /// Floor as given by the listing, e.g. "2" or "EG"
+    pub floor_level: Option<String>,
+    pub construction_year: Option<u16>,
+    /// Listing agent or agency, as shown on the laendleimmo.at contact card
+    pub agency_name: Option<String>,
+    pub agency_phone: Option<String>,
+    pub address: Option<String>,
+    pub size_living: Option<String>,
+    pub size_ground: Option<String>,
+    pub first_seen: Option<NaiveDate>,
+    pub last_seen: Option<NaiveDate>,
+}
+
+impl Property {
+    /// The price formatted for `locale`, or the raw text if it is not a number (e.g. "Unknown")
+    pub fn display_price(&self, locale: Locale) -> String {
+        match self.price.euros() {
+            Some(euros) => locale.format_euros(euros),
+            None => self.price.raw.clone(),
+        }
+    }
+
+    /// Format the property as a Markdown card for notes or chat messages
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("## {} in {}\n\n", capitalize(&self.property_type.to_string()), self.location);
+        if !self.name.is_empty() {
+            md.push_str(&format!("**{}**\n\n", self.name));
+        }
+
+        md.push_str(&format!("- **Price:** {}\n", self.display_price(Locale::austrian())));
+        md.push_str(&format!("- **Status:** {}\n", self.listing_type));
+        if let Some(date) = self.date {
+            md.push_str(&format!("- **Date:** {}\n", date));
+        }
+        if let Some(address) = &self.address {
+            md.push_str(&format!("- **Address:** {}\n", address));
+        }
+        if let Some(size) = &self.size_living {
+            md.push_str(&format!("- **Living area:** {} m²\n", size));
+        }
+        if let Some(size) = &self.size_ground {
+            md.push_str(&format!("- **Plot area:** {} m²\n", size));
+        }
+        if !self.features.is_empty() {
+            md.push_str(&format!("- **Features:** {}\n", self.features.join(", ")));
+        }
+        if let Some(rooms) = self.rooms {
+            md.push_str(&format!("- **Rooms:** {}\n", rooms));
+        }
+        if let Some(floor) = &self.floor_level {
+            md.push_str(&format!("- **Floor:** {}\n", floor));
+        }
+        if let Some(year) = self.construction_year {
+            md.push_str(&format!("- **Built:** {}\n", year));
+        }
+        if let Some(km) = self.driving_distance_km {
+            md.push_str(&format!("- **Commute:** {} km\n", km));
+        }
+
+        md.push_str(&format!("\n[View listing]({})", self.url));
+        if let Some(coordinates) = self.coordinates {
+            md.push_str(&format!(
+                " · [Google Maps](https://www.google.com/maps/search/?api=1&query={})",
+                coordinates
+            ));
+        }
+        md.push('\n');
+
+        if let Some(description) = &self.description {
+            md.push_str(&format!("\n> {}\n", description.replace('\n', "\n> ")));
+        }
+        md
+    }
+}
+
+impl Property {
+    /// JSON representation with coordinates as an object
+    ///
+    /// Uses the CSV column names otherwise, see [`coords_as_object`].
+    pub fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(record) = value.as_object_mut() {
+            record.insert(
+                "coordinates".to_string(),
+                coords_as_object::serialize(&self.coordinates, serde_json::value::Serializer)?,
+            );
+        }
+        Ok(value)
+    }
+
+    /// The living size, if it was scraped and is a valid number
+    pub fn parsed_living_size(&self) -> Option<PropertySize> {
+        self.size_living.as_deref().and_then(|size| size.parse().ok())
+    }
+
+    /// The plot size, if it was scraped and is a valid number
+    pub fn parsed_ground_size(&self) -> Option<PropertySize> {
+        self.size_ground.as_deref().and_then(|size| size.parse().ok())
+    }
+
+    /// Years since the building was constructed, 0 if it is still being built
+    pub fn age_years(&self) -> Option<u32> {
+        let current_year = chrono::Utc::now().year();
+        self.construction_year.map(|year| (current_year - i32::from(year)).max(0) as u32)
+    }
+
+    /// Whether the property is currently listed for sale
+    pub fn is_available(&self) -> bool {
+        self.listing_type == ListingType::Available
+    }
+
+    /// Whether the property was reported as sold
+    pub fn is_sold(&self) -> bool {
+        self.listing_type == ListingType::Sold
+    }
+
+    /// Whether the listing is gone, see `unavailable_reason` for why
+    pub fn is_unavailable(&self) -> bool {
+        self.listing_type == ListingType::Unavailable
+    }
+
+    /// Whether the property was first listed within the last `max_listing_age_days` days
+    ///
+    /// Uses the listing `date`, falling back to `first_seen` when the page had
+    /// no date. Properties with neither are never fresh.
+    pub fn is_fresh(&self, max_listing_age_days: u32) -> bool {
+        let today = chrono::Utc::now().naive_utc().date();
+        self.date
+            .or(self.first_seen)
+            .is_some_and(|listed| today - listed <= chrono::Duration::days(max_listing_age_days as i64))
+    }
+
+    /// Merge a newer scrape of the same property into this one
+    ///
+    /// The newer data wins, except where the scraper came back empty-handed:
+    /// unknown names, prices, locations and types, empty features and missing
+    /// optional fields keep the existing value. A property that just became
+    /// unavailable keeps all existing data and only takes the new status.
+    /// An existing `first_seen` is never replaced.
+    pub fn merge_with(&mut self, newer: Property) {
+        let existing = &*self;
+
+        // URLs differ when the classification in a laendleimmo.at URL changes;
+        // take the new one if it is better classified or available again
+        let url = if newer.url != existing.url
+            && !newer.is_available()
+            && (newer.property_type.is_unknown() || !existing.property_type.is_unknown())
+        {
+            existing.url.clone()
+        } else {
+            if newer.url != existing.url {
+                debug_println!("Updating URL from {} to {} (better classification or became available)",
+                             existing.url, newer.url);
+            }
+            newer.url.clone()
+        };
+
+        let merged = if newer.is_unavailable() && !existing.is_unavailable() {
+            // Property became unavailable - preserve all existing data except status and dates
+            debug_println!("Property became unavailable, preserving existing data: {}", url);
+            Property {
+                url,
+                internal_id: existing.internal_id.clone().or(newer.internal_id),
+                page_fingerprint: existing.page_fingerprint.clone().or(newer.page_fingerprint),
+                completeness_score: None,
+                name: if existing.name != "Unknown Property" && existing.name != "Unavailable Property" { existing.name.clone() } else { newer.name },
+                price: existing.price.clone(), // Always preserve existing price when becoming unavailable
+                location: if !existing.location.is_unknown() { existing.location.clone() } else { newer.location },
+                property_type: existing.property_type.more_confident(newer.property_type),
+                listing_type: newer.listing_type, // Update to unavailable
+                unavailable_reason: newer.unavailable_reason,
+                date: existing.date.or(newer.date), // Preserve original listing date
+                coordinates: existing.coordinates.or(newer.coordinates),
+                quality_score: if existing.coordinates.is_some() { existing.quality_score } else { newer.quality_score },
+                plot_polygon: existing.plot_polygon.clone().or(newer.plot_polygon),
+                description: existing.description.clone().or(newer.description),
+                features: if existing.features.is_empty() { newer.features } else { existing.features.clone() },
+                driving_distance_km: existing.driving_distance_km.or(newer.driving_distance_km),
+                rooms: existing.rooms.or(newer.rooms),
+                floor_level: existing.floor_level.clone().or(newer.floor_level),
+                construction_year: existing.construction_year.or(newer.construction_year),
+                agency_name: existing.agency_name.clone().or(newer.agency_name),
+                agency_phone: existing.agency_phone.clone().or(newer.agency_phone),
+                address: existing.address.clone().or(newer.address),
+                size_living: existing.size_living.clone().or(newer.size_living),
+                size_ground: existing.size_ground.clone().or(newer.size_ground),
+                first_seen: existing.first_seen.or(newer.first_seen),
+                // Preserve existing last_seen since property became unavailable
+                last_seen: existing.last_seen.or(newer.last_seen),
+            }
+        } else {
+            // Normal property update - use new data but preserve existing data when scraper fails
+            Property {
+                url,
+                internal_id: newer.internal_id.or(existing.internal_id.clone()),
+                page_fingerprint: newer.page_fingerprint.or(existing.page_fingerprint.clone()),
+                completeness_score: None,
+                name: if newer.name.is_empty() || newer.name == "Unknown Property" || newer.name == "Unavailable Property" { existing.name.clone() } else { newer.name },
+                price: if newer.price.is_unknown() { existing.price.clone() } else { newer.price },
+                location: if newer.location.is_unknown() { existing.location.clone() } else { newer.location },
+                property_type: newer.property_type.more_confident(existing.property_type),
+                unavailable_reason: if newer.listing_type == ListingType::Unavailable { newer.unavailable_reason.or(existing.unavailable_reason) } else { None },
+                listing_type: newer.listing_type, // Always update listing status
+                date: newer.date.or(existing.date),
+                coordinates: newer.coordinates.or(existing.coordinates),
+                quality_score: if newer.coordinates.is_some() { newer.quality_score } else { existing.quality_score },
+                plot_polygon: newer.plot_polygon.or(existing.plot_polygon.clone()),
+                description: newer.description.or(existing.description.clone()),
+                features: if newer.features.is_empty() { existing.features.clone() } else { newer.features },
+                driving_distance_km: newer.driving_distance_km.or(existing.driving_distance_km),
+                rooms: newer.rooms.or(existing.rooms),
+                floor_level: newer.floor_level.or(existing.floor_level.clone()),
+                construction_year: newer.construction_year.or(existing.construction_year),
+                agency_name: newer.agency_name.or(existing.agency_name.clone()),
+                agency_phone: newer.agency_phone.or(existing.agency_phone.clone()),
+                address: newer.address.or(existing.address.clone()),
+                size_living: newer.size_living.or(existing.size_living.clone()),
+                size_ground: newer.size_ground.or(existing.size_ground.clone()),
+                first_seen: existing.first_seen.or(newer.first_seen),
+                // Use the latest last_seen date
+                last_seen: newer.last_seen.or(existing.last_seen),
+            }
+        };
+        *self = merged;
+    }
+}
+
+pub(crate) fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Format a plot boundary of (lat, lng) points as WKT, e.g. `POLYGON((9.64 47.27, 9.65 47.27, ...))`
+///
+/// WKT puts the longitude first, like GeoJSON.
+pub fn polygon_to_wkt(points: &[(f64, f64)]) -> String {
+    let ring: Vec<String> = points.iter().map(|(lat, lng)| format!("{} {}", lng, lat)).collect();
+    format!("POLYGON(({}))", ring.join(", "))
+}
+
+/// Parse the outer ring of a WKT `POLYGON` into (lat, lng) points
+///
+/// Returns None for anything that isn't a polygon with at least three valid points.
+pub fn polygon_from_wkt(wkt: &str) -> Option<Vec<(f64, f64)>> {
+    let wkt = wkt.trim();
+    let body = wkt.get(..7).filter(|prefix| prefix.eq_ignore_ascii_case("POLYGON")).map(|_| wkt[7..].trim())?;
+    let ring = body.strip_prefix("((")?.split(')').next()?;
+    let points = ring
+        .split(',')
+        .map(|point| {
+            let mut numbers = point.split_whitespace().map(|n| n.parse::<f64>().ok());
+            match (numbers.next()??, numbers.next()??, numbers.next()) {
+                (lng, lat, None) if Coordinates::new(lat, lng).is_ok() => Some((lat, lng)),
+                _ => None,
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    (points.len() >= 3).then_some(points)
+}
+
+// Custom serialization for Property to flatten the location and coordinates
+impl Serialize for Property {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Property", 30)?;
+        state.serialize_field("url", &self.url)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("price", &self.price)?;
+        state.serialize_field("location_municipality", &self.location.municipality)?;
+        state.serialize_field("location_district", &self.location.district)?;
+        state.serialize_field("location_region", &self.location.region)?;
+        state.serialize_field("property_type", &self.property_type)?;
+        state.serialize_field("listing_type", &self.listing_type)?;
+        state.serialize_field("date", &self.date)?;
+
+        // Serialize coordinates as a single string field
+        let coords_str = self
+            .coordinates
+            .map(|coords| coords.to_string())
+            .unwrap_or_default();
+        state.serialize_field("coordinates", &coords_str)?;
+
+        state.serialize_field("address", &self.address)?;
+        state.serialize_field("size_living", &self.size_living)?;
+        state.serialize_field("size_ground", &self.size_ground)?;
+        state.serialize_field("first_seen", &self.first_seen)?;
+        state.serialize_field("last_seen", &self.last_seen)?;
+        state.serialize_field("quality_score", &self.quality_score)?;
+        state.serialize_field("description", &self.description)?;
+        state.serialize_field("features", &self.features.join("|"))?;
+        state.serialize_field("driving_distance_km", &self.driving_distance_km)?;
+        state.serialize_field("rooms", &self.rooms)?;
+        state.serialize_field("floor_level", &self.floor_level)?;
+        state.serialize_field("construction_year", &self.construction_year)?;
+        state.serialize_field("agency_name", &self.agency_name)?;
+        state.serialize_field("agency_phone", &self.agency_phone)?;
+        state.serialize_field("unavailable_reason", &self.unavailable_reason)?;
+        state.serialize_field("internal_id", &self.internal_id)?;
+        state.serialize_field("page_fingerprint", &self.page_fingerprint)?;
+        state.serialize_field("plot_polygon", &self.plot_polygon.as_deref().map(polygon_to_wkt))?;
+        // Always up to date in saved files, whatever was loaded
+        state.serialize_field("completeness_score", &crate::utils::data_completeness_score(self))?;
+        // Rounded so 0.7 is not written as 0.699999988079071
+        let confidence = (f64::from(self.property_type.confidence) * 100.0).round() / 100.0;
+        state.serialize_field("property_type_confidence", &confidence)?;
+
+        state.end()
+    }
+}
+
+// Custom deserialization for Property to handle the coordinates string
+impl<'de> Deserialize<'de> for Property {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct PropertyHelper {
+            url: String,
+            #[serde(default)]
+            name: String,
+            price: Price,
+            // Legacy single-string location column, superseded by location_* columns
+            #[serde(default)]
+            location: Option<String>,
+            #[serde(default)]
+            location_municipality: Option<String>,
+            #[serde(default)]
+            location_district: Option<String>,
+            #[serde(default)]
+            location_region: Option<String>,
+            property_type: PropertyType,
+            listing_type: ListingType,
+            date: Option<NaiveDate>,
+            coordinates: String,
+            address: Option<String>,
+            size_living: Option<String>,
+            size_ground: Option<String>,
+            #[serde(default)]
+            first_seen: Option<NaiveDate>,
+            #[serde(default)]
+            last_seen: Option<NaiveDate>,
+            #[serde(default)]
+            quality_score: Option<u8>,
+            #[serde(default)]
+            description: Option<String>,
+            #[serde(default)]
+            features: Option<String>,
+            #[serde(default)]
+            driving_distance_km: Option<f64>,
+            #[serde(default)]
+            rooms: Option<f64>,
+            #[serde(default)]
+            floor_level: Option<String>,
+            #[serde(default)]
+            construction_year: Option<u16>,
+            #[serde(default)]
+            agency_name: Option<String>,
+            #[serde(default)]
+            agency_phone: Option<String>,
+            #[serde(default)]
+            unavailable_reason: Option<UnavailableReason>,
+            #[serde(default)]
+            internal_id: Option<String>,
+            #[serde(default)]
+            page_fingerprint: Option<String>,
+            #[serde(default)]
+            completeness_score: Option<f64>,
+            #[serde(default)]
+            plot_polygon: Option<String>,
+            #[serde(default)]
+            property_type_confidence: Option<f32>,
+        }
+
+        let helper = PropertyHelper::deserialize(deserializer)?;
+
+        // Prefer the structured columns, fall back to parsing the legacy location string
+        let location = if helper.location_municipality.is_some()
+            || helper.location_district.is_some()
+            || helper.location_region.is_some()
+        {
+            Location {
+                municipality: helper.location_municipality.unwrap_or_default(),
+                district: helper.location_district,
+                region: helper.location_region,
+            }
+        } else {
+            Location::from_str(helper.location.as_deref().unwrap_or_default())
+        };
+
+        // Parse coordinates from string, dropping malformed or out-of-range values
+        let coordinates = helper.coordinates.parse::<Coordinates>().ok();
+        let plot_polygon = helper.plot_polygon.as_deref().and_then(polygon_from_wkt);
+
+        let mut property_type = helper.property_type;
+        match helper.property_type_confidence {
+            Some(confidence) => property_type.confidence = confidence,
+            None if property_type.confidence > 0.0 => property_type.confidence = LEGACY_TYPE_CONFIDENCE,
+            None => {}
+        }
+
+        Ok(Property {
+            url: helper.url,
+            internal_id: helper.internal_id,
+            page_fingerprint: helper.page_fingerprint,
+            completeness_score: helper.completeness_score,
+            name: helper.name,
+            price: helper.price,
+            location,
+            property_type,
+            listing_type: helper.listing_type,
+            unavailable_reason: helper.unavailable_reason,
+            date: helper.date,
+            coordinates,
+            quality_score: helper.quality_score,
+            plot_polygon,
+            description: helper.description,
+            features: helper
+                .features
+                .unwrap_or_default()
+                .split('|')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(str::to_string)
+                .collect(),
+            driving_distance_km: helper.driving_distance_km,
+            rooms: helper.rooms,
+            floor_level: helper.floor_level,
+            construction_year: helper.construction_year,
+            agency_name: helper.agency_name,
+            agency_phone: helper.agency_phone,
+            address: helper.address,
+            size_living: helper.size_living,
+            size_ground: helper.size_ground,
+            first_seen: helper.first_seen,
+            last_seen: helper.last_seen,
+        })
+    }
+}
+
+/// Programmatic filter over properties, constructed with a builder-style API
+///
+/// All criteria are optional; an empty filter matches every property.
+///
+/// ```
+/// use laendlefinder::models::{PropertyFilter, PropertyKind};
+///
+/// let filter = PropertyFilter::new()
+///     .max_price(500_000.0)
+///     .property_types(vec![PropertyKind::House, PropertyKind::Land])
+///     .with_coordinates_only(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PropertyFilter {
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub property_types: Vec<PropertyKind>,
+    pub listing_types: Vec<ListingType>,
+    pub locations: Vec<String>,
+    pub municipalities: Vec<String>,
+    pub districts: Vec<String>,
+    pub min_size_living: Option<f64>,
+    pub max_size_living: Option<f64>,
+    pub min_size_ground: Option<f64>,
+    pub max_size_ground: Option<f64>,
+    pub with_coordinates_only: bool,
+    pub since: Option<NaiveDate>,
+    pub description_contains: Option<String>,
+    pub features: Vec<String>,
+    pub max_driving_distance_km: Option<f64>,
+    pub min_age_years: Option<u32>,
+    pub max_age_years: Option<u32>,
+}
+
+impl PropertyFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn min_price(mut self, min_price: f64) -> Self {
+        self.min_price = Some(min_price);
+        self
+    }
+
+    pub fn max_price(mut self, max_price: f64) -> Self {
+        self.max_price = Some(max_price);
+        self
+    }
+
+    pub fn property_types(mut self, property_types: Vec<PropertyKind>) -> Self {
+        self.property_types = property_types;
+        self
+    }
+
+    pub fn listing_types(mut self, listing_types: Vec<ListingType>) -> Self {
+        self.listing_types = listing_types;
+        self
+    }
+
+    pub fn locations(mut self, locations: Vec<String>) -> Self {
+        self.locations = locations;
+        self
+    }
+
+    /// Only match properties in one of the given municipalities (case-insensitive)
+    pub fn municipalities(mut self, municipalities: Vec<String>) -> Self {
+        self.municipalities = municipalities;
+        self
+    }
+
+    /// Only match properties in one of the given districts (case-insensitive)
+    pub fn districts(mut self, districts: Vec<String>) -> Self {
+        self.districts = districts;
+        self
+    }
+
+    pub fn min_size_living(mut self, min_size_living: f64) -> Self {
+        self.min_size_living = Some(min_size_living);
+        self
+    }
+
+    pub fn max_size_living(mut self, max_size_living: f64) -> Self {
+        self.max_size_living = Some(max_size_living);
+        self
+    }
+
+    pub fn min_size_ground(mut self, min_size_ground: f64) -> Self {
+        self.min_size_ground = Some(min_size_ground);
+        self
+    }
+
+    pub fn max_size_ground(mut self, max_size_ground: f64) -> Self {
+        self.max_size_ground = Some(max_size_ground);
+        self
+    }
+
+    pub fn with_coordinates_only(mut self, with_coordinates_only: bool) -> Self {
+        self.with_coordinates_only = with_coordinates_only;
+        self
+    }
+
+    /// Only match properties whose description contains the term (case-insensitive)
+    pub fn description_contains(mut self, term: &str) -> Self {
+        self.description_contains = Some(term.to_string());
+        self
+    }
+
+    /// Only match properties having all of the given features (case-insensitive)
+    pub fn features(mut self, features: Vec<String>) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Only match properties within the given driving distance of the commute reference point
+    pub fn max_driving_distance_km(mut self, max_km: f64) -> Self {
+        self.max_driving_distance_km = Some(max_km);
+        self
+    }
+
+    pub fn min_age_years(mut self, min_age_years: u32) -> Self {
+        self.min_age_years = Some(min_age_years);
+        self
+    }
+
+    pub fn max_age_years(mut self, max_age_years: u32) -> Self {
+        self.max_age_years = Some(max_age_years);
+        self
+    }
+
+    /// Only match properties first seen on or after the given date
+    pub fn since(mut self, since: NaiveDate) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Check whether a property satisfies all criteria of this filter
+    ///
+    /// Numeric bounds only match properties where the value could be parsed,
+    /// e.g. a price of "Unknown" never satisfies a `max_price` bound.
+    pub fn matches(&self, property: &Property) -> bool {
+        if !Self::in_range(
+            property.price.euros(),
+            self.min_price,
+            self.max_price,
+        ) {
+            return false;
+        }
+
+        if !self.property_types.is_empty() && !self.property_types.contains(&property.property_type.kind)
+        {
+            return false;
+        }
+
+        if !self.listing_types.is_empty() && !self.listing_types.contains(&property.listing_type) {
+            return false;
+        }
+
+        if !self.municipalities.is_empty()
+            && !self
+                .municipalities
+                .iter()
+                .any(|m| m.eq_ignore_ascii_case(&property.location.municipality))
+        {
+            return false;
+        }
+
+        if !self.districts.is_empty() {
+            let district = property.location.district.as_deref().unwrap_or_default();
+            if !self.districts.iter().any(|d| d.eq_ignore_ascii_case(district)) {
+                return false;
+            }
+        }
+
+        if !self.locations.is_empty() {
+            let location = property.location.to_string().to_lowercase();
+            if !self
+                .locations
+                .iter()
+                .any(|l| location.contains(&l.to_lowercase()))
+            {
+                return false;
+            }
+        }
+
+        if !Self::in_range(
+            property.parsed_living_size().map(|size| size.sqm),
+            self.min_size_living,
+            self.max_size_living,
+        ) {
+            return false;
+        }
+
+        if !Self::in_range(
+            property.parsed_ground_size().map(|size| size.sqm),
+            self.min_size_ground,
+            self.max_size_ground,
+        ) {
+            return false;
+        }
+
+        if self.with_coordinates_only && property.coordinates.is_none() {
+            return false;
+        }
+
+        if !Self::in_range(property.driving_distance_km, None, self.max_driving_distance_km) {
+            return false;
+        }
+
+        if !Self::in_range(
+            property.age_years().map(f64::from),
+            self.min_age_years.map(f64::from),
+            self.max_age_years.map(f64::from),
+        ) {
+            return false;
+        }
+
+        if let Some(since) = self.since {
+            match property.first_seen {
+                Some(first_seen) if first_seen >= since => {}
+                _ => return false,
+            }
+        }
+
+        if !self.features.iter().all(|wanted| {
+            property
+                .features
+                .iter()
+                .any(|feature| feature.eq_ignore_ascii_case(wanted))
+        }) {
+            return false;
+        }
+
+        if let Some(term) = &self.description_contains {
+            let term = term.to_lowercase();
+            if !property
+                .description
+                .as_ref()
+                .is_some_and(|description| description.to_lowercase().contains(&term))
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn in_range(value: Option<f64>, min: Option<f64>, max: Option<f64>) -> bool {
+        if min.is_none() && max.is_none() {
+            return true;
+        }
+        match value {
+            Some(v) => min.map_or(true, |min| v >= min) && max.map_or(true, |max| v <= max),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn property_with(price: &str, size_living: Option<String>) -> Property {
+        Property {
+            url: "https://www.vol.at/test".to_string(),
+            internal_id: None,
+            page_fingerprint: None,
+            completeness_score: None,
+            name: "Test".to_string(),
+            price: Price::from_str(price),
+            location: Location::from_str("Rankweil, Bezirk Feldkirch"),
+            property_type: PropertyKind::House.into(),
+            listing_type: ListingType::Sold,
+            unavailable_reason: None,
+            date: None,
+            coordinates: None,
+            quality_score: None,
+            plot_polygon: None,
+            description: None,
+            features: Vec::new(),
+            driving_distance_km: None,
+            rooms: None,
+            floor_level: None,
+            construction_year: None,
+            agency_name: None,
+            agency_phone: None,
+            address: None,
+            size_living,
+            size_ground: None,
+            first_seen: NaiveDate::from_ymd_opt(2025, 1, 1),
+            last_seen: None,
+        }
+    }
+
+    /// A fully populated property as it would come from a successful scrape
+    fn scraped(name: &str, price: &str, listing_type: ListingType) -> Property {
+        Property {
+            url: "https://www.laendleimmo.at/immobilien/haus/einfamilienhaus/vorarlberg/feldkirch/1".to_string(),
+            internal_id: None,
+            page_fingerprint: None,
+            completeness_score: None,
+            name: name.to_string(),
+            price: Price::from_str(price),
+            location: Location::new(name),
+            property_type: PropertyKind::House.into(),
+            listing_type,
+            unavailable_reason: None,
+            date: NaiveDate::from_ymd_opt(2025, 1, 1),
+            coordinates: Some(Coordinates::new(47.24, 9.6).unwrap()),
+            quality_score: Some(5),
+            plot_polygon: None,
+            description: Some(name.to_string()),
+            features: vec![name.to_string()],
+            driving_distance_km: Some(1.0),
+            rooms: Some(1.0),
+            floor_level: Some(name.to_string()),
+            construction_year: Some(2000),
+            agency_name: Some(name.to_string()),
+            agency_phone: Some(name.to_string()),
+            address: Some(name.to_string()),
+            size_living: Some("100".to_string()),
+            size_ground: Some("500".to_string()),
+            first_seen: NaiveDate::from_ymd_opt(2025, 1, 1),
+            last_seen: NaiveDate::from_ymd_opt(2025, 1, 1),
+        }
+    }
+
+    #[test]
+    fn test_merge_with_listing_type_combinations() {
+        use ListingType::*;
+        for old_type in [Available, Sold, Unavailable] {
+            for new_type in [Available, Sold, Unavailable] {
+                let mut merged = scraped("Old", "100000", old_type.clone());
+                let mut newer = scraped("New", "200000", new_type.clone());
+                newer.first_seen = NaiveDate::from_ymd_opt(2025, 2, 1);
+                newer.last_seen = NaiveDate::from_ymd_opt(2025, 2, 1);
+                merged.merge_with(newer);
+
+                let case = format!("{} -> {}", old_type, new_type);
+                assert_eq!(merged.listing_type, new_type, "{}", case);
+                assert_eq!(merged.first_seen, NaiveDate::from_ymd_opt(2025, 1, 1), "{}", case);
+                // Only a property that just became unavailable keeps the existing data
+                let keeps_existing = new_type == Unavailable && old_type != Unavailable;
+                let (name, price, last_seen) = if keeps_existing {
+                    ("Old", "100000", NaiveDate::from_ymd_opt(2025, 1, 1))
+                } else {
+                    ("New", "200000", NaiveDate::from_ymd_opt(2025, 2, 1))
+                };
+                assert_eq!(merged.name, name, "{}", case);
+                assert_eq!(merged.price, Price::from_str(price), "{}", case);
+                assert_eq!(merged.location, Location::new(name), "{}", case);
+                assert_eq!(merged.description.as_deref(), Some(name), "{}", case);
+                assert_eq!(merged.features, vec![name.to_string()], "{}", case);
+                assert_eq!(merged.agency_name.as_deref(), Some(name), "{}", case);
+                assert_eq!(merged.last_seen, last_seen, "{}", case);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let today = chrono::Utc::now().naive_utc().date();
+        let mut property = property_with("100000", None);
+        property.date = None;
+        property.first_seen = None;
+        assert!(!property.is_fresh(7));
+
+        property.first_seen = Some(today - chrono::Duration::days(3));
+        assert!(property.is_fresh(7));
+        // The listing date wins over the scrape date
+        property.date = Some(today - chrono::Duration::days(30));
+        assert!(!property.is_fresh(7));
+        assert!(property.is_fresh(30));
+    }
+
+    #[test]
+    fn test_merge_with_keeps_existing_data_for_empty_fields() {
+        for empty_name in ["", "Unknown Property", "Unavailable Property"] {
+            let mut merged = scraped("Old", "100000", ListingType::Available);
+            merged.merge_with(Property {
+                url: merged.url.clone(),
+                internal_id: None,
+                page_fingerprint: None,
+                completeness_score: None,
+                name: empty_name.to_string(),
+                price: Price::unknown(),
+                location: Location::unknown(),
+                property_type: PropertyType::unknown(),
+                listing_type: ListingType::Sold,
+                unavailable_reason: None,
+                date: None,
+                coordinates: None,
+                quality_score: None,
+                plot_polygon: None,
+                description: None,
+                features: Vec::new(),
+                driving_distance_km: None,
+                rooms: None,
+                floor_level: None,
+                construction_year: None,
+                agency_name: None,
+                agency_phone: None,
+                address: None,
+                size_living: None,
+                size_ground: None,
+                first_seen: None,
+                last_seen: None,
+            });
+
+            let mut expected = scraped("Old", "100000", ListingType::Sold);
+            expected.url = merged.url.clone();
+            assert_eq!(format!("{:?}", merged), format!("{:?}", expected), "name {:?}", empty_name);
+        }
+    }
+
+    #[test]
+    fn test_merge_with_url_and_property_type() {
+        let old_url = "https://www.laendleimmo.at/immobilien/x/y/vorarlberg/feldkirch/1";
+        let new_url = "https://www.laendleimmo.at/immobilien/haus/einfamilienhaus/vorarlberg/feldkirch/1";
+
+        // A better classification replaces the URL and type
+        let mut merged = scraped("Old", "100000", ListingType::Sold);
+        merged.url = old_url.to_string();
+        merged.property_type = PropertyType::unknown();
+        merged.merge_with(scraped("New", "200000", ListingType::Sold));
+        assert_eq!(merged.url, new_url);
+        assert_eq!(merged.property_type, PropertyKind::House.into());
+
+        // An unclassified rescrape of a sold property keeps the URL and type
+        let mut merged = scraped("Old", "100000", ListingType::Sold);
+        merged.url = old_url.to_string();
+        let mut unclassified = scraped("New", "200000", ListingType::Sold);
+        unclassified.property_type = PropertyType::unknown();
+        merged.merge_with(unclassified);
+        assert_eq!(merged.url, old_url);
+        assert_eq!(merged.property_type, PropertyKind::House.into());
+
+        // Becoming available again always takes the new URL
+        let mut merged = scraped("Old", "100000", ListingType::Sold);
+        merged.url = old_url.to_string();
+        let mut available = scraped("New", "200000", ListingType::Available);
+        available.property_type = PropertyType::new(PropertyKind::House, 0.7);
+        merged.merge_with(available);
+        assert_eq!(merged.url, new_url);
+        assert_eq!(merged.property_type, PropertyKind::House.into());
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = PropertyFilter::new();
+        assert!(filter.matches(&property_with("Unknown", None)));
+        assert!(filter.matches(&property_with("450000", Some("120".to_string()))));
+    }
+
+    #[test]
+    fn test_unparseable_price_fails_price_bounds() {
+        let filter = PropertyFilter::new().max_price(500_000.0);
+        assert!(!filter.matches(&property_with("Unknown", None)));
+    }
+
+    #[test]
+    fn test_location_and_type_filters() {
+        let property = property_with("450000", None);
+        assert!(PropertyFilter::new()
+            .locations(vec!["feldkirch".to_string()])
+            .matches(&property));
+        assert!(!PropertyFilter::new()
+            .locations(vec!["Bregenz".to_string()])
+            .matches(&property));
+        assert!(PropertyFilter::new()
+            .municipalities(vec!["rankweil".to_string()])
+            .districts(vec!["Feldkirch".to_string()])
+            .matches(&property));
+        assert!(!PropertyFilter::new()
+            .municipalities(vec!["Feldkirch".to_string()])
+            .matches(&property));
+        assert!(!PropertyFilter::new()
+            .property_types(vec![PropertyKind::Land])
+            .matches(&property));
+        assert!(!PropertyFilter::new()
+            .with_coordinates_only(true)
+            .matches(&property));
+    }
+
+    #[test]
+    fn test_description_filter() {
+        let mut property = property_with("100000", None);
+        let filter = PropertyFilter::new().description_contains("sauna");
+        assert!(!filter.matches(&property));
+
+        property.description = Some("Haus mit Sauna und Terrasse".to_string());
+        assert!(filter.matches(&property));
+        assert!(!PropertyFilter::new().description_contains("Pool").matches(&property));
+    }
+
+    #[test]
+    fn test_max_driving_distance_filter() {
+        let mut property = property_with("100000", None);
+        let filter = PropertyFilter::new().max_driving_distance_km(20.0);
+        assert!(!filter.matches(&property));
+
+        property.driving_distance_km = Some(12.5);
+        assert!(filter.matches(&property));
+        property.driving_distance_km = Some(20.1);
+        assert!(!filter.matches(&property));
+    }
+
+    #[test]
+    fn test_features_csv_and_filter() {
+        let mut property = property_with("100000", None);
+        property.features = vec!["garage".to_string(), "sauna".to_string()];
+
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.serialize(&property).unwrap();
+        let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert!(data.contains(",garage|sauna"));
+
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+        let loaded: Property = reader.deserialize().next().unwrap().unwrap();
+        assert_eq!(loaded.features, property.features);
+
+        assert!(PropertyFilter::new().features(vec!["Sauna".to_string()]).matches(&loaded));
+        assert!(!PropertyFilter::new().features(vec!["sauna".to_string(), "pool".to_string()]).matches(&loaded));
+    }
+
+    #[test]
+    fn test_unavailable_reason_csv_and_merge() {
+        let mut property = property_with("100000", None);
+        property.listing_type = ListingType::Unavailable;
+        property.unavailable_reason = Some(UnavailableReason::NotFound);
+
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.serialize(&property).unwrap();
+        let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert!(data.contains(",not_found,"));
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+        let loaded: Property = reader.deserialize().next().unwrap().unwrap();
+        assert_eq!(loaded.unavailable_reason, Some(UnavailableReason::NotFound));
+
+        let mut merged = property_with("100000", None);
+        merged.merge_with(property.clone());
+        assert_eq!(merged.unavailable_reason, Some(UnavailableReason::NotFound));
+        let mut without_reason = property.clone();
+        without_reason.unavailable_reason = None;
+        merged.merge_with(without_reason);
+        assert_eq!(merged.unavailable_reason, Some(UnavailableReason::NotFound));
+        merged.merge_with(property_with("100000", None));
+        assert_eq!(merged.unavailable_reason, None);
+    }
+
+    #[test]
+    fn test_property_type_from_title_and_url() {
+        let laendleimmo = "https://www.laendleimmo.at/immobilien/wohnung/dachgeschosswohnung/vorarlberg/dornbirn/123";
+        // The URL wins over a misleading title
+        assert_eq!(PropertyType::from_title_and_url("Haus am See", laendleimmo).kind, PropertyKind::Apartment);
+        assert_eq!(PropertyType::from_url(laendleimmo), Some(PropertyKind::Apartment.into()));
+        assert_eq!(PropertyType::from_url("https://www.vol.at/haus-in-dornbirn-verkauft/1234"), None);
+
+        // Without URL segments the title decides
+        let article = "https://www.vol.at/neuer-eintrag/1234";
+        assert_eq!(PropertyType::from_title_and_url("Einfamilienhaus in Dornbirn", article).kind, PropertyKind::House);
+        // A weakly classified URL loses against a more confident title
+        let weak = PropertyType::from_title_and_url("Eigentumswohnung", "https://www.laendleimmo.at/immobilien/sonstige/objekt-grund/x/1");
+        assert_eq!(weak, PropertyType::new(PropertyKind::Apartment, 1.0));
+        // Neither alone classifies, the URL words do
+        assert_eq!(
+            PropertyType::from_title_and_url("Objekt in Bregenz", "https://www.vol.at/bauplatz-bregenz/1234").kind,
+            PropertyKind::Land
+        );
+        assert!(PropertyType::from_title_and_url("Objekt in Bregenz", article).is_unknown());
+    }
+
+    #[test]
+    fn test_property_type_garage() {
+        assert_eq!(PropertyType::from_string("Tiefgaragenstellplatz").kind, PropertyKind::Garage);
+        assert_eq!(PropertyType::from_string("Carport").kind, PropertyKind::Garage);
+        assert_eq!(PropertyType::from_string("Haus mit Garage").kind, PropertyKind::House);
+        assert_eq!(PropertyType::from(PropertyKind::Garage).to_string(), "garage");
+        assert_eq!(PropertyType::from_string("Gewerbeobjekt in Lustenau").kind, PropertyKind::Commercial);
+        assert_eq!(PropertyType::from_string("Bürofläche zu verkaufen").kind, PropertyKind::Commercial);
+        assert_eq!(PropertyType::from(PropertyKind::Commercial).to_string(), "commercial");
+    }
+
+    #[test]
+    fn test_coords_as_object() {
+        let mut property = property_with("450000", None);
+        property.coordinates = Some(Coordinates::new(47.123, 9.756).unwrap());
+        let json = property.to_json().unwrap();
+        assert_eq!(json["coordinates"], serde_json::json!({"lat": 47.123, "lng": 9.756}));
+        assert_eq!(json["property_type_confidence"], serde_json::json!(1.0));
+        property.property_type = PropertyType::new(PropertyKind::House, 0.7);
+        assert_eq!(property.to_json().unwrap()["property_type_confidence"], serde_json::json!(0.7));
+        assert_eq!(json["url"], serde_json::json!(property.url));
+
+        property.coordinates = None;
+        assert_eq!(property.to_json().unwrap()["coordinates"], serde_json::Value::Null);
+
+        #[derive(Deserialize)]
+        struct Record {
+            #[serde(with = "coords_as_object")]
+            coordinates: Option<Coordinates>,
+        }
+        for (input, expected) in [
+            (r#"{"coordinates": {"lat": 47.4, "lng": 9.7}}"#, Some((47.4, 9.7))),
+            (r#"{"coordinates": "47.4,9.7"}"#, Some((47.4, 9.7))),
+            (r#"{"coordinates": ""}"#, None),
+            (r#"{"coordinates": null}"#, None),
+        ] {
+            let record: Record = serde_json::from_str(input).unwrap();
+            assert_eq!(record.coordinates.map(|c| (c.lat, c.lng)), expected, "{}", input);
+        }
+        assert!(serde_json::from_str::<Record>(r#"{"coordinates": {"lat": 999.0, "lng": 9.7}}"#).is_err());
+    }
+
+    #[test]
+    fn test_property_type_deserialize_variants() {
+        let parse = |s: &str| -> PropertyType {
+            serde_json::from_value(serde_json::Value::String(s.to_string())).unwrap()
+        };
+        // Canonical values are exact
+        for (s, kind) in [
+            ("apartment", PropertyKind::Apartment),
+            ("house", PropertyKind::House),
+            ("land", PropertyKind::Land),
+            ("garage", PropertyKind::Garage),
+            ("commercial", PropertyKind::Commercial),
+            ("unknown", PropertyKind::Unknown),
+            ("unavailable", PropertyKind::Unavailable),
+            ("", PropertyKind::Unknown),
+        ] {
+            assert_eq!(parse(s), kind.into(), "{:?}", s);
+        }
+        // Other spellings keep the kind, with the classifier's confidence for German forms
+        for (s, kind) in [
+            ("House", PropertyKind::House),
+            ("HOUSE", PropertyKind::House),
+            (" Land ", PropertyKind::Land),
+            ("Apartment", PropertyKind::Apartment),
+            ("GARAGE", PropertyKind::Garage),
+            ("Unknown", PropertyKind::Unknown),
+            ("Unavailable", PropertyKind::Unavailable),
+            ("haus", PropertyKind::House),
+            ("Haus", PropertyKind::House),
+            ("Einfamilienhaus", PropertyKind::House),
+            ("Wohnung", PropertyKind::Apartment),
+            ("Eigentumswohnung", PropertyKind::Apartment),
+            ("Grundstück", PropertyKind::Land),
+            ("Baugrundstück", PropertyKind::Land),
+            ("Stellplatz", PropertyKind::Garage),
+            ("Schloss", PropertyKind::Unknown),
+        ] {
+            assert_eq!(parse(s).kind, kind, "{:?}", s);
+        }
+        assert_eq!(parse("Haus"), PropertyType::new(PropertyKind::House, 0.7));
+    }
+
+    #[test]
+    fn test_property_type_confidence() {
+        assert_eq!(PropertyType::from_string("Eigentumswohnung").confidence, 1.0);
+        assert_eq!(PropertyType::from_string("Haus in Dornbirn").confidence, 0.7);
+        assert_eq!(PropertyType::from_string("Einfamilienhaus").confidence, 1.0);
+        assert_eq!(PropertyType::from_string("Grundriss").confidence, 0.6);
+        assert_eq!(PropertyType::from_string("Büro").confidence, 0.8);
+        assert_eq!(PropertyType::from_string("Zimmer").confidence, 0.0);
+
+        let exact = PropertyType::from_string("Baugrundstück");
+        let fuzzy = PropertyType::from_string("Hausgrund");
+        assert_eq!(fuzzy.more_confident(exact), exact);
+        assert_eq!(exact.more_confident(PropertyType::unknown()), exact);
+
+        // The confidence is stored next to the kind and survives a CSV round trip
+        let mut property = property_with("450000", None);
+        property.property_type = fuzzy;
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.serialize(&property).unwrap();
+        let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert!(data.contains(",house,"));
+        assert!(data.lines().next().unwrap().ends_with(",property_type_confidence"));
+        let loaded: Property = csv::Reader::from_reader(data.as_bytes()).deserialize().next().unwrap().unwrap();
+        assert_eq!(loaded.property_type, fuzzy);
+
+        // Files without the column load with a low confidence, so a fresh classification wins the merge
+        let legacy = data.replace(",property_type_confidence", "").replacen(",0.7\n", "\n", 1);
+        let mut loaded: Property = csv::Reader::from_reader(legacy.as_bytes()).deserialize().next().unwrap().unwrap();
+        assert_eq!(loaded.property_type, PropertyType::new(PropertyKind::House, 0.5));
+        let mut newer = loaded.clone();
+        newer.property_type = PropertyType::from_string("Grundriss");
+        loaded.merge_with(newer);
+        assert_eq!(loaded.property_type.kind, PropertyKind::Land);
+    }
+
+    #[test]
+    fn test_location_from_str() {
+        assert_eq!(Location::from_str("Feldkirch"), Location::new("Feldkirch"));
+        assert_eq!(
+            Location::from_str("Bezirk Feldkirch"),
+            Location {
+                municipality: String::new(),
+                district: Some("Feldkirch".to_string()),
+                region: None,
+            }
+        );
+        let location = Location::from_str("Rankweil, Bezirk Feldkirch, Vorarlberg");
+        assert_eq!(location.municipality, "Rankweil");
+        assert_eq!(location.district.as_deref(), Some("Feldkirch"));
+        assert_eq!(location.region.as_deref(), Some("Vorarlberg"));
+        assert_eq!(location.to_string(), "Rankweil, Bezirk Feldkirch, Vorarlberg");
+        assert!(Location::from_str("Unknown").is_unknown());
+        assert!(Location::from_str("").is_unknown());
+    }
+
+    #[test]
+    fn test_location_csv_round_trip_and_legacy_column() {
+        let property = property_with("450000", None);
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.serialize(&property).unwrap();
+        let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert!(data.starts_with("url,name,price,location_municipality,location_district,location_region,"));
+
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+        let loaded: Property = reader.deserialize().next().unwrap().unwrap();
+        assert_eq!(loaded.location, property.location);
+
+        let legacy = "url,name,price,location,property_type,listing_type,date,coordinates,address,size_living,size_ground\n\
+                      https://www.vol.at/x,Test,100,Bezirk Bregenz,house,sold,,,,,\n";
+        let mut reader = csv::Reader::from_reader(legacy.as_bytes());
+        let loaded: Property = reader.deserialize().next().unwrap().unwrap();
+        assert_eq!(loaded.location.district.as_deref(), Some("Bregenz"));
+    }
+
+    #[test]
+    fn test_property_size() {
+        for (raw, sqm) in [("120.5", 120.5), (" 85 ", 85.0), ("120,5", 120.5), ("420 m²", 420.0), ("60m2", 60.0)] {
+            assert_eq!(raw.parse::<PropertySize>().unwrap().sqm, sqm, "{:?}", raw);
+        }
+        for raw in ["", "groß", "-10", "NaN"] {
+            assert!(raw.parse::<PropertySize>().is_err(), "{:?}", raw);
+        }
+        assert_eq!(PropertySize { sqm: 120.5 }.to_string(), "120.5 m²");
+        assert_eq!(serde_json::to_value(PropertySize { sqm: 85.0 }).unwrap(), "85");
+        let size: PropertySize = serde_json::from_value("85.5".into()).unwrap();
+        assert_eq!(size.sqm, 85.5);
+    }
+
+    #[test]
+    fn test_coordinates_validation() {
+        let dornbirn = Coordinates::new(47.4125, 9.7417).unwrap();
+        assert!(dornbirn.is_in_vorarlberg());
+
+        let vienna = Coordinates::new(48.2082, 16.3738).unwrap();
+        assert!(!vienna.is_in_vorarlberg());
+
+        assert!(Coordinates::new(9999.0, -9999.0).is_err());
+        assert!(Coordinates::new(52.52, 13.405).is_err()); // Berlin
+    }
+
+    #[test]
+    fn test_coordinates_csv_format() {
+        let coords: Coordinates = "47.4125, 9.7417".parse().unwrap();
+        assert_eq!(coords, Coordinates { lat: 47.4125, lng: 9.7417 });
+        assert_eq!(coords.to_string(), "47.4125,9.7417");
+        assert!("47.4125".parse::<Coordinates>().is_err());
+
+        // Out-of-range coordinates in an existing CSV are dropped instead of failing the row
+        let csv = "url,name,price,location,property_type,listing_type,date,coordinates,address,size_living,size_ground\n\
+                   https://www.vol.at/x,Test,100,Dornbirn,house,sold,,\"9999.0,-9999.0\",,,\n";
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        let loaded: Property = reader.deserialize().next().unwrap().unwrap();
+        assert_eq!(loaded.coordinates, None);
+    }
+
+    #[test]
+    fn test_display_price() {
+        let property = |price: &str| property_with(price, None);
+        assert_eq!(property("450000").display_price(Locale::austrian()), "€ 450.000");
+        assert_eq!(property("€ 1.250.000,5").display_price(Locale::austrian()), "€ 1.250.000,50");
+        assert_eq!(property("450.000").display_price(Locale::international()), "€ 450,000");
+        assert_eq!(property("999").display_price(Locale::international()), "€ 999");
+        assert_eq!(property("Unknown").display_price(Locale::austrian()), "Unknown");
+
+        let suffixed = Locale { symbol_before: false, ..Locale::austrian() };
+        assert_eq!(suffixed.format_euros(12345.0), "12.345 €");
+        assert_eq!(Locale::international().format_euros(1234567.891), "€ 1,234,567.89");
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let mut property = property_with("450000", Some("120".to_string()));
+        property.coordinates = Some(Coordinates::new(47.27, 9.64).unwrap());
+
+        let md = property.to_markdown();
+        assert!(md.starts_with("## House in Rankweil, Bezirk Feldkirch\n"));
+        assert!(md.contains("- **Price:** € 450.000\n"));
+        assert!(md.contains("- **Living area:** 120 m²\n"));
+        assert!(md.contains("[View listing](https://www.vol.at/test)"));
+        assert!(md.contains("query=47.27,9.64)"));
+        assert!(!property_with("1", None).to_markdown().contains("Google Maps"));
+    }
+
+    #[test]
+    fn test_listing_type_legacy_aliases() {
+        for alias in ["unavailable", "gone", "404", "deleted", "Deleted"] {
+            let listing_type: ListingType = serde_json::from_value(alias.into()).unwrap();
+            assert_eq!(listing_type, ListingType::Unavailable, "{}", alias);
+        }
+        let sold: ListingType = serde_json::from_value(" Sold ".into()).unwrap();
+        assert_eq!(sold, ListingType::Sold);
+        assert!(serde_json::from_value::<ListingType>("rented".into()).is_err());
+    }
+
+    #[test]
+    fn test_price_parsing() {
+        assert_eq!(Price::from_str("450000").euros(), Some(450000.0));
+        assert_eq!(Price::from_str("450000.5").euros(), Some(450000.5));
+        assert_eq!(Price::from_str("450.000").euros(), Some(450000.0));
+        assert_eq!(Price::from_str("1.250.000").euros(), Some(1250000.0));
+        assert_eq!(Price::from_str("€ 450.000,50").euros(), Some(450000.5));
+        assert_eq!(Price::from_str("450,000 €").euros(), Some(450000.0));
+        assert_eq!(Price::from_str("Unknown").euros(), None);
+        assert!(Price::unknown().is_unknown());
+        assert_eq!(Price::from_str("€ 450.000").to_string(), "€ 450.000");
+    }
+
+    #[test]
+    fn test_price_is_parsed_lazily_after_loading() {
+        let price: Price = serde_json::from_str("\"450.000\"").unwrap();
+        assert!(price.euros.get().is_none());
+        assert_eq!(price.euros(), Some(450000.0));
+        assert_eq!(serde_json::to_string(&price).unwrap(), "\"450.000\"");
+    }
+
+    proptest! {
+
+        #[test]
+        fn prop_price_bounds(price in 0.0..2_000_000.0f64, min in 0.0..2_000_000.0f64, max in 0.0..2_000_000.0f64) {
+            let property = property_with(&price.to_string(), None);
+            let filter = PropertyFilter::new().min_price(min).max_price(max);
+            prop_assert_eq!(filter.matches(&property), price >= min && price <= max);
+        }
+
+        #[test]
+        fn prop_living_size_bounds(size in 0.0..1000.0f64, min in 0.0..1000.0f64) {
+            let property = property_with("100000", Some(size.to_string()));
+            let filter = PropertyFilter::new().min_size_living(min);
+            prop_assert_eq!(filter.matches(&property), size >= min);
+        }
+
+        #[test]
+        fn prop_since(days in 0i64..1000, offset in -500i64..500) {
+            let base = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+            let mut property = property_with("100000", None);
+            property.first_seen = Some(base + chrono::Duration::days(days));
+            let since = base + chrono::Duration::days(days + offset);
+            let filter = PropertyFilter::new().since(since);
+            prop_assert_eq!(filter.matches(&property), offset <= 0);
+        }
     }
 }
@@ -1,4 +1,5 @@
 use anyhow::{Result, Context};
+use crate::models::Location;
 use regex::Regex;
 
 pub fn extract_price(text: &str) -> Result<f64> {
@@ -15,13 +16,22 @@ pub fn extract_price(text: &str) -> Result<f64> {
     Ok(price)
 }
 
-pub fn extract_location(text: &str) -> Result<String> {
-    let re = Regex::new(r"in\s+([A-Za-zÄÖÜäöüß-]+)").unwrap();
+/// Extract the place name following "in" from a headline
+///
+/// Captures one or more capitalised words, optionally prefixed by "St." or
+/// "Sankt" and joined by lowercase connectors like "am", "im" or "bei", e.g.
+/// "Hard am Bodensee" or "St. Anton im Montafon". The name ends at any other
+/// lowercase word or punctuation like a comma or colon.
+pub fn extract_location(text: &str) -> Result<Location> {
+    let re = Regex::new(
+        r"\bin\s+((?:(?:St\.|Sankt)\s*)?\p{Lu}[\p{L}-]*(?:\s+(?:(?:am|im|bei|ob|an\s+der|unter)\s+)?\p{Lu}[\p{L}-]*)*)",
+    )
+    .unwrap();
     let captures = re.captures(text)
         .context("Location not found in text")?;
     
-    let location = captures.get(1).unwrap().as_str().to_string();
-    Ok(location)
+    let location = captures.get(1).unwrap().as_str();
+    Ok(Location::from_str(location))
 }
 
 pub fn extract_property_type(text: &str) -> Result<String> {
@@ -32,3 +42,73 @@ pub fn extract_property_type(text: &str) -> Result<String> {
     let property_type = captures.get(1).unwrap().as_str().to_string();
     Ok(property_type)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn municipality(text: &str) -> String {
+        extract_location(text).unwrap().municipality
+    }
+
+    #[test]
+    fn test_extract_location_single_word() {
+        assert_eq!(municipality("Haus in Lustenau um 450.000 Euro verkauft"), "Lustenau");
+        assert_eq!(municipality("Wohnung in Dornbirn: 320.000 Euro"), "Dornbirn");
+        assert_eq!(municipality("Grundstück in Höchst, 800 m²"), "Höchst");
+        assert_eq!(municipality("Wohnung in Götzis."), "Götzis");
+        assert_eq!(municipality("Baugrund in Übersaxen um 120.000 Euro"), "Übersaxen");
+        assert_eq!(municipality("Wohnung in Dornbirn-Hatlerdorf verkauft"), "Dornbirn-Hatlerdorf");
+    }
+
+    #[test]
+    fn test_extract_location_skips_lowercase_phrases() {
+        assert_eq!(municipality("Haus in bester Lage in Feldkirch um 1 Euro"), "Feldkirch");
+        assert!(extract_location("Haus in bester Lage").is_err());
+    }
+
+    #[test]
+    fn test_extract_location_multi_word_municipalities() {
+        let cases = [
+            ("Haus in Hard am Bodensee um 650.000 Euro verkauft", "Hard am Bodensee"),
+            ("Wohnung in Lochau am Bodensee um 400.000 Euro", "Lochau am Bodensee"),
+            ("Grundstück in Fußach am Bodensee, 600 m²", "Fußach am Bodensee"),
+            ("Haus in Gaißau am Rhein um 500.000 Euro", "Gaißau am Rhein"),
+            ("Wohnung in Höchst am Rhein um 300.000 Euro", "Höchst am Rhein"),
+            ("Haus in Schwarzach im Bregenzerwald um 700.000 Euro", "Schwarzach im Bregenzerwald"),
+            ("Haus in Egg im Bregenzerwald um 550.000 Euro", "Egg im Bregenzerwald"),
+            ("Wohnung in Bezau im Bregenzerwald um 380.000 Euro", "Bezau im Bregenzerwald"),
+            ("Haus in Au im Bregenzerwald um 480.000 Euro", "Au im Bregenzerwald"),
+            ("Wohnung in Wolfurt bei Bregenz um 350.000 Euro", "Wolfurt bei Bregenz"),
+            ("Haus in Langen bei Bregenz um 620.000 Euro", "Langen bei Bregenz"),
+            ("Wohnung in Göfis bei Feldkirch um 330.000 Euro", "Göfis bei Feldkirch"),
+            ("Haus in Nüziders bei Bludenz um 510.000 Euro", "Nüziders bei Bludenz"),
+            ("Grundstück in Bürserberg bei Bludenz, 900 m²", "Bürserberg bei Bludenz"),
+            ("Wohnung in St. Gallenkirch um 290.000 Euro", "St. Gallenkirch"),
+            ("Wohnung in St. Anton im Montafon um 310.000 Euro", "St. Anton im Montafon"),
+            ("Haus in St. Gerold um 450.000 Euro", "St. Gerold"),
+            ("Wohnung in Sankt Gallenkirch um 280.000 Euro", "Sankt Gallenkirch"),
+            ("Haus in Schruns im Montafon um 690.000 Euro", "Schruns im Montafon"),
+            ("Wohnung in Tschagguns im Montafon um 270.000 Euro", "Tschagguns im Montafon"),
+            ("Haus in Gaschurn im Montafon um 720.000 Euro", "Gaschurn im Montafon"),
+            ("Haus in Vandans im Montafon um 430.000 Euro", "Vandans im Montafon"),
+            ("Grundstück in Bartholomäberg im Montafon um 90.000 Euro", "Bartholomäberg im Montafon"),
+            ("Wohnung in Lech am Arlberg um 1.900.000 Euro", "Lech am Arlberg"),
+            ("Haus in Warth am Arlberg um 850.000 Euro", "Warth am Arlberg"),
+            ("Wohnung in Klösterle am Arlberg um 260.000 Euro", "Klösterle am Arlberg"),
+            ("Wohnung in Mittelberg im Kleinwalsertal um 420.000 Euro", "Mittelberg im Kleinwalsertal"),
+            ("Haus in Riezlern im Kleinwalsertal um 890.000 Euro", "Riezlern im Kleinwalsertal"),
+            ("Haus in Sonntag im Großen Walsertal um 240.000 Euro", "Sonntag im Großen Walsertal"),
+            ("Haus in Raggal im Großen Walsertal um 260.000 Euro", "Raggal im Großen Walsertal"),
+            ("Wohnung in Fontanella im Großen Walsertal, 60 m²", "Fontanella im Großen Walsertal"),
+            ("Haus in Nenzing im Walgau um 540.000 Euro", "Nenzing im Walgau"),
+            ("Wohnung in Satteins im Walgau um 300.000 Euro", "Satteins im Walgau"),
+            ("Haus in Thüringen im Walgau um 470.000 Euro", "Thüringen im Walgau"),
+            ("Haus in Brand im Brandnertal um 610.000 Euro", "Brand im Brandnertal"),
+            ("Wohnung in Feldkirch an der Ill um 360.000 Euro", "Feldkirch an der Ill"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(municipality(text), expected, "{}", text);
+        }
+    }
+}
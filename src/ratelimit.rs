@@ -0,0 +1,89 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Token bucket rate limiter
+///
+/// The bucket holds up to `max_tokens` tokens and refills at `refill_rate`
+/// tokens per second. Each request consumes one token, so `max_tokens` is the
+/// allowed burst size and `refill_rate` the sustained requests per second.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    tokens: f64,
+    max_tokens: f64,
+    refill_rate: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket
+    pub fn new(max_tokens: f64, refill_rate: f64) -> Self {
+        Self {
+            tokens: max_tokens,
+            max_tokens,
+            refill_rate,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.max_tokens);
+        self.last_refill = now;
+    }
+
+    /// Take a token if one is available without waiting
+    pub fn try_acquire(&mut self) -> bool {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// How long until a token becomes available, zero if one is available now
+    pub fn time_until_token(&mut self) -> Duration {
+        self.refill();
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.refill_rate)
+        }
+    }
+
+    /// Block only as long as necessary to acquire a token, then consume it
+    pub fn wait_for_token(&mut self) {
+        let wait = self.time_until_token();
+        if !wait.is_zero() {
+            thread::sleep(wait);
+            self.refill();
+        }
+        self.tokens = (self.tokens - 1.0).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_empty() {
+        let mut bucket = TokenBucket::new(3.0, 0.001);
+        assert!(bucket.try_acquire());
+        assert!(bucket.try_acquire());
+        assert!(bucket.try_acquire());
+        assert!(!bucket.try_acquire());
+    }
+
+    #[test]
+    fn test_wait_for_token_sleeps_until_refilled() {
+        let mut bucket = TokenBucket::new(1.0, 20.0);
+        bucket.wait_for_token();
+        let start = Instant::now();
+        bucket.wait_for_token();
+        // One token at 20 tokens/sec takes ~50ms to refill
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
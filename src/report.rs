@@ -0,0 +1,619 @@
+use crate::models::{Locale, Property, PropertyKind, UnavailableReason};
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// Chart.js colors per property kind, in legend order
+const SERIES: [(PropertyKind, &str); 5] = [
+    (PropertyKind::House, "#d9534f"),
+    (PropertyKind::Apartment, "#0275d8"),
+    (PropertyKind::Land, "#5cb85c"),
+    (PropertyKind::Garage, "#f0ad4e"),
+    (PropertyKind::Commercial, "#5bc0de"),
+];
+
+/// Median price per month and property kind, keyed by the first day of the month
+///
+/// Uses the listing `date`, falling back to `first_seen`. Properties without a
+/// parseable price or any date, and those of unknown kind, are skipped.
+pub fn compute_monthly_medians(properties: &[Property]) -> HashMap<PropertyKind, Vec<(NaiveDate, f64)>> {
+    let mut prices: HashMap<PropertyKind, HashMap<NaiveDate, Vec<f64>>> = HashMap::new();
+    for property in properties {
+        let (Some(price), Some(date)) = (property.price.euros(), property.date.or(property.first_seen)) else {
+            continue;
+        };
+        if property.property_type.is_unknown() {
+            continue;
+        }
+        let Some(month) = date.with_day(1) else { continue };
+        prices
+            .entry(property.property_type.kind)
+            .or_default()
+            .entry(month)
+            .or_default()
+            .push(price);
+    }
+
+    prices
+        .into_iter()
+        .map(|(kind, by_month)| {
+            let mut medians: Vec<(NaiveDate, f64)> = by_month
+                .into_iter()
+                .map(|(month, mut prices)| (month, median(&mut prices)))
+                .collect();
+            medians.sort_by_key(|(month, _)| *month);
+            (kind, medians)
+        })
+        .collect()
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Chart.js line chart data with one dataset per property kind
+///
+/// All datasets share the sorted month labels; months without sales are `null`.
+fn price_trend_chart_data(medians: &HashMap<PropertyKind, Vec<(NaiveDate, f64)>>) -> serde_json::Value {
+    let months: BTreeSet<NaiveDate> = medians.values().flatten().map(|(month, _)| *month).collect();
+
+    let datasets: Vec<serde_json::Value> = SERIES
+        .iter()
+        .filter_map(|(kind, color)| {
+            let series: HashMap<NaiveDate, f64> = medians.get(kind)?.iter().copied().collect();
+            let data: Vec<Option<f64>> = months.iter().map(|month| series.get(month).copied()).collect();
+            Some(serde_json::json!({
+                "label": crate::models::capitalize(&kind.to_string()),
+                "data": data,
+                "borderColor": color,
+                "backgroundColor": color,
+                "spanGaps": true,
+                "tension": 0.2,
+            }))
+        })
+        .collect();
+
+    serde_json::json!({
+        "labels": months.iter().map(|month| month.format("%Y-%m").to_string()).collect::<Vec<_>>(),
+        "datasets": datasets,
+    })
+}
+
+/// Listing count and average price of one agency
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgencyStats {
+    pub agency: String,
+    pub listings: usize,
+    /// Average over the listings with a parseable price
+    pub average_price: Option<f64>,
+}
+
+/// Agencies ranked by number of listings, ties broken by name
+pub fn compute_agency_stats(properties: &[Property]) -> Vec<AgencyStats> {
+    let mut by_agency: HashMap<&str, (usize, Vec<f64>)> = HashMap::new();
+    for property in properties {
+        let Some(agency) = property.agency_name.as_deref() else { continue };
+        let (listings, prices) = by_agency.entry(agency).or_default();
+        *listings += 1;
+        prices.extend(property.price.euros());
+    }
+
+    let mut stats: Vec<AgencyStats> = by_agency
+        .into_iter()
+        .map(|(agency, (listings, prices))| AgencyStats {
+            agency: agency.to_string(),
+            listings,
+            average_price: (!prices.is_empty()).then(|| prices.iter().sum::<f64>() / prices.len() as f64),
+        })
+        .collect();
+    stats.sort_by(|a, b| b.listings.cmp(&a.listings).then_with(|| a.agency.cmp(&b.agency)));
+    stats
+}
+
+/// Number of unavailable properties per reason, most common first
+///
+/// Properties marked unavailable before reasons were recorded count as `Unknown`.
+pub fn count_unavailable_by_reason(properties: &[Property]) -> Vec<(UnavailableReason, usize)> {
+    let mut counts: HashMap<UnavailableReason, usize> = HashMap::new();
+    for property in properties.iter().filter(|p| p.is_unavailable()) {
+        *counts.entry(property.unavailable_reason.unwrap_or(UnavailableReason::Unknown)).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.to_string().cmp(&b.0.to_string())));
+    counts
+}
+
+/// Listing count and price range of one municipality
+///
+/// The prices are None if none of its listings has a parseable price.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LocationStats {
+    pub count: usize,
+    pub mean_price: Option<f64>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+}
+
+/// Listing count and prices per municipality
+///
+/// All properties with a known municipality are counted, the prices come
+/// from those with a parseable price.
+pub fn compute_location_density(properties: &[Property]) -> HashMap<String, LocationStats> {
+    let mut by_location: HashMap<&str, (usize, Vec<f64>)> = HashMap::new();
+    for property in properties.iter().filter(|p| !p.location.is_unknown()) {
+        let (count, prices) = by_location.entry(&property.location.municipality).or_default();
+        *count += 1;
+        prices.extend(property.price.euros());
+    }
+
+    by_location
+        .into_iter()
+        .map(|(location, (count, prices))| {
+            let priced = !prices.is_empty();
+            let stats = LocationStats {
+                count,
+                mean_price: priced.then(|| prices.iter().sum::<f64>() / prices.len() as f64),
+                min_price: priced.then(|| prices.iter().copied().fold(f64::INFINITY, f64::min)),
+                max_price: priced.then(|| prices.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+            };
+            (location.to_string(), stats)
+        })
+        .collect()
+}
+
+/// Escape text for use in HTML content and quoted attribute values
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Locations ranked by listing count, ties broken by name
+pub fn rank_locations(density: &HashMap<String, LocationStats>) -> Vec<(&str, &LocationStats)> {
+    let mut ranked: Vec<_> = density.iter().map(|(location, stats)| (location.as_str(), stats)).collect();
+    ranked.sort_by(|a, b| b.1.count.cmp(&a.1.count).then_with(|| a.0.cmp(b.0)));
+    ranked
+}
+
+/// Table rows shaded by listing count, with an SVG sparkline of each price range
+///
+/// The sparklines share one scale from the lowest to the highest price of all
+/// locations, the dot marks the mean.
+fn location_table_rows(density: &HashMap<String, LocationStats>) -> String {
+    let ranked = rank_locations(density);
+    let max_count = ranked.first().map_or(1, |(_, stats)| stats.count) as f64;
+    let low = density.values().filter_map(|s| s.min_price).fold(f64::INFINITY, f64::min);
+    let high = density.values().filter_map(|s| s.max_price).fold(f64::NEG_INFINITY, f64::max);
+    let x = |price: f64| if high > low { 2.0 + (price - low) / (high - low) * 116.0 } else { 60.0 };
+    let locale = Locale::austrian();
+
+    ranked
+        .iter()
+        .map(|(location, stats)| {
+            let (mean, range) = match (stats.mean_price, stats.min_price, stats.max_price) {
+                (Some(mean), Some(min), Some(max)) => (
+                    locale.format_euros(mean.round()),
+                    format!(
+                        r##"<svg width="120" height="12"><line x1="{x_min:.1}" y1="6" x2="{x_max:.1}" y2="6" stroke="#333" stroke-width="2"/><circle cx="{x_mean:.1}" cy="6" r="3" fill="#0275d8"/></svg> {min} – {max}"##,
+                        x_min = x(min),
+                        x_max = x(max),
+                        x_mean = x(mean),
+                        min = locale.format_euros(min.round()),
+                        max = locale.format_euros(max.round()),
+                    ),
+                ),
+                _ => ("–".to_string(), "no prices".to_string()),
+            };
+            format!(
+                r##"<tr style="background: rgba(217, 83, 79, {alpha:.2})"><td>{location}</td><td>{count}</td><td>{mean}</td><td>{range}</td></tr>"##,
+                alpha = 0.1 + 0.6 * stats.count as f64 / max_count,
+                location = escape_html(location),
+                count = stats.count,
+                mean = mean,
+                range = range,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n            ")
+}
+
+/// Listings younger than this many days get a "New listing!" badge in the HTML report
+const FRESH_LISTING_DAYS: u32 = 7;
+
+/// List items of the fresh available listings, newest first
+fn fresh_listing_items(properties: &[Property]) -> String {
+    let mut fresh: Vec<&Property> = properties
+        .iter()
+        .filter(|p| p.is_available() && p.is_fresh(FRESH_LISTING_DAYS))
+        .collect();
+    fresh.sort_by_key(|p| std::cmp::Reverse(p.date.or(p.first_seen)));
+    if fresh.is_empty() {
+        return format!("<li>No listings from the last {} days.</li>", FRESH_LISTING_DAYS);
+    }
+    fresh
+        .iter()
+        .map(|p| {
+            format!(
+                r#"<li><span class="badge">New listing!</span> <a href="{url}">{name}</a> – {price}, {location}</li>"#,
+                url = escape_html(&p.url),
+                name = escape_html(&p.name),
+                price = escape_html(&p.display_price(Locale::austrian())),
+                location = escape_html(&p.location.to_string()),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n        ")
+}
+
+/// Properties first seen on or after `since` (all if `None`), newest first
+pub fn newest_first(properties: Vec<&Property>, since: Option<NaiveDate>) -> Vec<&Property> {
+    let mut newest: Vec<&Property> = properties
+        .into_iter()
+        .filter(|property| since.map_or(true, |since| property.first_seen.is_some_and(|seen| seen >= since)))
+        .collect();
+    newest.sort_by_key(|property| std::cmp::Reverse(property.first_seen));
+    newest
+}
+
+/// Mean and median `utils::data_completeness_score` of all properties, None if there are none
+pub fn completeness_stats(properties: &[Property]) -> Option<(f64, f64)> {
+    let mut scores: Vec<f64> = properties.iter().map(crate::utils::data_completeness_score).collect();
+    if scores.is_empty() {
+        return None;
+    }
+    let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+    Some((mean, median(&mut scores)))
+}
+
+/// Number of properties per 10-year age bucket, keyed by the bucket's lowest age
+///
+/// Properties without a construction year are left out.
+pub fn age_histogram(properties: &[Property]) -> Vec<(u32, usize)> {
+    let mut buckets = std::collections::BTreeMap::new();
+    for age in properties.iter().filter_map(Property::age_years) {
+        *buckets.entry(age / 10 * 10).or_default() += 1;
+    }
+    buckets.into_iter().collect()
+}
+
+/// Number of properties per property type confidence, rounded to one decimal, most confident first
+pub fn confidence_distribution(properties: &[Property]) -> Vec<(f32, usize)> {
+    let mut counts = std::collections::BTreeMap::new();
+    for property in properties {
+        let tenths = (property.property_type.confidence * 10.0).round() as u32;
+        *counts.entry(std::cmp::Reverse(tenths)).or_default() += 1;
+    }
+    counts.into_iter().map(|(std::cmp::Reverse(tenths), count)| (tenths as f32 / 10.0, count)).collect()
+}
+
+/// Number of properties that can't be shown on a map because they have no coordinates
+pub fn missing_coordinates_count(properties: &[Property]) -> usize {
+    properties.iter().filter(|property| property.coordinates.is_none()).count()
+}
+
+/// Write a standalone HTML report with a monthly median price chart per property kind
+pub fn generate_html_report(properties: &[Property], path: &str) -> Result<()> {
+    let medians = compute_monthly_medians(properties);
+    let priced: usize = medians.values().map(|series| series.len()).sum();
+    let chart_data = price_trend_chart_data(&medians);
+    let location_rows = location_table_rows(&compute_location_density(properties));
+    let fresh_items = fresh_listing_items(properties);
+
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>LaendleFinder - Price Report</title>
+    <script src="https://cdn.jsdelivr.net/npm/chart.js@4.4.1/dist/chart.umd.min.js"></script>
+    <style>
+        body {{ font-family: Arial, sans-serif; margin: 2em; }}
+        .chart-container {{ position: relative; width: 100%; max-width: 1100px; height: 60vh; }}
+        table {{ border-collapse: collapse; }}
+        th, td {{ padding: 0.3em 0.8em; text-align: left; }}
+        .badge {{ background: #5cb85c; color: white; border-radius: 0.3em; padding: 0.1em 0.4em; font-size: 0.85em; }}
+    </style>
+</head>
+<body>
+    <h1>LaendleFinder Price Report</h1>
+    <p>{total} properties, {priced} monthly medians from properties with a price and date.</p>
+    <h2>Monthly median price</h2>
+    <div class="chart-container"><canvas id="price-trend"></canvas></div>
+    <script>
+        const euros = new Intl.NumberFormat('de-AT', {{ style: 'currency', currency: 'EUR', maximumFractionDigits: 0 }});
+        new Chart(document.getElementById('price-trend'), {{
+            type: 'line',
+            data: {chart_data},
+            options: {{
+                responsive: true,
+                maintainAspectRatio: false,
+                interaction: {{ mode: 'index', intersect: false }},
+                plugins: {{
+                    legend: {{ position: 'top' }},
+                    tooltip: {{
+                        callbacks: {{
+                            label: (context) => `${{context.dataset.label}}: ${{euros.format(context.parsed.y)}}`
+                        }}
+                    }}
+                }},
+                scales: {{
+                    y: {{ ticks: {{ callback: (value) => euros.format(value) }} }}
+                }}
+            }}
+        }});
+    </script>
+    <h2>New listings</h2>
+    <ul>
+        {fresh_items}
+    </ul>
+    <h2>Listings per municipality</h2>
+    <table>
+        <thead><tr><th>Municipality</th><th>Listings</th><th>Mean price</th><th>Price range</th></tr></thead>
+        <tbody>
+            {location_rows}
+        </tbody>
+    </table>
+</body>
+</html>
+"#,
+        total = properties.len(),
+        priced = priced,
+        chart_data = chart_data,
+        location_rows = location_rows,
+        fresh_items = fresh_items,
+    );
+
+    std::fs::write(path, html).with_context(|| format!("Failed to write HTML report: {}", path))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ListingType, Location, Price, PropertyFilter, PropertyType};
+
+    fn sale(kind: PropertyKind, price: &str, date: Option<&str>, first_seen: Option<&str>) -> Property {
+        let parse = |d: Option<&str>| d.map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap());
+        Property {
+            url: format!("https://www.vol.at/{}-{}", kind, price),
+            internal_id: None,
+            page_fingerprint: None,
+            completeness_score: None,
+            name: "Test".to_string(),
+            price: Price::from_str(price),
+            location: Location::new("Dornbirn"),
+            property_type: kind.into(),
+            listing_type: ListingType::Sold,
+            unavailable_reason: None,
+            date: parse(date),
+            coordinates: None,
+            quality_score: None,
+            plot_polygon: None,
+            description: None,
+            features: Vec::new(),
+            driving_distance_km: None,
+            rooms: None,
+            floor_level: None,
+            construction_year: None,
+            agency_name: None,
+            agency_phone: None,
+            address: None,
+            size_living: None,
+            size_ground: None,
+            first_seen: parse(first_seen),
+            last_seen: None,
+        }
+    }
+
+    #[test]
+    fn test_compute_agency_stats() {
+        let with_agency = |price, agency: Option<&str>| {
+            let mut property = sale(PropertyKind::House, price, None, None);
+            property.agency_name = agency.map(str::to_string);
+            property
+        };
+        let properties = vec![
+            with_agency("300000", Some("Zima")),
+            with_agency("500000", Some("Rhomberg")),
+            with_agency("Auf Anfrage", Some("Rhomberg")),
+            with_agency("700000", Some("Rhomberg")),
+            with_agency("400000", Some("Amann")),
+            with_agency("900000", None),
+        ];
+
+        let stats = compute_agency_stats(&properties);
+        let ranked: Vec<(&str, usize, Option<f64>)> =
+            stats.iter().map(|s| (s.agency.as_str(), s.listings, s.average_price)).collect();
+        assert_eq!(ranked, vec![
+            ("Rhomberg", 3, Some(600000.0)),
+            ("Amann", 1, Some(400000.0)),
+            ("Zima", 1, Some(300000.0)),
+        ]);
+    }
+
+    #[test]
+    fn test_count_unavailable_by_reason() {
+        let unavailable = |reason| {
+            let mut property = sale(PropertyKind::House, "300000", None, None);
+            property.listing_type = ListingType::Unavailable;
+            property.unavailable_reason = reason;
+            property
+        };
+        let properties = vec![
+            unavailable(Some(UnavailableReason::NotFound)),
+            unavailable(Some(UnavailableReason::Removed)),
+            unavailable(Some(UnavailableReason::NotFound)),
+            unavailable(None),
+            sale(PropertyKind::Land, "200000", None, None),
+        ];
+        assert_eq!(count_unavailable_by_reason(&properties), vec![
+            (UnavailableReason::NotFound, 2),
+            (UnavailableReason::Removed, 1),
+            (UnavailableReason::Unknown, 1),
+        ]);
+    }
+
+    #[test]
+    fn test_compute_location_density() {
+        let at = |location: &str, price| {
+            let mut property = sale(PropertyKind::House, price, None, None);
+            property.location = Location::new(location);
+            property
+        };
+        let properties = vec![
+            at("Dornbirn", "300000"),
+            at("Dornbirn", "500000"),
+            at("Dornbirn", "Auf Anfrage"),
+            at("Bludenz", "200000"),
+            at("Lech & Zürs", "Auf Anfrage"),
+            at("Unknown", "100000"),
+        ];
+
+        let density = compute_location_density(&properties);
+        assert_eq!(density.len(), 3);
+        assert_eq!(
+            density["Dornbirn"],
+            LocationStats { count: 3, mean_price: Some(400000.0), min_price: Some(300000.0), max_price: Some(500000.0) }
+        );
+        assert_eq!(density["Lech & Zürs"], LocationStats { count: 1, mean_price: None, min_price: None, max_price: None });
+        let ranked: Vec<&str> = rank_locations(&density).into_iter().map(|(location, _)| location).collect();
+        assert_eq!(ranked, vec!["Dornbirn", "Bludenz", "Lech & Zürs"]);
+        let rows = location_table_rows(&density);
+        assert!(rows.contains("<td>Bludenz</td><td>1</td>"));
+        assert!(rows.contains("<td>Lech &amp; Zürs</td><td>1</td><td>–</td>"));
+    }
+
+    #[test]
+    fn test_fresh_listing_items() {
+        let today = chrono::Utc::now().naive_utc().date().to_string();
+        let mut fresh = sale(PropertyKind::House, "300000", Some(&today), None);
+        fresh.listing_type = ListingType::Available;
+        fresh.name = "Haus <mit> Garten & Pool".to_string();
+        let old = sale(PropertyKind::Land, "200000", Some("2020-01-01"), None);
+
+        let items = fresh_listing_items(&[fresh.clone(), old]);
+        assert_eq!(items.matches("New listing!").count(), 1);
+        assert!(items.contains(&fresh.url));
+        assert!(items.contains("Haus &lt;mit&gt; Garten &amp; Pool"), "{}", items);
+        assert!(fresh_listing_items(&[]).contains("No listings"));
+    }
+
+    #[test]
+    fn test_missing_coordinates_count() {
+        let mut located = sale(PropertyKind::House, "300000", None, None);
+        located.coordinates = Some(crate::models::Coordinates { lat: 47.41, lng: 9.74 });
+        let properties = vec![located, sale(PropertyKind::Land, "200000", None, None)];
+        assert_eq!(missing_coordinates_count(&properties), 1);
+        assert_eq!(missing_coordinates_count(&[]), 0);
+    }
+
+    #[test]
+    fn test_compute_monthly_medians() {
+        let properties = vec![
+            sale(PropertyKind::House, "500000", Some("2024-03-02"), None),
+            sale(PropertyKind::House, "700000", Some("2024-03-28"), None),
+            sale(PropertyKind::House, "900000", None, Some("2024-03-15")),
+            sale(PropertyKind::House, "400000", Some("2024-01-10"), None),
+            sale(PropertyKind::Land, "200000", Some("2024-02-01"), None),
+            sale(PropertyKind::Land, "300000", Some("2024-02-20"), None),
+            // Skipped: no price, no date, unknown kind
+            sale(PropertyKind::Land, "Preis auf Anfrage", Some("2024-02-05"), None),
+            sale(PropertyKind::Apartment, "300000", None, None),
+            sale(PropertyKind::Unknown, "100000", Some("2024-02-05"), None),
+        ];
+
+        let medians = compute_monthly_medians(&properties);
+        let month = |m| NaiveDate::from_ymd_opt(2024, m, 1).unwrap();
+        assert_eq!(medians[&PropertyKind::House], vec![(month(1), 400000.0), (month(3), 700000.0)]);
+        assert_eq!(medians[&PropertyKind::Land], vec![(month(2), 250000.0)]);
+        assert_eq!(medians.len(), 2);
+
+        let chart = price_trend_chart_data(&medians);
+        assert_eq!(chart["labels"], serde_json::json!(["2024-01", "2024-02", "2024-03"]));
+        assert_eq!(chart["datasets"][0]["label"], "House");
+        assert_eq!(chart["datasets"][0]["data"], serde_json::json!([400000.0, null, 700000.0]));
+        assert_eq!(chart["datasets"][1]["label"], "Land");
+    }
+
+    #[test]
+    fn test_newest_first() {
+        let properties = [
+            sale(PropertyKind::House, "300000", None, Some("2025-03-01")),
+            sale(PropertyKind::House, "400000", None, None),
+            sale(PropertyKind::House, "500000", None, Some("2025-03-10")),
+            sale(PropertyKind::House, "600000", None, Some("2025-02-20")),
+        ];
+        let prices = |newest: Vec<&Property>| newest.iter().map(|p| p.price.to_string()).collect::<Vec<_>>();
+
+        let since = NaiveDate::from_ymd_opt(2025, 3, 1);
+        assert_eq!(prices(newest_first(properties.iter().collect(), since)), ["500000", "300000"]);
+        assert_eq!(prices(newest_first(properties.iter().collect(), None)), ["500000", "300000", "600000", "400000"]);
+    }
+
+    #[test]
+    fn test_completeness_stats() {
+        assert_eq!(completeness_stats(&[]), None);
+
+        let mut properties = vec![
+            sale(PropertyKind::House, "300000", None, None),
+            sale(PropertyKind::House, "400000", Some("2024-01-10"), None),
+            sale(PropertyKind::Land, "200000", Some("2024-02-01"), None),
+        ];
+        properties[2].address = Some("Marktstraße 1".to_string());
+        properties[2].rooms = Some(3.0);
+
+        let (mean, median) = completeness_stats(&properties).unwrap();
+        assert!((mean - 4.0 / 36.0).abs() < 1e-9, "{}", mean);
+        assert!((median - 1.0 / 12.0).abs() < 1e-9, "{}", median);
+    }
+
+    #[test]
+    fn test_confidence_distribution() {
+        let mut fuzzy = sale(PropertyKind::House, "300000", None, None);
+        fuzzy.property_type = PropertyType::new(PropertyKind::House, 0.7);
+        let properties = vec![
+            sale(PropertyKind::House, "300000", None, None),
+            fuzzy.clone(),
+            fuzzy,
+            sale(PropertyKind::Unknown, "300000", None, None),
+        ];
+        assert_eq!(confidence_distribution(&properties), vec![(1.0, 1), (0.7, 2), (0.0, 1)]);
+        assert!(confidence_distribution(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_age_histogram() {
+        let current_year = chrono::Utc::now().year() as u16;
+        let built = |years_ago: Option<u16>| {
+            let mut property = sale(PropertyKind::House, "300000", None, None);
+            property.construction_year = years_ago.map(|years| current_year - years);
+            property
+        };
+        let properties = vec![built(Some(3)), built(Some(9)), built(Some(10)), built(Some(47)), built(None)];
+
+        assert_eq!(properties[3].age_years(), Some(47));
+        assert_eq!(properties[4].age_years(), None);
+        assert_eq!(age_histogram(&properties), vec![(0, 2), (10, 1), (40, 1)]);
+
+        let filter = PropertyFilter::new().min_age_years(5).max_age_years(20);
+        let ages: Vec<_> = properties.iter().filter(|p| filter.matches(p)).map(|p| p.age_years()).collect();
+        assert_eq!(ages, vec![Some(9), Some(10)]);
+    }
+}
@@ -1,45 +1,81 @@
-use crate::models::{ListingType, Property, PropertyType};
+use crate::common_scraper::{ScrapingContext, ScrapingOptions};
+use crate::models::{Coordinates, ListingType, Price, Property, PropertyType};
 use crate::parser;
+use crate::debug::Tracer;
+use crate::error::ScrapeError;
+use crate::http_logging::SendLogged;
 use crate::tui::ScraperTUI;
-use crate::utils::{sanitize_url, get_random_user_agent};
+use crate::utils::{self, sanitize_url};
 use crate::{debug_eprintln, debug_println};
 use anyhow::{Context, Result};
 use chrono::NaiveDate;
 use scraper::{Html, Selector};
 use serde_json::Value;
 use std::collections::HashSet;
+use std::sync::OnceLock;
 
-const INDEX_URL: &str = "https://www.vol.at/themen/grund-und-boden";
+/// Topic page of sold properties ("Grund und Boden")
+pub const INDEX_URL: &str = "https://www.vol.at/themen/grund-und-boden";
 
-pub fn scrape_new_urls_until_no_new_found(mut tui: Option<&mut ScraperTUI>, existing_urls: &HashSet<String>) -> Result<Vec<String>> {
-    use std::collections::HashSet;
-    
+/// Topic page of sold garages and parking spaces ("Garagen & Stellplätze")
+pub const GARAGE_INDEX_URL: &str = "https://www.vol.at/themen/garagen-und-stellplaetze";
+
+/// Whether a vol.at URL is an article about a property sale
+///
+/// Articles live at `/{slug}/{numeric id}`; the slug has to name a property
+/// type, which sorts out the news articles a sitemap lists as well.
+pub fn is_property_article_url(url: &str) -> bool {
+    if utils::domain_from_url(url) != "vol.at" {
+        return false;
+    }
+    let path = sanitize_url(url);
+    let segments: Vec<&str> = path
+        .split_once("vol.at/")
+        .map_or("", |(_, path)| path)
+        .trim_end_matches('/')
+        .split('/')
+        .collect();
+    match segments[..] {
+        [slug, id] => {
+            !id.is_empty()
+                && id.chars().all(|c| c.is_ascii_digit())
+                && !PropertyType::from_string(&slug.replace('-', " ")).is_unknown()
+        }
+        _ => false,
+    }
+}
+
+pub fn scrape_new_urls_until_no_new_found(context: &ScrapingContext, tui: Option<&mut ScraperTUI>, existing_urls: &HashSet<String>) -> Result<Vec<String>> {
+    scrape_new_topic_urls_until_no_new_found(context, INDEX_URL, tui, existing_urls)
+}
+
+/// Gather article URLs of a vol.at topic page until 5 consecutive pages yield nothing new
+pub fn scrape_new_topic_urls_until_no_new_found(context: &ScrapingContext, base_url: &str, mut tui: Option<&mut ScraperTUI>, existing_urls: &HashSet<String>) -> Result<Vec<String>> {
     let mut all_property_urls = Vec::new();
     let mut seen_urls = HashSet::new();
     let mut new_count = 0;
     let mut known_count = 0;
     let mut pages_without_new = 0;
     let mut current_page = 1;
-    let base_url = "https://www.vol.at/themen/grund-und-boden";
 
     if let Some(tui) = tui.as_mut() {
         tui.start_gathering_new_mode()?;
     }
 
     loop {
-        let page_url = if current_page == 1 {
-            base_url.to_string()
-        } else {
-            format!("{}/page/{}", base_url, current_page)
-        };
-        
+        let page_url = topic_page_url(base_url, current_page);
+
         debug_println!("Scraping index page: {}", page_url);
 
-        let property_urls = if current_page == 1 {
-            scrape_index_page()?
-        } else {
-            scrape_index_page_with_url(&page_url)?
-        };
+        let (property_urls, total) = scrape_index_page_with_total(context, &page_url)?;
+        // The total is only shown: stored URLs include other topics and articles
+        // that dropped out of this one, so it can't tell whether anything is new
+        if let Some(total) = total.filter(|_| current_page == 1) {
+            debug_println!("Found {} total listings", total);
+            if let Some(tui) = tui.as_mut() {
+                tui.set_total_listings(total);
+            }
+        }
 
         if property_urls.is_empty() {
             debug_println!("No properties found on page {}, stopping", current_page);
@@ -86,14 +122,16 @@ pub fn scrape_new_urls_until_no_new_found(mut tui: Option<&mut ScraperTUI>, exis
     Ok(all_property_urls)
 }
 
-pub fn scrape_all_index_pages(max_pages: usize, mut tui: Option<&mut ScraperTUI>, existing_urls: &HashSet<String>) -> Result<Vec<String>> {
-    use std::collections::HashSet;
-    
+pub fn scrape_all_index_pages(context: &ScrapingContext, max_pages: usize, tui: Option<&mut ScraperTUI>, existing_urls: &HashSet<String>) -> Result<Vec<String>> {
+    scrape_all_topic_index_pages(context, INDEX_URL, max_pages, tui, existing_urls)
+}
+
+/// Gather article URLs from up to `max_pages` pages of a vol.at topic page
+pub fn scrape_all_topic_index_pages(context: &ScrapingContext, base_url: &str, max_pages: usize, mut tui: Option<&mut ScraperTUI>, existing_urls: &HashSet<String>) -> Result<Vec<String>> {
     let mut all_property_urls = Vec::new();
     let mut seen_urls = HashSet::new();
     let mut new_count = 0;
     let mut known_count = 0;
-    let base_url = "https://www.vol.at/themen/grund-und-boden";
 
     if let Some(tui) = tui.as_mut() {
         tui.start_gathering(max_pages)?;
@@ -101,8 +139,12 @@ pub fn scrape_all_index_pages(max_pages: usize, mut tui: Option<&mut ScraperTUI>
 
     debug_println!("Scraping index page: {}", base_url);
 
-    // Scrape the first page
-    let property_urls = scrape_index_page()?;
+    // Scrape the first page; its total tells how many pages there actually are
+    let (property_urls, total) = scrape_index_page_with_total(context, base_url)?;
+    let max_pages = match total {
+        Some(total) => max_pages.min(index_page_count(total, property_urls.len()).max(1)),
+        None => max_pages,
+    };
     for url in property_urls {
         if seen_urls.insert(url.clone()) {
             all_property_urls.push(url.clone());
@@ -128,10 +170,10 @@ pub fn scrape_all_index_pages(max_pages: usize, mut tui: Option<&mut ScraperTUI>
 
     // Otherwise, scrape additional pages up to max_pages
     for page in 2..=max_pages {
-        let page_url = format!("{}/page/{}", base_url, page);
+        let page_url = topic_page_url(base_url, page);
         debug_println!("Scraping index page: {}", page_url);
 
-        match scrape_index_page_with_url(&page_url) {
+        match scrape_index_page_with_url(context, &page_url) {
             Ok(urls) => {
                 if urls.is_empty() {
                     debug_println!("No more properties found on page {}, stopping", page);
@@ -171,19 +213,47 @@ pub fn scrape_all_index_pages(max_pages: usize, mut tui: Option<&mut ScraperTUI>
     Ok(all_property_urls)
 }
 
-pub fn scrape_index_page() -> Result<Vec<String>> {
-    scrape_index_page_with_url(INDEX_URL)
+/// Check that a topic page responds in time and still embeds its `#topicDataNode`
+pub fn check_topic_page(context: &mut ScrapingContext, url: &str) -> Result<()> {
+    let body = crate::common_scraper::fetch_for_health_check(context, url)?;
+    let document = Html::parse_document(&body);
+    let script_selector = Selector::parse("#topicDataNode").unwrap();
+    let script = document
+        .select(&script_selector)
+        .next()
+        .with_context(|| format!("Topic data script not found on {}", url))?;
+    serde_json::from_str::<Value>(&script.inner_html())
+        .with_context(|| format!("Topic data on {} is not valid JSON", url))?;
+    Ok(())
+}
+
+pub fn scrape_index_page(context: &ScrapingContext) -> Result<Vec<String>> {
+    scrape_index_page_with_url(context, INDEX_URL)
+}
+
+pub fn scrape_index_page_with_url(context: &ScrapingContext, url: &str) -> Result<Vec<String>> {
+    scrape_index_page_with_total(context, url).map(|(links, _)| links)
 }
 
-fn scrape_index_page_with_url(url: &str) -> Result<Vec<String>> {
+/// Article links of an index page, with the topic's total article count if the page has one
+pub fn scrape_index_page_with_total(context: &ScrapingContext, url: &str) -> Result<(Vec<String>, Option<usize>)> {
     debug_println!("Scraping index page: {}", url);
 
     // Fetch the index page
-    let response = reqwest::blocking::get(url).context("Failed to fetch index page")?;
+    let fetch_trace = Tracer::new("fetch_index_page", 5000);
+    let response = context
+        .http_client_builder()
+        .build()?
+        .get(url)
+        .send_counted(context.performance())
+        .context("Failed to fetch index page")?;
     let html = response.text().context("Failed to get response text")?;
+    drop(fetch_trace);
 
     // Parse the HTML
+    let parse_trace = Tracer::new("parse_index_page", 1000);
     let document = Html::parse_document(&html);
+    drop(parse_trace);
 
     // Find the script tag containing the JSON data
     let script_selector = Selector::parse("#topicDataNode").unwrap();
@@ -194,34 +264,291 @@ fn scrape_index_page_with_url(url: &str) -> Result<Vec<String>> {
 
     // Parse the JSON content
     let json_str = script.inner_html();
+    let json_trace = Tracer::new("parse_topic_json", 1000);
     let json: Value = serde_json::from_str(&json_str).context("Failed to parse JSON data")?;
+    drop(json_trace);
+
+    let links = topic_hit_links(&json["prefetchedRawData"]);
+    debug_println!("Found {} property links on page", links.len());
+
+    Ok((links, topic_total(&json["prefetchedRawData"])))
+}
+
+/// URL of page `page` of a vol.at topic, counting from 1
+fn topic_page_url(base_url: &str, page: usize) -> String {
+    if page <= 1 {
+        base_url.to_string()
+    } else {
+        format!("{}/page/{}", base_url, page)
+    }
+}
+
+/// Number of index pages needed to list `total` articles at `per_page` articles each
+pub fn index_page_count(total: usize, per_page: usize) -> usize {
+    if per_page == 0 {
+        return 0;
+    }
+    total.div_ceil(per_page)
+}
+
+/// Article links from the `hits` array of a topic data object
+fn topic_hit_links(data: &Value) -> Vec<String> {
+    data["hits"]
+        .as_array()
+        .map(|hits| {
+            hits.iter()
+                .filter_map(|hit| hit["link"].as_str())
+                .map(|link| link.replace(r"\/", "/"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Total number of articles of a topic, called `total` or `count` depending on the endpoint
+fn topic_total(data: &Value) -> Option<usize> {
+    data["total"].as_u64().or_else(|| data["count"].as_u64()).map(|total| total as usize)
+}
+
+/// Gather article URLs of the "Grund und Boden" topic from the JSON data of its pages
+///
+/// Each topic page embeds its batch of articles and the topic's total article
+/// count in `prefetchedRawData`, which gives the number of pages. Pages are
+/// read until all pages or `max_pages` pages were read, or `threshold`
+/// consecutive pages contained no URL outside `existing_urls`.
+pub fn scrape_vol_json_api(max_pages: usize, existing_urls: &HashSet<String>, threshold: usize) -> Result<Vec<String>> {
+    let mut context = ScrapingContext::new(&ScrapingOptions::default());
+    scrape_vol_json_pages(&mut context, max_pages, existing_urls, threshold, None)
+}
+
+/// Like `scrape_vol_json_api` for new mode: reads all pages and shows progress in the TUI
+///
+/// Page requests wait for the vol.at rate limiter of `context`.
+pub fn scrape_new_urls_from_vol_json(
+    context: &mut ScrapingContext,
+    existing_urls: &HashSet<String>,
+    threshold: usize,
+    tui: Option<&mut ScraperTUI>,
+) -> Result<Vec<String>> {
+    scrape_vol_json_pages(context, usize::MAX, existing_urls, threshold, tui)
+}
+
+fn scrape_vol_json_pages(
+    context: &mut ScrapingContext,
+    max_pages: usize,
+    existing_urls: &HashSet<String>,
+    threshold: usize,
+    mut tui: Option<&mut ScraperTUI>,
+) -> Result<Vec<String>> {
+    context.wait_for_domain("vol.at");
+    let (mut links, total) = scrape_index_page_with_total(context, INDEX_URL)?;
+    let per_page = links.len();
+    if per_page == 0 {
+        return Ok(Vec::new());
+    }
+    // Without a count, keep going until a page comes back empty
+    let mut total_pages = total.map_or(usize::MAX, |total| index_page_count(total, per_page)).min(max_pages);
+    debug_println!("vol.at topic has {} pages of {} articles", total_pages, per_page);
 
-    // Extract all links from hits array
-    let mut links = Vec::new();
-    if let Some(hits) = json["prefetchedRawData"]["hits"].as_array() {
-        for hit in hits {
-            if let Some(link) = hit["link"].as_str() {
-                links.push(link.replace(r"\/", "/").to_string());
+    if let Some(tui) = tui.as_mut() {
+        tui.start_gathering_new_mode()?;
+        if let Some(total) = total {
+            tui.set_total_listings(total);
+        }
+    }
+
+    let mut all_urls = Vec::new();
+    let mut seen_urls = HashSet::new();
+    let (mut new_count, mut known_count) = (0, 0);
+    let mut pages_without_new = 0;
+    let mut page = 1;
+
+    loop {
+        let mut new_on_page = 0;
+        for url in links {
+            if seen_urls.insert(url.clone()) {
+                if existing_urls.contains(&url) {
+                    known_count += 1;
+                } else {
+                    new_count += 1;
+                    new_on_page += 1;
+                }
+                all_urls.push(url);
             }
         }
+        debug_println!("Topic page {}/{}: {} new URLs, {} total", page, total_pages, new_on_page, all_urls.len());
+        if let Some(tui) = tui.as_mut() {
+            let shown_pages = if total_pages == usize::MAX { 0 } else { total_pages };
+            tui.update_gathering_progress(page, shown_pages, all_urls.len(), new_count, known_count)?;
+        }
+
+        pages_without_new = if new_on_page == 0 { pages_without_new + 1 } else { 0 };
+        if pages_without_new >= threshold {
+            debug_println!("No new URLs found in {} consecutive pages, stopping", threshold);
+            break;
+        }
+        if page >= total_pages {
+            break;
+        }
+
+        page += 1;
+        context.wait_for_domain("vol.at");
+        let (next_links, next_total) = scrape_index_page_with_total(context, &topic_page_url(INDEX_URL, page))?;
+        if next_links.is_empty() {
+            break;
+        }
+        links = next_links;
+        if let Some(total) = next_total {
+            total_pages = index_page_count(total, per_page).min(max_pages);
+        }
     }
 
-    debug_println!("Found {} property links on page", links.len());
+    if let Some(tui) = tui.as_mut() {
+        tui.finish_gathering(all_urls.len())?;
+    }
+
+    Ok(all_urls)
+}
 
+/// RSS feed of the "Grund und Boden" topic, listing its latest articles
+pub const RSS_URL: &str = "https://www.vol.at/themen/grund-und-boden/rss";
+
+/// Links of all `<item>` elements of an RSS document
+///
+/// Only the tags needed are matched, which is enough for a feed and avoids
+/// pulling in an XML parser. Links may be wrapped in CDATA or contain escaped
+/// ampersands.
+pub fn parse_rss_item_links(xml: &str) -> Vec<String> {
+    static ITEM: OnceLock<regex::Regex> = OnceLock::new();
+    static LINK: OnceLock<regex::Regex> = OnceLock::new();
+    let item = ITEM.get_or_init(|| regex::Regex::new(r"(?s)<item\b[^>]*>(.*?)</item>").unwrap());
+    let link = LINK.get_or_init(|| {
+        regex::Regex::new(r"(?s)<link\b[^>]*>\s*(?:<!\[CDATA\[)?(.*?)(?:\]\]>)?\s*</link>").unwrap()
+    });
+    item.captures_iter(xml)
+        .filter_map(|item_captures| {
+            link.captures(&item_captures[1]).map(|link_captures| link_captures[1].trim().replace("&amp;", "&"))
+        })
+        .filter(|url| !url.is_empty())
+        .collect()
+}
+
+/// Fetch an RSS feed and return the links of its items
+pub fn fetch_rss_urls(context: &ScrapingContext, feed_url: &str) -> Result<Vec<String>> {
+    debug_println!("Fetching RSS feed: {}", feed_url);
+    let fetch_trace = Tracer::new("fetch_rss_feed", 5000);
+    let xml = context
+        .http_client_builder()
+        .build()?
+        .get(feed_url)
+        .header("Accept", "application/rss+xml, application/xml")
+        .send_counted(context.performance())
+        .context("Failed to fetch RSS feed")?
+        .error_for_status()
+        .context("RSS feed request failed")?
+        .text()
+        .context("Failed to get RSS feed text")?;
+    drop(fetch_trace);
+
+    let links = parse_rss_item_links(&xml);
+    debug_println!("Found {} links in RSS feed", links.len());
     Ok(links)
 }
 
+/// Property article URLs from the vol.at RSS feed that are not in `existing_urls`
+///
+/// The feed only holds the latest articles, so this is a quick way to pick
+/// up what was published since the last run, not to backfill older ones.
+pub fn new_listings_since(context: &ScrapingContext, existing_urls: &HashSet<String>) -> Result<Vec<String>> {
+    let mut seen_urls = HashSet::new();
+    Ok(fetch_rss_urls(context, RSS_URL)?
+        .into_iter()
+        .map(|url| sanitize_url(&url))
+        .filter(|url| is_property_article_url(url) && !existing_urls.contains(url))
+        .filter(|url| seen_urls.insert(url.clone()))
+        .collect())
+}
+
+/// Markers of vol.at articles that show only a teaser to non-subscribers
+///
+/// `isAccessibleForFree` and `data-paywall` are no such markers, they are also
+/// set on Plus articles that subscribers read in full.
+const PAYWALL_MARKERS: [&str; 2] = ["paywall-teaser", "weiterlesen mit vol.at plus"];
+
+/// Whether an article page is cut off by the subscriber paywall
+pub fn detect_paywall(html: &str) -> bool {
+    let lower = html.to_lowercase();
+    PAYWALL_MARKERS.iter().any(|marker| lower.contains(marker))
+}
+
+/// Whether the article is marked as subscriber content, whether or not it was cut off
+fn is_plus_article(html: &str) -> bool {
+    let lower = html.to_lowercase();
+    lower.contains("\"isaccessibleforfree\":false") || lower.contains("\"isaccessibleforfree\": false")
+}
+
+/// Parse an article, failing with `ScrapeError::Paywall` if it is cut off
+///
+/// A Plus article is only considered cut off if it shows the teaser or its
+/// data can't be extracted.
+fn parse_article(html: &str, url: &str, listing_type: ListingType, with_cookies: bool) -> Result<Property> {
+    let paywall = || ScrapeError::Paywall { url: url.to_string(), with_cookies }.into();
+    if detect_paywall(html) {
+        return Err(paywall());
+    }
+    parse_property_html(html, url, listing_type).map_err(|e| if is_plus_article(html) { paywall() } else { e })
+}
+
+/// Scrape an article with the next user agent and the HTTP headers of `context`
 pub fn scrape_property_page(
+    context: &mut ScrapingContext,
     url: &str,
     cookies: Option<&str>,
     listing_type: ListingType,
 ) -> Result<Property> {
+    scrape_property_page_if_changed(context, url, cookies, listing_type, None)
+        .map(|property| property.expect("no fingerprint to match"))
+}
+
+/// Like `scrape_property_page`, but None without parsing if the page still has `known_fingerprint`
+///
+/// Articles are fetched without cookies first and only fetched again with
+/// `cookies` if they are behind the paywall.
+pub fn scrape_property_page_if_changed(
+    context: &mut ScrapingContext,
+    url: &str,
+    cookies: Option<&str>,
+    listing_type: ListingType,
+    known_fingerprint: Option<&str>,
+) -> Result<Option<Property>> {
+    let user_agent = &context.next_user_agent().to_string();
+    let mut html = fetch_property_page(context, url, None, user_agent)?;
+    if known_fingerprint == Some(utils::page_fingerprint(&html).as_str()) {
+        debug_println!("Page unchanged since the last scrape: {}", url);
+        return Ok(None);
+    }
+    let mut parsed = parse_article(&html, url, listing_type.clone(), false);
+    if let (Err(e), Some(cookies)) = (&parsed, cookies) {
+        if ScrapeError::is_paywall(e) {
+            debug_println!("Article is behind the paywall, fetching it again with cookies: {}", url);
+            html = fetch_property_page(context, url, Some(cookies), user_agent)?;
+            parsed = parse_article(&html, url, listing_type, true);
+        }
+    }
+    let mut property = parsed?;
+    property.page_fingerprint = Some(utils::page_fingerprint(&html));
+    Ok(Some(property))
+}
+
+/// Fetch an article page, failing on CAPTCHAs
+fn fetch_property_page(context: &ScrapingContext, url: &str, cookies: Option<&str>, user_agent: &str) -> Result<String> {
     debug_println!("Scraping property page: {}", url);
 
     // Build request with optional cookies
-    let mut request = reqwest::blocking::Client::new()
+    let mut request = context
+        .http_client_builder()
+        .build()?
         .get(url)
-        .header("User-Agent", get_random_user_agent());
+        .header("User-Agent", user_agent);
 
     if let Some(cookie_str) = cookies {
         debug_println!("Using cookies: {}", cookie_str);
@@ -243,12 +570,18 @@ pub fn scrape_property_page(
     }
 
     // Fetch the property page
-    let response = match request.send() {
+    let fetch_trace = Tracer::new("fetch_property_page", 5000);
+    let response = match request.send_counted(context.performance()) {
         Ok(resp) => {
             debug_println!("Response status: {}", resp.status());
 
-            if !resp.status().is_success() {
-                return Err(anyhow::anyhow!("HTTP error status: {}", resp.status()));
+            let status = resp.status();
+            if !status.is_success() {
+                // A 403 is only a CAPTCHA if it serves a challenge page, otherwise a plain error
+                if status == reqwest::StatusCode::FORBIDDEN && resp.text().is_ok_and(|body| utils::detect_captcha(&body)) {
+                    return Err(ScrapeError::Captcha { url: url.to_string() }.into());
+                }
+                return Err(anyhow::anyhow!("HTTP error status: {}", status));
             }
             resp
         }
@@ -271,9 +604,20 @@ pub fn scrape_property_page(
             return Err(anyhow::anyhow!("Failed to get response text: {}", e));
         }
     };
+    drop(fetch_trace);
 
+    if utils::detect_captcha(&html) {
+        return Err(ScrapeError::Captcha { url: url.to_string() }.into());
+    }
+    Ok(html)
+}
+
+/// Extract a property from an article page, from its embedded JSON if possible
+fn parse_property_html(html: &str, url: &str, listing_type: ListingType) -> Result<Property> {
     // Parse the HTML
-    let document = Html::parse_document(&html);
+    let parse_trace = Tracer::new("parse_property_page", 1000);
+    let document = Html::parse_document(html);
+    drop(parse_trace);
 
     // Try to extract data from embedded JavaScript
     let script_selector = Selector::parse("#newExternalPostDataNode").unwrap();
@@ -282,13 +626,36 @@ pub fn scrape_property_page(
         let json_str = script.inner_html();
 
         // Parse the JSON content
+        let json_trace = Tracer::new("parse_property_json", 1000);
         let json: Value = serde_json::from_str(&json_str)
             .context("Failed to parse JSON data from externalPostDataNode")?;
+        drop(json_trace);
 
         // Extract property data from the JSON
+        if !is_sold_property_article(&json) {
+            return Err(anyhow::anyhow!("Not a sold property article: {}", url));
+        }
         return extract_property_from_json(json, url, &listing_type);
     }
 
+    // First fallback: post data embedded under a different script tag ID
+    if let Some(json) = utils::extract_all_json_scripts(&document)
+        .into_iter()
+        .find(|json| json["content"]["data"]["post"]["title"].is_string())
+    {
+        debug_println!("Found post data in another JSON script tag");
+        if !is_sold_property_article(&json) {
+            return Err(anyhow::anyhow!("Not a sold property article: {}", url));
+        }
+        return extract_property_from_json(json, url, &listing_type);
+    }
+
+    // Second fallback: Open Graph meta tags usually carry the headline as well
+    match extract_from_open_graph(&document, url, &listing_type) {
+        Ok(property) => return Ok(property),
+        Err(e) => debug_println!("Open Graph extraction failed: {}", e),
+    }
+
     // Fallback to traditional HTML parsing if JavaScript data not found
     debug_println!("JavaScript data not found, falling back to HTML parsing");
 
@@ -332,7 +699,7 @@ pub fn scrape_property_page(
     let location = parser::extract_location(&headline)?;
 
     // Extract property type using classification
-    let property_type = PropertyType::from_string(&headline);
+    let property_type = PropertyType::from_title_and_url(&headline, url);
 
     debug_println!(
         "Extracted data: price={}, location={}, type={}",
@@ -346,13 +713,95 @@ pub fn scrape_property_page(
     
     Ok(Property {
         url: sanitize_url(url),
+        internal_id: None,
+        page_fingerprint: None,
+        completeness_score: None,
         name: headline,
-        price: price.to_string(),
+        price: Price::from_str(&price.to_string()),
+        location,
+        property_type,
+        listing_type: listing_type.clone(),
+        unavailable_reason: None,
+        date: None,
+        coordinates: None,
+        quality_score: None,
+        plot_polygon: None,
+        description: None,
+        features: Vec::new(),
+        driving_distance_km: None,
+        rooms: None,
+        floor_level: None,
+        construction_year: None,
+        agency_name: None,
+        agency_phone: None,
+        address: None,
+        size_living: None,
+        size_ground: None,
+        first_seen: Some(now),
+        last_seen: Some(now),
+    })
+}
+
+/// Extract property data from Open Graph meta tags (`og:title`, `og:description`)
+///
+/// Used for articles without the `#newExternalPostDataNode` script tag. Price and
+/// location are parsed from the title, falling back to the description.
+fn extract_from_open_graph(
+    document: &Html,
+    url: &str,
+    listing_type: &ListingType,
+) -> Result<Property> {
+    let meta_content = |property: &str| -> Option<String> {
+        let selector = Selector::parse(&format!("meta[property='{}']", property)).ok()?;
+        document
+            .select(&selector)
+            .next()
+            .and_then(|el| el.value().attr("content"))
+            .map(|content| content.trim().to_string())
+            .filter(|content| !content.is_empty())
+    };
+
+    let title = meta_content("og:title").context("og:title meta tag not found")?;
+    let description = meta_content("og:description").unwrap_or_default();
+    debug_println!("Open Graph title: {}, description: {}", title, description);
+
+    let price = parser::extract_price(&title).or_else(|_| parser::extract_price(&description))?;
+    let location =
+        parser::extract_location(&title).or_else(|_| parser::extract_location(&description))?;
+    let property_type = PropertyType::from_title_and_url(&title, url);
+
+    debug_println!(
+        "Extracted data from Open Graph: price={}, location={}, type={}",
+        price,
+        location,
+        property_type
+    );
+
+    let now = chrono::Utc::now().naive_utc().date();
+
+    Ok(Property {
+        url: sanitize_url(url),
+        internal_id: None,
+        page_fingerprint: None,
+        completeness_score: None,
+        name: title,
+        price: Price::from_str(&price.to_string()),
         location,
         property_type,
         listing_type: listing_type.clone(),
+        unavailable_reason: None,
         date: None,
         coordinates: None,
+        quality_score: None,
+        plot_polygon: None,
+        description: None,
+        features: Vec::new(),
+        driving_distance_km: None,
+        rooms: None,
+        floor_level: None,
+        construction_year: None,
+        agency_name: None,
+        agency_phone: None,
         address: None,
         size_living: None,
         size_ground: None,
@@ -361,7 +810,51 @@ pub fn scrape_property_page(
     })
 }
 
-fn extract_property_from_json(
+/// Parsed data of the `russmedia/grund-und-boden` block of a post, if it has one
+fn grund_und_boden_data(post: &Value) -> Option<Value> {
+    post["blocks"]
+        .as_array()?
+        .iter()
+        .filter(|block| block["ot"] == "russmedia/grund-und-boden")
+        .filter_map(|block| {
+            block["a"]
+                .as_array()
+                .and_then(|attrs| attrs.iter().find(|attr| attr["key"] == "data"))
+                .and_then(|attr| attr["value"].as_str())
+        })
+        .find_map(|data_str| serde_json::from_str::<Value>(data_str).ok())
+}
+
+/// Topic slugs of vol.at articles that report a property transaction
+const PROPERTY_TOPICS: [&str; 2] = ["grund-und-boden", "garagen-und-stellplaetze"];
+
+/// Whether the post data belongs to a sold-property article rather than a news story
+///
+/// Requires a `russmedia/grund-und-boden` block with a positive price and a
+/// transaction date. If the post lists its categories, one of them must be a
+/// property topic; news articles linked from the topic page carry their own.
+pub fn is_sold_property_article(json: &Value) -> bool {
+    let post = &json["content"]["data"]["post"];
+    let Some(data) = grund_und_boden_data(post) else {
+        return false;
+    };
+    let has_price = data["price"].as_f64().is_some_and(|price| price > 0.0);
+    let has_date = data["transactionDate"].as_str().is_some_and(|date| !date.trim().is_empty());
+    let in_property_topic = match post["categories"].as_array() {
+        Some(categories) if !categories.is_empty() => categories
+            .iter()
+            .filter_map(|category| category["slug"].as_str())
+            .any(|slug| PROPERTY_TOPICS.contains(&slug)),
+        _ => true,
+    };
+    has_price && has_date && in_property_topic
+}
+
+/// Extract a property from the post data of a vol.at article
+///
+/// The title carries location and type; price, date, coordinates and sizes
+/// come from the `russmedia/grund-und-boden` block if the article has one.
+pub fn extract_property_from_json(
     json: Value,
     url: &str,
     listing_type: &ListingType,
@@ -380,7 +873,7 @@ fn extract_property_from_json(
     let location = parser::extract_location(title)?;
 
     // Extract property type using classification
-    let property_type = PropertyType::from_string(title);
+    let property_type = PropertyType::from_title_and_url(title, url);
 
     // Try to extract the transaction date and coordinates from the structured data
     let mut price = None;
@@ -389,69 +882,84 @@ fn extract_property_from_json(
     let mut address = None;
     let mut size_living = None;
     let mut size_ground = None;
+    let mut rooms = None;
+    let mut floor_level = None;
+    let mut construction_year = None;
 
     // Look for the GrundUndBoden block which contains structured data
-    if let Some(blocks) = post["blocks"].as_array() {
-        for block in blocks {
-            if block["ot"] == "russmedia/grund-und-boden" {
-                if let Some(data_str) = block["a"]
-                    .as_array()
-                    .and_then(|attrs| attrs.iter().find(|attr| attr["key"] == "data"))
-                    .and_then(|attr| attr["value"].as_str())
-                {
-                    // Parse the data attribute which is a JSON string
-                    if let Ok(data_json) = serde_json::from_str::<Value>(data_str) {
-                        debug_println!("Found grund-und-boden data: {}", data_str);
-
-                        // Extract price
-                        match &data_json["price"] {
-                            Value::Number(p) => {
-                                price = Some(p.to_string());
-                                debug_println!("Found price: {}", p);
-                            }
-                            _ => {
-                                debug_println!("Price not found in JSON data");
-                            }
-                        }
+    if let Some(data_json) = grund_und_boden_data(post) {
+        debug_println!("Found grund-und-boden data: {}", data_json);
+
+        // Extract price
+        match &data_json["price"] {
+            Value::Number(p) => {
+                price = Some(p.to_string());
+                debug_println!("Found price: {}", p);
+            }
+            _ => {
+                debug_println!("Price not found in JSON data");
+            }
+        }
 
-                        // Extract transaction date
-                        if let Some(date_str) = data_json["transactionDate"].as_str() {
-                            debug_println!("Found transaction date: {}", date_str);
-                            // Parse the date in format YYYY-MM-DD
-                            if let Ok(parsed_date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                            {
-                                date = Some(parsed_date);
-                            }
-                        }
+        // Extract transaction date
+        if let Some(date_str) = data_json["transactionDate"].as_str() {
+            debug_println!("Found transaction date: {}", date_str);
+            // Parse the date in format YYYY-MM-DD
+            if let Ok(parsed_date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+            {
+                date = Some(parsed_date);
+            }
+        }
 
-                        // Extract coordinates
-                        if let Some(coords) = data_json["coords"].as_object() {
-                            if let (Some(lat), Some(lng)) =
-                                (coords["lat"].as_f64(), coords["lng"].as_f64())
-                            {
-                                coordinates = Some((lat, lng));
-                                debug_println!("Found coordinates: lat={}, lng={}", lat, lng);
-                            }
-                        }
+        // Extract coordinates
+        if let Some(coords) = data_json["coords"].as_object() {
+            if let (Some(lat), Some(lng)) =
+                (coords["lat"].as_f64(), coords["lng"].as_f64())
+            {
+                coordinates = Coordinates::new(lat, lng).ok();
+                debug_println!("Found coordinates: lat={}, lng={}", lat, lng);
+            }
+        }
 
-                        // Extract address
-                        if let Some(addr) = data_json["address"].as_str() {
-                            address = Some(addr.to_string());
-                            debug_println!("Found address: {}", addr);
-                        }
+        // Extract address
+        if let Some(addr) = data_json["address"].as_str() {
+            address = Some(addr.to_string());
+            debug_println!("Found address: {}", addr);
+        }
 
-                        // Extract living size
-                        if let Some(size) = data_json["sizeLiving"].as_str() {
-                            size_living = Some(size.to_string());
-                            debug_println!("Found living size: {}", size);
-                        }
+        // Extract living size
+        if let Some(size) = data_json["sizeLiving"].as_str() {
+            size_living = Some(size.to_string());
+            debug_println!("Found living size: {}", size);
+        }
 
-                        // Extract ground size
-                        if let Some(size) = data_json["sizeGround"].as_str() {
-                            size_ground = Some(size.to_string());
-                            debug_println!("Found ground size: {}", size);
-                        }
-                    }
+        // Extract ground size, falling back to the lot size
+        if let Some(size) = data_json["sizeGround"].as_str() {
+            size_ground = Some(size.to_string());
+            debug_println!("Found ground size: {}", size);
+        } else if let Some(size) = json_number(&data_json["lotSize"]) {
+            size_ground = Some(size.to_string());
+            debug_println!("Found lot size: {}", size);
+        }
+
+        rooms = json_number(&data_json["numberOfRooms"]);
+        floor_level = match &data_json["floorLevel"] {
+            Value::String(floor) if !floor.trim().is_empty() => Some(floor.trim().to_string()),
+            Value::Number(floor) => Some(floor.to_string()),
+            _ => None,
+        };
+        construction_year = json_number(&data_json["constructionYear"])
+            .filter(|year| (1000.0..=9999.0).contains(year))
+            .map(|year| year as u16);
+        debug_println!(
+            "Found rooms={:?}, floor={:?}, construction year={:?}",
+            rooms, floor_level, construction_year
+        );
+
+        if let Some(fields) = data_json.as_object() {
+            for (key, value) in fields {
+                if !GRUND_UND_BODEN_KEYS.contains(&key.as_str()) {
+                    debug_println!("Unmapped grund-und-boden key: {} = {}", key, value);
                 }
             }
         }
@@ -470,13 +978,27 @@ fn extract_property_from_json(
     
     Ok(Property {
         url: sanitize_url(url),
+        internal_id: None,
+        page_fingerprint: None,
+        completeness_score: None,
         name: title.to_string(),
-        price: price.unwrap_or("Unknown".to_string()),
+        price: price.map(|p| Price::from_str(&p)).unwrap_or_else(Price::unknown),
         location,
         property_type,
         listing_type: listing_type.clone(),
+        unavailable_reason: None,
         date,
         coordinates,
+        quality_score: None,
+        plot_polygon: None,
+        description: None,
+        features: Vec::new(),
+        driving_distance_km: None,
+        rooms,
+        floor_level,
+        construction_year,
+        agency_name: None,
+        agency_phone: None,
         address,
         size_living,
         size_ground,
@@ -484,3 +1006,211 @@ fn extract_property_from_json(
         last_seen: Some(now),
     })
 }
+
+/// Keys of the `russmedia/grund-und-boden` data that are mapped to `Property` fields
+const GRUND_UND_BODEN_KEYS: [&str; 10] = [
+    "price",
+    "transactionDate",
+    "coords",
+    "address",
+    "sizeLiving",
+    "sizeGround",
+    "lotSize",
+    "numberOfRooms",
+    "floorLevel",
+    "constructionYear",
+];
+
+/// Read a number that may be encoded as a JSON number or a string like "3,5"
+fn json_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().replace(',', ".").parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PropertyKind;
+
+    #[test]
+    fn test_extract_from_open_graph() {
+        let html = r#"<html><head>
+            <meta property="og:title" content="Grundstück in Feldkirch um 450.000 Euro verkauft" />
+            <meta property="og:description" content="Ein Grundstück mit 700 m² wechselte den Besitzer." />
+        </head><body></body></html>"#;
+        let document = Html::parse_document(html);
+
+        let property = extract_from_open_graph(
+            &document,
+            "https://www.vol.at/grundstueck-feldkirch/123?utm_source=test",
+            &ListingType::Sold,
+        )
+        .unwrap();
+
+        assert_eq!(property.url, "https://www.vol.at/grundstueck-feldkirch/123");
+        assert_eq!(property.name, "Grundstück in Feldkirch um 450.000 Euro verkauft");
+        assert_eq!(property.price.raw, "450000");
+        assert_eq!(property.location.municipality, "Feldkirch");
+        assert_eq!(property.property_type.kind, PropertyKind::Land);
+        assert_eq!(property.listing_type, ListingType::Sold);
+    }
+
+    #[test]
+    fn test_extract_from_open_graph_without_tags() {
+        let document = Html::parse_document("<html><head></head><body></body></html>");
+        assert!(extract_from_open_graph(&document, "https://www.vol.at/x", &ListingType::Sold).is_err());
+    }
+
+    #[test]
+    fn test_topic_hit_links() {
+        let json: Value = serde_json::from_str(r#"{
+            "total": 42,
+            "hits": [
+                {"link": "https:\/\/www.vol.at\/haus-dornbirn\/1"},
+                {"title": "No link"},
+                {"link": "https://www.vol.at/grundstueck-hard/2"}
+            ]
+        }"#).unwrap();
+        assert_eq!(topic_hit_links(&json), vec![
+            "https://www.vol.at/haus-dornbirn/1".to_string(),
+            "https://www.vol.at/grundstueck-hard/2".to_string(),
+        ]);
+        assert_eq!(topic_total(&json), Some(42));
+        assert_eq!(topic_total(&serde_json::json!({"count": 7})), Some(7));
+        assert!(topic_hit_links(&Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_detect_paywall() {
+        assert!(detect_paywall(r#"<div class="paywall-teaser">Jetzt weiterlesen mit VOL.AT Plus</div>"#));
+        assert!(!detect_paywall(r#"<script type="application/ld+json">{"isAccessibleForFree":false}</script>"#));
+        assert!(!detect_paywall(r#"<article data-paywall="plus"><h1>Haus verkauft</h1></article>"#));
+        assert!(!detect_paywall("<h1>Haus in Dornbirn um 800.000 Euro verkauft</h1>"));
+    }
+
+    #[test]
+    fn test_parse_article_behind_paywall() {
+        let url = "https://www.vol.at/grundstueck-feldkirch/123";
+        let plus = r#"<script type="application/ld+json">{"isAccessibleForFree":false}</script>"#;
+        let full = format!(
+            r#"<html><head>{}<meta property="og:title" content="Grundstück in Feldkirch um 450.000 Euro verkauft" />
+            </head><body data-paywall="plus"></body></html>"#,
+            plus
+        );
+
+        // A subscriber sees the whole Plus article
+        let property = parse_article(&full, url, ListingType::Sold, true).unwrap();
+        assert_eq!(property.price.raw, "450000");
+
+        let empty = format!("<html><head>{}</head><body></body></html>", plus);
+        let error = parse_article(&empty, url, ListingType::Sold, false).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ScrapeError>(),
+            Some(&ScrapeError::Paywall { url: url.to_string(), with_cookies: false })
+        );
+
+        let teaser = r#"<div class="paywall-teaser">Jetzt weiterlesen mit VOL.AT Plus</div>"#;
+        assert!(ScrapeError::is_paywall(&parse_article(teaser, url, ListingType::Sold, true).unwrap_err()));
+        assert!(!ScrapeError::is_paywall(&parse_article("<html></html>", url, ListingType::Sold, false).unwrap_err()));
+    }
+
+    #[test]
+    fn test_is_property_article_url() {
+        assert!(is_property_article_url("https://www.vol.at/grundstueck-feldkirch/123"));
+        assert!(is_property_article_url("https://www.vol.at/einfamilienhaus-in-lustenau-verkauft/8712345?utm=x"));
+        assert!(!is_property_article_url("https://www.vol.at/fussball-altach-gewinnt/8712345"));
+        assert!(!is_property_article_url("https://www.vol.at/themen/grund-und-boden"));
+        assert!(!is_property_article_url("https://www.laendleimmo.at/haus-dornbirn/123"));
+    }
+
+    #[test]
+    fn test_extract_property_from_json_reads_rich_fields() {
+        let data = serde_json::json!({
+            "price": 420000,
+            "transactionDate": "2025-03-14",
+            "sizeLiving": "85",
+            "lotSize": "420",
+            "numberOfRooms": "3,5",
+            "floorLevel": 2,
+            "constructionYear": 1998,
+            "parkingSpaces": 1
+        });
+        let json = serde_json::json!({
+            "content": { "data": { "post": {
+                "title": "Wohnung in Hard am Bodensee um 420.000 Euro verkauft",
+                "blocks": [{
+                    "ot": "russmedia/grund-und-boden",
+                    "a": [{ "key": "data", "value": data.to_string() }]
+                }]
+            }}}
+        });
+
+        let property = extract_property_from_json(json, "https://www.vol.at/wohnung-hard/1", &ListingType::Sold).unwrap();
+
+        assert_eq!(property.location.municipality, "Hard am Bodensee");
+        assert_eq!(property.rooms, Some(3.5));
+        assert_eq!(property.floor_level.as_deref(), Some("2"));
+        assert_eq!(property.construction_year, Some(1998));
+        assert_eq!(property.size_ground.as_deref(), Some("420"));
+        assert_eq!(property.date, NaiveDate::from_ymd_opt(2025, 3, 14));
+    }
+
+    #[test]
+    fn test_is_sold_property_article() {
+        let article = |data: serde_json::Value, slug: &str| {
+            serde_json::json!({
+                "content": { "data": { "post": {
+                    "title": "Haus in Dornbirn um 650.000 Euro verkauft",
+                    "categories": [{ "slug": slug }],
+                    "blocks": [{
+                        "ot": "russmedia/grund-und-boden",
+                        "a": [{ "key": "data", "value": data.to_string() }]
+                    }]
+                }}}
+            })
+        };
+        let data = serde_json::json!({ "price": 650000, "transactionDate": "2025-01-20" });
+
+        assert!(is_sold_property_article(&article(data.clone(), "grund-und-boden")));
+        assert!(is_sold_property_article(&article(data.clone(), "garagen-und-stellplaetze")));
+        assert!(!is_sold_property_article(&article(data, "vorarlberg-chronik")));
+        assert!(!is_sold_property_article(&article(serde_json::json!({ "transactionDate": "2025-01-20" }), "grund-und-boden")));
+        assert!(!is_sold_property_article(&article(serde_json::json!({ "price": 650000 }), "grund-und-boden")));
+        assert!(!is_sold_property_article(&serde_json::json!({
+            "content": { "data": { "post": { "title": "Neue Wohnanlage in Bregenz", "blocks": [] } } }
+        })));
+    }
+
+    #[test]
+    fn test_parse_rss_item_links() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"><channel>
+  <title>Grund und Boden</title>
+  <link>https://www.vol.at/themen/grund-und-boden</link>
+  <item>
+    <title>Wohnung in Dornbirn verkauft</title>
+    <link>https://www.vol.at/wohnung-in-dornbirn/8012345</link>
+  </item>
+  <item><title>Haus</title><link><![CDATA[https://www.vol.at/haus-in-bregenz/8012346?utm_source=rss&amp;utm_medium=feed]]></link></item>
+  <item><title>Ohne Link</title></item>
+</channel></rss>"#;
+        assert_eq!(
+            parse_rss_item_links(xml),
+            vec![
+                "https://www.vol.at/wohnung-in-dornbirn/8012345".to_string(),
+                "https://www.vol.at/haus-in-bregenz/8012346?utm_source=rss&utm_medium=feed".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_page_count() {
+        assert_eq!(index_page_count(1234, 20), 62);
+        assert_eq!(index_page_count(40, 20), 2);
+        assert_eq!(index_page_count(0, 20), 0);
+        assert_eq!(index_page_count(40, 0), 0);
+    }
+}
@@ -1,9 +1,13 @@
-use crate::common_scraper::PlatformScraper;
-use crate::models::{ListingType, Property};
+use crate::common_scraper::{PlatformScraper, ScrapingContext, ScrapingOptions};
+use crate::models::{ListingType, Property, PropertyKind};
 use crate::tui::ScraperTUI;
 use crate::{laendleimmo_scraper, scraper};
+use crate::debug_println;
 use anyhow::Result;
 
+/// Consecutive listing pages without new URLs after which new mode stops
+const NEW_URLS_THRESHOLD: usize = 5;
+
 pub struct VolScraper;
 
 impl PlatformScraper for VolScraper {
@@ -13,24 +17,110 @@ impl PlatformScraper for VolScraper {
 
     fn scrape_listings(
         &self,
-        max_pages: Option<usize>,
+        options: &ScrapingOptions,
+        context: &mut ScrapingContext,
         tui: Option<&mut ScraperTUI>,
         existing_urls: &std::collections::HashSet<String>,
     ) -> Result<Vec<String>> {
-        scraper::scrape_all_index_pages(max_pages.unwrap_or(1), tui, existing_urls)
+        scraper::scrape_all_index_pages(context, options.max_pages.unwrap_or(1), tui, existing_urls)
     }
 
     fn scrape_new_urls(
         &self,
+        options: &ScrapingOptions,
+        context: &mut ScrapingContext,
         tui: Option<&mut ScraperTUI>,
         existing_urls: &std::collections::HashSet<String>,
     ) -> Result<Vec<String>> {
-        scraper::scrape_new_urls_until_no_new_found(tui, existing_urls)
+        if options.use_rss {
+            match scraper::new_listings_since(context, existing_urls) {
+                Ok(urls) => return Ok(urls),
+                Err(e) => debug_println!("vol.at RSS feed failed, falling back to topic pages: {:#}", e),
+            }
+        }
+        scraper::scrape_new_urls_from_vol_json(context, existing_urls, NEW_URLS_THRESHOLD, tui)
     }
 
-    fn scrape_property(&self, url: &str, cookies: Option<&str>) -> Result<Property> {
+    fn scrape_property(&self, context: &mut ScrapingContext, url: &str, cookies: Option<&str>) -> Result<Property> {
         check_url(self, url)?;
-        scraper::scrape_property_page(url, cookies, ListingType::Sold)
+        scraper::scrape_property_page(context, url, cookies, ListingType::Sold)
+    }
+
+    fn scrape_property_if_changed(
+        &self,
+        context: &mut ScrapingContext,
+        url: &str,
+        cookies: Option<&str>,
+        stored: Option<&Property>,
+    ) -> Result<Option<Property>> {
+        check_url(self, url)?;
+        let known_fingerprint = stored.and_then(|p| p.page_fingerprint.as_deref());
+        scraper::scrape_property_page_if_changed(context, url, cookies, ListingType::Sold, known_fingerprint)
+    }
+
+    fn is_property_url(&self, url: &str) -> bool {
+        scraper::is_property_article_url(url)
+    }
+
+    fn health_check(&self, context: &mut ScrapingContext) -> Result<()> {
+        scraper::check_topic_page(context, scraper::INDEX_URL)
+    }
+}
+
+/// Sold garages and parking spaces from the vol.at "Garagen & Stellplätze" topic
+pub struct VolGarageScraper;
+
+impl PlatformScraper for VolGarageScraper {
+    fn base_url(&self) -> &str {
+        "vol.at"
+    }
+
+    fn scrape_listings(
+        &self,
+        options: &ScrapingOptions,
+        context: &mut ScrapingContext,
+        tui: Option<&mut ScraperTUI>,
+        existing_urls: &std::collections::HashSet<String>,
+    ) -> Result<Vec<String>> {
+        scraper::scrape_all_topic_index_pages(context, scraper::GARAGE_INDEX_URL, options.max_pages.unwrap_or(1), tui, existing_urls)
+    }
+
+    fn scrape_new_urls(
+        &self,
+        _options: &ScrapingOptions,
+        context: &mut ScrapingContext,
+        tui: Option<&mut ScraperTUI>,
+        existing_urls: &std::collections::HashSet<String>,
+    ) -> Result<Vec<String>> {
+        scraper::scrape_new_topic_urls_until_no_new_found(context, scraper::GARAGE_INDEX_URL, tui, existing_urls)
+    }
+
+    fn scrape_property(&self, context: &mut ScrapingContext, url: &str, cookies: Option<&str>) -> Result<Property> {
+        check_url(self, url)?;
+        let mut property = scraper::scrape_property_page(context, url, cookies, ListingType::Sold)?;
+        // Everything on the garage topic is a garage, even if the headline doesn't say so
+        property.property_type = PropertyKind::Garage.into();
+        Ok(property)
+    }
+
+    fn scrape_property_if_changed(
+        &self,
+        context: &mut ScrapingContext,
+        url: &str,
+        cookies: Option<&str>,
+        stored: Option<&Property>,
+    ) -> Result<Option<Property>> {
+        check_url(self, url)?;
+        let known_fingerprint = stored.and_then(|p| p.page_fingerprint.as_deref());
+        let property = scraper::scrape_property_page_if_changed(context, url, cookies, ListingType::Sold, known_fingerprint)?;
+        Ok(property.map(|mut property| {
+            property.property_type = PropertyKind::Garage.into();
+            property
+        }))
+    }
+
+    fn health_check(&self, context: &mut ScrapingContext) -> Result<()> {
+        scraper::check_topic_page(context, scraper::GARAGE_INDEX_URL)
     }
 }
 
@@ -43,24 +133,62 @@ impl PlatformScraper for LaendleimmoScraper {
 
     fn scrape_listings(
         &self,
-        max_pages: Option<usize>,
+        options: &ScrapingOptions,
+        context: &mut ScrapingContext,
         tui: Option<&mut ScraperTUI>,
         existing_urls: &std::collections::HashSet<String>,
     ) -> Result<Vec<String>> {
-        laendleimmo_scraper::scrape_all_listing_pages(max_pages.unwrap_or(1), tui, existing_urls)
+        let filters = &options.listing_filters;
+        laendleimmo_scraper::scrape_all_listing_pages(context, options.max_pages.unwrap_or(1), filters, tui, existing_urls)
     }
 
     fn scrape_new_urls(
         &self,
+        options: &ScrapingOptions,
+        context: &mut ScrapingContext,
         tui: Option<&mut ScraperTUI>,
         existing_urls: &std::collections::HashSet<String>,
     ) -> Result<Vec<String>> {
-        laendleimmo_scraper::scrape_new_urls_until_no_new_found(tui, existing_urls)
+        laendleimmo_scraper::scrape_new_urls_until_no_new_found(context, &options.listing_filters, tui, existing_urls)
     }
 
-    fn scrape_property(&self, url: &str, _cookies: Option<&str>) -> Result<Property> {
+    fn scrape_property(&self, context: &mut ScrapingContext, url: &str, _cookies: Option<&str>) -> Result<Property> {
         check_url(self, url)?;
-        laendleimmo_scraper::scrape_property_page(url)
+        laendleimmo_scraper::scrape_property_page(context, url)
+    }
+
+    fn scrape_property_if_changed(
+        &self,
+        context: &mut ScrapingContext,
+        url: &str,
+        _cookies: Option<&str>,
+        stored: Option<&Property>,
+    ) -> Result<Option<Property>> {
+        check_url(self, url)?;
+        laendleimmo_scraper::scrape_property_page_if_changed(context, url, stored)
+    }
+
+    fn url_matches_regions(&self, url: &str, regions: &[String]) -> bool {
+        laendleimmo_scraper::url_matches_location(url, regions)
+    }
+
+    fn is_property_url(&self, url: &str) -> bool {
+        laendleimmo_scraper::is_property_url(url)
+    }
+
+    fn health_check(&self, context: &mut ScrapingContext) -> Result<()> {
+        laendleimmo_scraper::check_listing_page(context)
+    }
+}
+
+/// Pick the scraper responsible for a URL by its domain
+pub fn scraper_for_url(url: &str) -> Option<&'static dyn PlatformScraper> {
+    if url.contains("vol.at") {
+        Some(&VolScraper)
+    } else if url.contains("laendleimmo.at") {
+        Some(&LaendleimmoScraper)
+    } else {
+        None
     }
 }
 
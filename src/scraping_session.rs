@@ -0,0 +1,141 @@
+use crate::common_scraper::ScrapingRun;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Outcome of one scraper run within a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub timestamp: DateTime<Utc>,
+    pub scraper_name: String,
+    /// Properties that were not stored before
+    pub properties_added: usize,
+    /// Stored properties that were scraped again
+    pub properties_updated: usize,
+    pub properties_failed: usize,
+    pub duration_secs: f64,
+}
+
+impl RunRecord {
+    /// Record a finished scraper run at the current time
+    pub fn from_run(run: &ScrapingRun) -> Self {
+        RunRecord {
+            timestamp: Utc::now(),
+            scraper_name: run.scraper_name.clone(),
+            properties_added: run.new_scraped - run.updated,
+            properties_updated: run.updated,
+            properties_failed: run.failed,
+            duration_secs: run.duration.as_secs_f64(),
+        }
+    }
+}
+
+/// Related scraping runs grouped together, e.g. everything scraped this week
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub session_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub runs: Vec<RunRecord>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session {
+            session_id: Uuid::new_v4(),
+            started_at: Utc::now(),
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path).with_context(|| format!("Failed to read session file: {}", path))?;
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse session file: {}", path))
+    }
+
+    /// Load the session from a file, or start a new one if the file does not exist yet
+    pub fn load_or_new(path: &str) -> Result<Self> {
+        if Path::new(path).exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::new())
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write session file: {}", path))
+    }
+
+    /// Summary of all runs, one line per run and the totals
+    pub fn report(&self) -> String {
+        let mut lines = vec![format!(
+            "Session {} started {}, {} runs",
+            self.session_id,
+            self.started_at.format("%Y-%m-%d %H:%M"),
+            self.runs.len()
+        )];
+        for run in &self.runs {
+            lines.push(format!(
+                "  {}  {:<25} +{} added, {} updated, {} failed in {:.0}s",
+                run.timestamp.format("%Y-%m-%d %H:%M"),
+                run.scraper_name,
+                run.properties_added,
+                run.properties_updated,
+                run.properties_failed,
+                run.duration_secs
+            ));
+        }
+        let total = |f: fn(&RunRecord) -> usize| self.runs.iter().map(f).sum::<usize>();
+        lines.push(format!(
+            "Total: {} added, {} updated, {} failed in {:.0}s",
+            total(|r| r.properties_added),
+            total(|r| r.properties_updated),
+            total(|r| r.properties_failed),
+            self.runs.iter().map(|r| r.duration_secs).sum::<f64>()
+        ));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_session_save_load_and_report() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-session-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+
+        let mut session = Session::load_or_new(path).unwrap();
+        for (name, new_scraped, updated) in [("www.vol.at", 5, 2), ("www.laendleimmo.at", 3, 0)] {
+            session.runs.push(RunRecord::from_run(&ScrapingRun {
+                scraper_name: name.to_string(),
+                new_scraped,
+                updated,
+                failed: 1,
+                known: 10,
+                duration: Duration::from_secs(30),
+            }));
+        }
+        session.save(path).unwrap();
+
+        let loaded = Session::load_or_new(path).unwrap();
+        fs::remove_file(path).unwrap();
+        assert_eq!(loaded, session);
+        assert_eq!(loaded.runs[0].properties_added, 3);
+        let report = loaded.report();
+        assert!(report.contains("2 runs"), "{}", report);
+        assert!(report.contains("Total: 6 added, 2 updated, 2 failed in 60s"), "{}", report);
+    }
+}
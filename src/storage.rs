@@ -0,0 +1,358 @@
+use anyhow::{Context, Result};
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{params_from_iter, Connection};
+use serde_json::Map;
+use std::path::Path;
+
+use crate::debug_println;
+use crate::models::Property;
+use crate::utils::{
+    detect_output_format, load_properties_from_csv, load_properties_from_geojson, sanitize_url, save_properties_to_csv,
+    save_properties_to_csv_atomic, save_properties_to_geojson, OutputFormat,
+};
+
+/// Columns of the properties table, in the order `Property` is serialized
+const COLUMNS: [&str; 30] = [
+    "url",
+    "name",
+    "price",
+    "location_municipality",
+    "location_district",
+    "location_region",
+    "property_type",
+    "listing_type",
+    "date",
+    "coordinates",
+    "address",
+    "size_living",
+    "size_ground",
+    "first_seen",
+    "last_seen",
+    "quality_score",
+    "description",
+    "features",
+    "driving_distance_km",
+    "rooms",
+    "floor_level",
+    "construction_year",
+    "agency_name",
+    "agency_phone",
+    "unavailable_reason",
+    "internal_id",
+    "page_fingerprint",
+    "plot_polygon",
+    "completeness_score",
+    "property_type_confidence",
+];
+
+/// A place properties are loaded from and saved to
+pub trait PropertyStorage {
+    fn load(&self) -> Result<Vec<Property>>;
+    fn save(&self, properties: &[Property]) -> Result<()>;
+}
+
+/// How CSV files are read and written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsvOptions {
+    /// Skip malformed rows with a warning instead of failing
+    pub lenient: bool,
+    /// Write to a temporary file and rename it over the original
+    pub atomic_write: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self { lenient: false, atomic_write: true }
+    }
+}
+
+/// The default CSV file backend
+pub struct CsvStorage {
+    path: String,
+    options: CsvOptions,
+}
+
+impl CsvStorage {
+    pub fn new(path: &str, options: CsvOptions) -> Self {
+        Self { path: path.to_string(), options }
+    }
+}
+
+impl PropertyStorage for CsvStorage {
+    fn load(&self) -> Result<Vec<Property>> {
+        load_properties_from_csv(&self.path, self.options.lenient)
+    }
+
+    fn save(&self, properties: &[Property]) -> Result<()> {
+        if self.options.atomic_write {
+            save_properties_to_csv_atomic(properties, &self.path)
+        } else {
+            save_properties_to_csv(properties, &self.path)
+        }
+    }
+}
+
+/// GeoJSON FeatureCollection backend, e.g. for files edited in a GIS tool
+pub struct GeoJsonStorage {
+    path: String,
+}
+
+impl GeoJsonStorage {
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string() }
+    }
+}
+
+impl PropertyStorage for GeoJsonStorage {
+    fn load(&self) -> Result<Vec<Property>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(Vec::new());
+        }
+        load_properties_from_geojson(&self.path)
+    }
+
+    fn save(&self, properties: &[Property]) -> Result<()> {
+        save_properties_to_geojson(properties, &self.path)
+    }
+}
+
+/// SQLite database backend with a single `properties` table
+///
+/// Columns mirror the CSV format so both backends can be converted losslessly.
+pub struct SqliteStorage {
+    path: String,
+}
+
+impl SqliteStorage {
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string() }
+    }
+
+    fn open(&self) -> Result<Connection> {
+        let conn = Connection::open(&self.path)
+            .with_context(|| format!("Failed to open SQLite database: {}", self.path))?;
+        let columns = COLUMNS
+            .iter()
+            .map(|column| if *column == "url" { "url TEXT PRIMARY KEY".to_string() } else { column.to_string() })
+            .collect::<Vec<_>>()
+            .join(", ");
+        conn.execute(&format!("CREATE TABLE IF NOT EXISTS properties ({})", columns), [])?;
+
+        // Add columns introduced after the database was created
+        let existing: Vec<String> = conn
+            .prepare("SELECT name FROM pragma_table_info('properties')")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for column in COLUMNS.iter().filter(|c| !existing.iter().any(|e| e == *c)) {
+            conn.execute(&format!("ALTER TABLE properties ADD COLUMN {}", column), [])?;
+        }
+        Ok(conn)
+    }
+}
+
+impl PropertyStorage for SqliteStorage {
+    fn load(&self) -> Result<Vec<Property>> {
+        if !Path::new(&self.path).exists() {
+            debug_println!("SQLite database {} does not exist, creating a new one", self.path);
+            return Ok(Vec::new());
+        }
+
+        let conn = self.open()?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM properties ORDER BY rowid", COLUMNS.join(", ")))?;
+        let rows = stmt.query_map([], |row| {
+            let mut record = Map::new();
+            for (i, column) in COLUMNS.iter().enumerate() {
+                let value = match row.get_ref(i)? {
+                    ValueRef::Null => serde_json::Value::Null,
+                    ValueRef::Integer(n) => n.into(),
+                    ValueRef::Real(f) => f.into(),
+                    ValueRef::Text(text) | ValueRef::Blob(text) => {
+                        String::from_utf8_lossy(text).into_owned().into()
+                    }
+                };
+                record.insert(column.to_string(), value);
+            }
+            Ok(record)
+        })?;
+
+        let mut properties = Vec::new();
+        for record in rows {
+            let mut property: Property = serde_json::from_value(serde_json::Value::Object(record?))
+                .with_context(|| "Failed to deserialize property from SQLite")?;
+            property.url = sanitize_url(&property.url);
+            properties.push(property);
+        }
+
+        debug_println!("Loaded {} properties from {}", properties.len(), self.path);
+        Ok(properties)
+    }
+
+    fn save(&self, properties: &[Property]) -> Result<()> {
+        let mut conn = self.open()?;
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM properties", [])?;
+        {
+            let placeholders = vec!["?"; COLUMNS.len()].join(", ");
+            let mut stmt = tx.prepare(&format!(
+                "INSERT OR REPLACE INTO properties ({}) VALUES ({})",
+                COLUMNS.join(", "),
+                placeholders
+            ))?;
+
+            for property in properties {
+                let record = serde_json::to_value(property)?;
+                let values = COLUMNS.iter().map(|column| match &record[*column] {
+                    serde_json::Value::String(s) => Value::Text(s.clone()),
+                    serde_json::Value::Number(n) => n
+                        .as_i64()
+                        .map(Value::Integer)
+                        .or_else(|| n.as_f64().map(Value::Real))
+                        .unwrap_or(Value::Null),
+                    _ => Value::Null,
+                });
+                stmt.execute(params_from_iter(values))?;
+            }
+        }
+        tx.commit()?;
+
+        debug_println!("Saved {} properties to {}", properties.len(), self.path);
+        Ok(())
+    }
+}
+
+/// Whether a path refers to an SQLite database rather than a CSV file
+pub fn is_sqlite_path(path: &str) -> bool {
+    detect_output_format(path) == OutputFormat::Sqlite
+}
+
+/// Pick the storage backend for a path based on its file extension
+///
+/// `csv` only applies to CSV files.
+pub fn open_storage(path: &str, csv: CsvOptions) -> Result<Box<dyn PropertyStorage>> {
+    let format = detect_output_format(path);
+    debug_println!("Using {} format for {}", format, path);
+    match format {
+        OutputFormat::Csv => Ok(Box::new(CsvStorage::new(path, csv))),
+        // The JSON export can't be read back losslessly, so it can't hold the database
+        OutputFormat::Json => Err(anyhow::anyhow!(
+            "JSON is export-only, use --export-json to write {} and a .csv, .geojson or .db file as output",
+            path
+        )),
+        OutputFormat::GeoJson => Ok(Box::new(GeoJsonStorage::new(path))),
+        OutputFormat::Sqlite => Ok(Box::new(SqliteStorage::new(path))),
+        OutputFormat::Xlsx | OutputFormat::Kml => Err(anyhow::anyhow!(
+            "{} files are not supported for {}, use a .csv, .geojson or .db file",
+            format,
+            path
+        )),
+    }
+}
+
+pub fn load_properties(path: &str, csv: CsvOptions) -> Result<Vec<Property>> {
+    open_storage(path, csv)?.load()
+}
+
+pub fn save_properties(properties: &[Property], path: &str, csv: CsvOptions) -> Result<()> {
+    open_storage(path, csv)?.save(properties)
+}
+
+/// Copy all properties from one storage to another, returning the number copied
+pub fn migrate(from: &dyn PropertyStorage, to: &dyn PropertyStorage) -> Result<usize> {
+    let properties = from.load()?;
+    to.save(&properties)?;
+    Ok(properties.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Coordinates, ListingType, Location, Price, PropertyKind};
+
+    fn sample_property() -> Property {
+        Property {
+            url: "https://www.vol.at/test".to_string(),
+            internal_id: None,
+            page_fingerprint: None,
+            completeness_score: None,
+            name: "Test".to_string(),
+            price: Price::from_str("450000"),
+            location: Location::from_str("Rankweil, Bezirk Feldkirch, Vorarlberg"),
+            property_type: PropertyKind::House.into(),
+            listing_type: ListingType::Sold,
+            unavailable_reason: None,
+            date: chrono::NaiveDate::from_ymd_opt(2025, 7, 25),
+            coordinates: Some(Coordinates::new(47.27, 9.64).unwrap()),
+            quality_score: Some(42),
+            plot_polygon: None,
+            description: Some("Haus mit Sauna".to_string()),
+            features: Vec::new(),
+            driving_distance_km: None,
+            rooms: None,
+            floor_level: None,
+            construction_year: None,
+            agency_name: None,
+            agency_phone: None,
+            address: None,
+            size_living: Some("120".to_string()),
+            size_ground: None,
+            first_seen: chrono::NaiveDate::from_ymd_opt(2025, 7, 26),
+            last_seen: None,
+        }
+    }
+
+    #[test]
+    fn test_sqlite_round_trip() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-test-{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+        let property = sample_property();
+
+        let storage = open_storage(path, CsvOptions::default()).unwrap();
+        storage.save(std::slice::from_ref(&property)).unwrap();
+        let loaded = storage.load().unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].location, property.location);
+        assert_eq!(loaded[0].coordinates, property.coordinates);
+        assert_eq!(loaded[0].quality_score, Some(42));
+        assert_eq!(loaded[0].description, property.description);
+        assert_eq!(loaded[0].date, property.date);
+        assert_eq!(loaded[0].size_ground, None);
+    }
+
+    #[test]
+    fn test_geojson_storage_survives_repeated_runs() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-storage-{}.geojson", std::process::id()));
+        let path = path.to_str().unwrap();
+        let mut property = sample_property();
+        property.rooms = Some(4.0);
+        property.construction_year = Some(1990);
+
+        let storage = open_storage(path, CsvOptions::default()).unwrap();
+        assert!(storage.load().unwrap().is_empty());
+        storage.save(std::slice::from_ref(&property)).unwrap();
+        // A second run loads what the first one saved and saves it again
+        let loaded = storage.load().unwrap();
+        storage.save(&loaded).unwrap();
+        let reloaded = storage.load().unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded[0].construction_year, Some(1990));
+        assert_eq!(reloaded[0].location, property.location);
+        assert_eq!(reloaded[0].coordinates, property.coordinates);
+    }
+
+    #[test]
+    fn test_json_is_not_a_storage_backend() {
+        let error = open_storage("properties.json", CsvOptions::default()).err().unwrap();
+        assert!(error.to_string().contains("--export-json"), "{}", error);
+    }
+
+    #[test]
+    fn test_is_sqlite_path() {
+        assert!(is_sqlite_path("properties.db"));
+        assert!(is_sqlite_path("data/properties.SQLITE"));
+        assert!(!is_sqlite_path("properties.csv"));
+    }
+}
@@ -1,10 +1,74 @@
 use crossterm::{
     cursor::MoveToPreviousLine,
     execute,
-    style::{Color, Print, ResetColor, SetForegroundColor},
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
     terminal::{Clear, ClearType},
 };
+use crate::common_scraper::{ScrapingOptions, ScrapingRun};
 use std::io;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How status icons are rendered
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IconMode {
+    Unicode,
+    /// ASCII replacements like `[+]` for terminals without UTF-8 support
+    PlainAsciiMode,
+}
+
+/// Narrowest terminal the compact grid is used in
+const COMPACT_MIN_WIDTH: usize = 120;
+
+/// Rows of properties shown at once
+const VISIBLE_ROWS: usize = 15;
+
+/// Columns of the compact grid, 1 if the terminal is too narrow for it
+fn grid_columns(requested: usize, terminal_width: usize) -> usize {
+    if terminal_width < COMPACT_MIN_WIDTH {
+        1
+    } else {
+        requested.max(1)
+    }
+}
+
+/// The end of a URL, e.g. the article slug, cut to at most `width` characters
+fn url_tail(url: &str, width: usize) -> String {
+    let tail = url.trim_end_matches('/').rsplit('/').find(|segment| !segment.chars().all(|c| c.is_ascii_digit()));
+    let tail = tail.unwrap_or(url);
+    if tail.chars().count() <= width {
+        tail.to_string()
+    } else {
+        let cut: String = tail.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    }
+}
+
+/// Format a remaining duration like "~2m30s", "~1h05m" or "<1m"
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    if secs < 60 {
+        "<1m".to_string()
+    } else if secs < 3600 {
+        format!("~{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("~{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+/// Number of most recent scrape durations averaged for the ETA
+const ETA_WINDOW: usize = 10;
+/// Scrapes needed before an ETA is shown
+const ETA_MIN_SAMPLES: usize = 3;
+
+/// Pick the Unicode icon or its ASCII replacement depending on the icon mode
+fn icon(icon_mode: IconMode, unicode: &'static str, ascii: &'static str) -> &'static str {
+    match icon_mode {
+        IconMode::Unicode => unicode,
+        IconMode::PlainAsciiMode => ascii,
+    }
+}
 
 pub struct ScraperTUI {
     initial_lines_printed: usize,
@@ -19,6 +83,16 @@ pub struct ScraperTUI {
     visible_start: usize,
     visible_end: usize,
     is_new_mode: bool,
+    /// Number of listings the platform reports for the topic, if known
+    total_listings: Option<usize>,
+    unsaved_count: usize,
+    /// Durations of the most recent scrapes, including rate limiting delays
+    scrape_times: Vec<Duration>,
+    /// When the previous scrape finished, or the first one started
+    last_scrape_finished: Option<Instant>,
+    icon_mode: IconMode,
+    /// Properties per row in compact mode, 1 for one property per line
+    compact_columns: usize,
 }
 
 #[derive(Clone)]
@@ -33,6 +107,20 @@ enum PropertyStatus {
     InProgress,  // White with activity marker
     Completed,   // Green
     Failed,      // Red
+    Filtered,    // Yellow
+}
+
+impl PropertyStatus {
+    /// Color and icon of a property with this status
+    fn style(&self, icon_mode: IconMode) -> (Color, &'static str) {
+        match self {
+            PropertyStatus::Pending => (Color::DarkGrey, icon(icon_mode, "⏳", "[?]")),
+            PropertyStatus::InProgress => (Color::White, icon(icon_mode, "🔄", "[>]")),
+            PropertyStatus::Completed => (Color::Green, icon(icon_mode, "✅", "[+]")),
+            PropertyStatus::Failed => (Color::Red, icon(icon_mode, "❌", "[!]")),
+            PropertyStatus::Filtered => (Color::Yellow, icon(icon_mode, "⏭", "[-]")),
+        }
+    }
 }
 
 impl ScraperTUI {
@@ -50,7 +138,107 @@ impl ScraperTUI {
             visible_start: 0,
             visible_end: 0,
             is_new_mode: false,
+            total_listings: None,
+            unsaved_count: 0,
+            scrape_times: Vec::new(),
+            last_scrape_finished: None,
+            icon_mode: IconMode::Unicode,
+            compact_columns: 1,
+        }
+    }
+
+    /// A TUI rendered with the icon mode and compact columns of `options`
+    pub fn for_options(options: &ScrapingOptions) -> Self {
+        Self::new()
+            .with_icon_mode(options.icon_mode)
+            .with_compact_columns(options.compact_columns)
+    }
+
+    /// Render status icons as Unicode or their ASCII replacements
+    pub fn with_icon_mode(mut self, icon_mode: IconMode) -> Self {
+        self.icon_mode = icon_mode;
+        self
+    }
+
+    /// Lay out property lists in a grid of `columns_per_row` properties per row
+    ///
+    /// Each cell only shows the status icon and the end of the URL. Terminals
+    /// narrower than 120 characters keep one property per line.
+    pub fn with_compact_columns(mut self, columns_per_row: usize) -> Self {
+        self.compact_columns = columns_per_row.max(1);
+        self
+    }
+
+    /// Pick the Unicode icon or its ASCII replacement depending on the icon mode
+    fn icon(&self, unicode: &'static str, ascii: &'static str) -> &'static str {
+        icon(self.icon_mode, unicode, ascii)
+    }
+
+    /// Properties per row right now, depending on compact mode and the terminal width
+    fn columns(&self) -> usize {
+        let requested = self.compact_columns;
+        if requested == 1 {
+            return 1;
+        }
+        let width = crossterm::terminal::size().map_or(0, |(width, _)| width as usize);
+        grid_columns(requested, width)
+    }
+
+    /// Properties shown at once
+    fn window_size(&self) -> usize {
+        VISIBLE_ROWS * self.columns()
+    }
+
+    pub fn icon_mode(&self) -> IconMode {
+        self.icon_mode
+    }
+
+    /// Guess whether the terminal can display Unicode icons
+    ///
+    /// Uses the locale from `LC_ALL` or `LANG` and rejects `TERM=dumb`. On
+    /// Windows the console code page must be UTF-8 (65001) unless running in
+    /// Windows Terminal.
+    pub fn detect_unicode_support() -> bool {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        if var("TERM").as_deref() == Some("dumb") {
+            return false;
+        }
+
+        if let Some(locale) = var("LC_ALL").or_else(|| var("LANG")) {
+            let locale = locale.to_lowercase();
+            return locale.contains("utf-8") || locale.contains("utf8");
+        }
+
+        #[cfg(windows)]
+        {
+            #[link(name = "kernel32")]
+            extern "system" {
+                fn GetConsoleOutputCP() -> u32;
+            }
+            const CP_UTF8: u32 = 65001;
+            // SAFETY: GetConsoleOutputCP takes no arguments and has no preconditions
+            var("WT_SESSION").is_some() || unsafe { GetConsoleOutputCP() } == CP_UTF8
         }
+
+        #[cfg(not(windows))]
+        {
+            false
+        }
+    }
+
+    /// Show a bold title bar at the start of a scraper's section
+    pub fn set_title(&mut self, title: &str) -> io::Result<()> {
+        execute!(
+            io::stdout(),
+            Print("\n"),
+            SetAttribute(Attribute::Bold),
+            Print(format!("=== {} ===\n", title)),
+            SetAttribute(Attribute::Reset),
+            ResetColor
+        )?;
+        self.initial_lines_printed += 2;
+        Ok(())
     }
 
     /// Show grey summary line with all loaded properties
@@ -59,7 +247,7 @@ impl ScraperTUI {
         execute!(
             io::stdout(),
             SetForegroundColor(Color::DarkGrey),
-            Print(format!("📁 Loaded {} existing properties\n", total_properties)),
+            Print(format!("{} Loaded {} existing properties\n", self.icon("📁", "[i]"), total_properties)),
             ResetColor
         )?;
         self.initial_lines_printed += 1;
@@ -71,7 +259,7 @@ impl ScraperTUI {
         execute!(
             io::stdout(),
             SetForegroundColor(Color::White),
-            Print(format!("⏳ Gathering URLs from listing pages (0/{})...\n", max_pages)),
+            Print(format!("{} Gathering URLs from listing pages (0/{})...\n", self.icon("⏳", "[.]"), max_pages)),
             ResetColor
         )?;
         self.initial_lines_printed += 1;
@@ -84,13 +272,18 @@ impl ScraperTUI {
         execute!(
             io::stdout(),
             SetForegroundColor(Color::White),
-            Print("⏳ Gathering URLs until no new found in 5 pages...\n"),
+            Print(format!("{} Gathering URLs until no new found in 5 pages...\n", self.icon("⏳", "[.]"))),
             ResetColor
         )?;
         self.initial_lines_printed += 1;
         Ok(())
     }
 
+    /// Show the platform's total number of listings while gathering in new mode
+    pub fn set_total_listings(&mut self, total: usize) {
+        self.total_listings = Some(total);
+    }
+
     /// Update gathering progress
     pub fn update_gathering_progress(&mut self, current_page: usize, max_pages: usize, urls_found: usize, new_urls: usize, known_urls: usize) -> io::Result<()> {
         // Move back to the gathering line and clear it
@@ -101,16 +294,20 @@ impl ScraperTUI {
         )?;
 
         let spinner = match current_page % 4 {
-            0 => "⠋",
-            1 => "⠙", 
-            2 => "⠹",
-            _ => "⠸",
+            0 => self.icon("⠋", "|"),
+            1 => self.icon("⠙", "/"),
+            2 => self.icon("⠹", "-"),
+            _ => self.icon("⠸", "\\"),
         };
 
         let message = if self.is_new_mode {
+            let total = match self.total_listings {
+                Some(total) => format!("Found {} total listings", total),
+                None => "unknown total".to_string(),
+            };
             format!(
-                "{} Gathering URLs until no new found (page {}) - {} URLs found ({} new, {} known)\n",
-                spinner, current_page, urls_found, new_urls, known_urls
+                "{} Gathering URLs until no new found (page {}) - {} URLs found ({} new, {} known), {}\n",
+                spinner, current_page, urls_found, new_urls, known_urls, total
             )
         } else {
             format!(
@@ -141,7 +338,7 @@ impl ScraperTUI {
         execute!(
             io::stdout(),
             SetForegroundColor(Color::DarkGrey),
-            Print(format!("✓ Gathered {} URLs from listing pages\n", total_urls)),
+            Print(format!("{} Gathered {} URLs from listing pages\n", self.icon("✓", "[+]"), total_urls)),
             ResetColor
         )?;
 
@@ -158,8 +355,8 @@ impl ScraperTUI {
             io::stdout(),
             SetForegroundColor(Color::White),
             Print(format!(
-                "🔍 Found {} new, {} already known properties\n",
-                new_count, known_count
+                "{} Found {} new, {} already known properties\n",
+                self.icon("🔍", "[i]"), new_count, known_count
             )),
             ResetColor
         )?;
@@ -177,8 +374,8 @@ impl ScraperTUI {
             io::stdout(),
             SetForegroundColor(Color::White),
             Print(format!(
-                "🔄 Found {} new, {} to be refreshed properties\n",
-                new_count, refresh_count
+                "{} Found {} new, {} to be refreshed properties\n",
+                self.icon("🔄", "[i]"), new_count, refresh_count
             )),
             ResetColor
         )?;
@@ -186,6 +383,30 @@ impl ScraperTUI {
         Ok(())
     }
 
+    /// Show that property page scraping is skipped in dry-run mode
+    pub fn show_dry_run(&mut self, url_count: usize) -> io::Result<()> {
+        execute!(
+            io::stdout(),
+            SetForegroundColor(Color::Yellow),
+            Print(format!("{} Dry run: skipping {} property pages\n", self.icon("🧪", "[i]"), url_count)),
+            ResetColor
+        )?;
+        self.initial_lines_printed += 1;
+        Ok(())
+    }
+
+    /// Show how many discovered URLs were dropped by the --region filter
+    pub fn show_region_filtered(&mut self, filtered_count: usize) -> io::Result<()> {
+        execute!(
+            io::stdout(),
+            SetForegroundColor(Color::Yellow),
+            Print(format!("{} Filtered {} URLs outside requested regions\n", self.icon("🗺 ", "[i]"), filtered_count)),
+            ResetColor
+        )?;
+        self.initial_lines_printed += 1;
+        Ok(())
+    }
+
     /// Add a new property to be scraped (initially greyed out)
     pub fn add_property(&mut self, url: String) -> io::Result<()> {
         let property_state = PropertyLineState {
@@ -196,9 +417,14 @@ impl ScraperTUI {
         self.property_lines.push(property_state);
         let new_index = self.property_lines.len() - 1;
         
+        // The grid is drawn as a whole by show_initial_progress_bar
+        if self.columns() > 1 {
+            return Ok(());
+        }
+        
         // Update visible range if this is the first property or if we're still in the initial window
-        if self.visible_end == 0 || new_index < 15 {
-            self.visible_end = (new_index + 1).min(15);
+        if self.visible_end == 0 || new_index < VISIBLE_ROWS {
+            self.visible_end = (new_index + 1).min(VISIBLE_ROWS);
         }
         
         // Only print if this property should be visible in our current window
@@ -206,7 +432,7 @@ impl ScraperTUI {
             execute!(
                 io::stdout(),
                 SetForegroundColor(Color::DarkGrey),
-                Print(format!("  ⏳ {}\n", &url)),
+                Print(format!("  {} {}\n", self.icon("⏳", "[?]"), &url)),
                 ResetColor
             )?;
             self.visible_lines += 1;
@@ -220,7 +446,10 @@ impl ScraperTUI {
         if !self.progress_bar_printed && !self.property_lines.is_empty() {
             // Set the initial visible window
             self.visible_start = 0;
-            self.visible_end = 15.min(self.property_lines.len());
+            self.visible_end = self.window_size().min(self.property_lines.len());
+            if self.columns() > 1 {
+                self.redraw_sliding_window()?;
+            }
             
             self.print_progress_bar()?;
             self.progress_bar_printed = true;
@@ -230,6 +459,7 @@ impl ScraperTUI {
 
     /// Mark a property as currently being scraped (white with activity marker)
     pub fn start_scraping_property(&mut self, url: &str) -> io::Result<()> {
+        self.last_scrape_finished.get_or_insert_with(Instant::now);
         if let Some(index) = self.find_property_index(url) {
             self.property_lines[index].status = PropertyStatus::InProgress;
             self.current_property_index = Some(index);
@@ -251,10 +481,11 @@ impl ScraperTUI {
     /// Update the activity marker for the currently active property
     pub fn update_activity(&mut self) -> io::Result<()> {
         if let Some(index) = self.current_property_index {
-            if self.property_lines[index].status == PropertyStatus::InProgress {
-                if index >= self.visible_start && index < self.visible_end {
-                    self.update_single_line(index)?;
-                }
+            if self.property_lines[index].status == PropertyStatus::InProgress
+                && index >= self.visible_start
+                && index < self.visible_end
+            {
+                self.update_single_line(index)?;
             }
         }
         Ok(())
@@ -262,6 +493,7 @@ impl ScraperTUI {
 
     /// Mark a property as completed (green)
     pub fn complete_property(&mut self, url: &str) -> io::Result<()> {
+        self.record_scrape_time();
         if let Some(index) = self.find_property_index(url) {
             self.property_lines[index].status = PropertyStatus::Completed;
             if Some(index) == self.current_property_index {
@@ -278,6 +510,7 @@ impl ScraperTUI {
 
     /// Mark a property as failed (red)
     pub fn fail_property(&mut self, url: &str) -> io::Result<()> {
+        self.record_scrape_time();
         if let Some(index) = self.find_property_index(url) {
             self.property_lines[index].status = PropertyStatus::Failed;
             if Some(index) == self.current_property_index {
@@ -292,6 +525,101 @@ impl ScraperTUI {
         Ok(())
     }
 
+    /// Mark a property as scraped but filtered out of the output (yellow)
+    pub fn filter_property(&mut self, url: &str) -> io::Result<()> {
+        self.record_scrape_time();
+        if let Some(index) = self.find_property_index(url) {
+            self.property_lines[index].status = PropertyStatus::Filtered;
+            if Some(index) == self.current_property_index {
+                self.current_property_index = None;
+            }
+            
+            // Just update the line in place if it's visible
+            if index >= self.visible_start && index < self.visible_end {
+                self.update_single_line(index)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Track the time since the previous scrape finished, keeping the last `ETA_WINDOW`
+    fn record_scrape_time(&mut self) {
+        let now = Instant::now();
+        if let Some(previous) = self.last_scrape_finished.replace(now) {
+            self.scrape_times.push(now - previous);
+            if self.scrape_times.len() > ETA_WINDOW {
+                self.scrape_times.remove(0);
+            }
+        }
+    }
+
+    /// Average of the recent scrape times multiplied by the number of properties left
+    ///
+    /// None until `ETA_MIN_SAMPLES` scrapes have finished.
+    pub fn estimated_time_remaining(&self) -> Option<Duration> {
+        if self.scrape_times.len() < ETA_MIN_SAMPLES {
+            return None;
+        }
+        let remaining = self
+            .property_lines
+            .iter()
+            .filter(|p| matches!(p.status, PropertyStatus::Pending | PropertyStatus::InProgress))
+            .count();
+        let average = self.scrape_times.iter().sum::<Duration>() / self.scrape_times.len() as u32;
+        Some(average * remaining as u32)
+    }
+
+    /// ETA text for the progress bar, e.g. "ETA: ~2m30s" or "ETA: <1m"
+    pub fn show_estimated_time_remaining(&self) -> String {
+        match self.estimated_time_remaining() {
+            None => "ETA: calculating...".to_string(),
+            Some(eta) => format!("ETA: {}", format_eta(eta)),
+        }
+    }
+
+    /// Number of scraped properties not yet written to disk, shown in the progress bar
+    pub fn set_unsaved_count(&mut self, unsaved_count: usize) {
+        self.unsaved_count = unsaved_count;
+    }
+
+    /// Show a countdown line below the progress bar while waiting for the next request
+    ///
+    /// Blocks for `delay_ms`. A ticker thread sends the remaining time every
+    /// 100ms; the line is cleared again once the delay has passed.
+    pub fn show_delay_countdown(&self, delay_ms: u64) -> io::Result<()> {
+        let (tx, rx) = mpsc::channel();
+        let ticker = thread::spawn(move || {
+            let start = Instant::now();
+            let total = Duration::from_millis(delay_ms);
+            loop {
+                let remaining = total.saturating_sub(start.elapsed());
+                if tx.send(remaining).is_err() || remaining.is_zero() {
+                    break;
+                }
+                thread::sleep(remaining.min(Duration::from_millis(100)));
+            }
+        });
+
+        for (tick, remaining) in rx.into_iter().enumerate() {
+            // Replace the previous countdown line
+            if tick > 0 {
+                execute!(io::stdout(), MoveToPreviousLine(1), Clear(ClearType::CurrentLine))?;
+            }
+            if remaining.is_zero() {
+                break;
+            }
+            execute!(
+                io::stdout(),
+                SetForegroundColor(Color::DarkGrey),
+                Print(format!("{} Next request in {:.1}s{}\n", self.icon("⏱", "[.]"), remaining.as_secs_f64(), self.icon("…", "..."))),
+                ResetColor
+            )?;
+        }
+
+        let _ = ticker.join();
+        Ok(())
+    }
+
     /// Show final summary
     pub fn show_final_summary(&mut self, _scraped_count: usize, total_count: usize) -> io::Result<()> {
         self.total_properties_in_db = total_count;
@@ -301,13 +629,14 @@ impl ScraperTUI {
         
         let completed = self.property_lines.iter().filter(|p| p.status == PropertyStatus::Completed).count();
         let failed = self.property_lines.iter().filter(|p| p.status == PropertyStatus::Failed).count();
+        let filtered = self.property_lines.iter().filter(|p| p.status == PropertyStatus::Filtered).count();
 
         execute!(
             io::stdout(),
-            Print("─".repeat(80)),
+            Print(self.icon("─", "-").repeat(80)),
             Print("\n"),
             SetForegroundColor(Color::Green),
-            Print(format!("✅ Scraping completed: {} successful", completed)),
+            Print(format!("{} Scraping completed: {} successful", self.icon("✅", "[+]"), completed)),
             ResetColor
         )?;
         
@@ -320,6 +649,15 @@ impl ScraperTUI {
             )?;
         }
         
+        if filtered > 0 {
+            execute!(
+                io::stdout(),
+                SetForegroundColor(Color::Yellow),
+                Print(format!(", {} filtered out", filtered)),
+                ResetColor
+            )?;
+        }
+        
         execute!(
             io::stdout(),
             SetForegroundColor(Color::DarkGrey),
@@ -330,6 +668,55 @@ impl ScraperTUI {
         Ok(())
     }
 
+    /// Show a per-scraper breakdown after several scrapers ran in one invocation
+    pub fn show_combined_summary(&self, runs: &[ScrapingRun]) -> io::Result<()> {
+        execute!(
+            io::stdout(),
+            Print("\n"),
+            SetAttribute(Attribute::Bold),
+            Print(format!(
+                "{:<28} {:>8} {:>8} {:>8} {:>10}\n",
+                "Scraper", "New", "Failed", "Known", "Duration"
+            )),
+            SetAttribute(Attribute::Reset),
+            Print(self.icon("─", "-").repeat(66)),
+            Print("\n"),
+        )?;
+
+        for run in runs {
+            let color = if run.failed > run.new_scraped { Color::Red } else { Color::White };
+            execute!(
+                io::stdout(),
+                SetForegroundColor(color),
+                Print(format!(
+                    "{:<28} {:>8} {:>8} {:>8} {:>9.1}s\n",
+                    run.scraper_name,
+                    run.new_scraped,
+                    run.failed,
+                    run.known,
+                    run.duration.as_secs_f64()
+                )),
+                ResetColor
+            )?;
+        }
+
+        let total = |f: fn(&ScrapingRun) -> usize| runs.iter().map(f).sum::<usize>();
+        execute!(
+            io::stdout(),
+            SetForegroundColor(Color::DarkGrey),
+            Print(format!(
+                "{:<28} {:>8} {:>8} {:>8} {:>9.1}s\n",
+                "Total",
+                total(|r| r.new_scraped),
+                total(|r| r.failed),
+                total(|r| r.known),
+                runs.iter().map(|r| r.duration.as_secs_f64()).sum::<f64>()
+            )),
+            ResetColor
+        )?;
+        Ok(())
+    }
+
     /// Show failure report with URLs and reasons
     pub fn show_failure_report(&self, failed_urls: &[(String, String)]) -> io::Result<()> {
         if !failed_urls.is_empty() {
@@ -337,7 +724,7 @@ impl ScraperTUI {
                 io::stdout(),
                 Print("\n"),
                 SetForegroundColor(Color::Red),
-                Print(format!("❌ Failure Report ({} failed URLs):\n", failed_urls.len())),
+                Print(format!("{} Failure Report ({} failed URLs):\n", self.icon("❌", "[!]"), failed_urls.len())),
                 ResetColor
             )?;
 
@@ -345,7 +732,7 @@ impl ScraperTUI {
                 execute!(
                     io::stdout(),
                     SetForegroundColor(Color::Red),
-                    Print("  • "),
+                    Print(format!("  {} ", self.icon("•", "-"))),
                     ResetColor,
                     SetForegroundColor(Color::White),
                     Print(format!("{}\n", url)),
@@ -373,12 +760,12 @@ impl ScraperTUI {
 
     /// Slide the window forward by a few positions
     fn slide_window_forward(&mut self) -> io::Result<()> {
-        // Calculate new window that shows last 3 completed + current + remaining pending (up to 15 total)
+        // Calculate new window that shows last 3 completed + current + remaining pending (up to 15 rows)
         if let Some(current_idx) = self.current_property_index {
             // Find the number of completed properties before current
             let completed_before = self.property_lines[..current_idx]
                 .iter()
-                .filter(|p| matches!(p.status, PropertyStatus::Completed | PropertyStatus::Failed))
+                .filter(|p| matches!(p.status, PropertyStatus::Completed | PropertyStatus::Failed | PropertyStatus::Filtered))
                 .count();
             
             // Start from 3 completed properties back, or beginning if less than 3
@@ -387,7 +774,7 @@ impl ScraperTUI {
                 let mut completed_count = 0;
                 let mut start_idx = current_idx;
                 for i in (0..current_idx).rev() {
-                    if matches!(self.property_lines[i].status, PropertyStatus::Completed | PropertyStatus::Failed) {
+                    if matches!(self.property_lines[i].status, PropertyStatus::Completed | PropertyStatus::Failed | PropertyStatus::Filtered) {
                         completed_count += 1;
                         if completed_count == 3 {
                             start_idx = i;
@@ -399,8 +786,10 @@ impl ScraperTUI {
             } else {
                 0 // Show from beginning if we don't have 3 completed yet
             };
+            // Keep the grid's columns in place
+            let new_start = new_start - new_start % self.columns();
             
-            let new_end = (new_start + 15).min(self.property_lines.len());
+            let new_end = (new_start + self.window_size()).min(self.property_lines.len());
             
             if new_start != self.visible_start || new_end != self.visible_end {
                 self.visible_start = new_start;
@@ -413,8 +802,10 @@ impl ScraperTUI {
 
     /// Slide the window to show a specific property
     fn slide_window_to_show(&mut self, index: usize) -> io::Result<()> {
-        let new_start = index.saturating_sub(7); // Show more context with 15 total lines
-        let new_end = (new_start + 15).min(self.property_lines.len());
+        let columns = self.columns();
+        let new_start = index.saturating_sub(7 * columns); // Show more context with 15 total rows
+        let new_start = new_start - new_start % columns;
+        let new_end = (new_start + self.window_size()).min(self.property_lines.len());
         
         self.visible_start = new_start;
         self.visible_end = new_end;
@@ -440,9 +831,18 @@ impl ScraperTUI {
         self.visible_lines = 0;
 
         // Redraw visible properties
-        for i in self.visible_start..self.visible_end {
-            self.draw_property_line(&self.property_lines[i])?;
-            self.visible_lines += 1;
+        let columns = self.columns();
+        if columns > 1 {
+            let width = crossterm::terminal::size().map_or(COMPACT_MIN_WIDTH, |(width, _)| width as usize);
+            for row in self.property_lines[self.visible_start..self.visible_end].chunks(columns) {
+                self.draw_property_row(row, width / columns)?;
+                self.visible_lines += 1;
+            }
+        } else {
+            for i in self.visible_start..self.visible_end {
+                self.draw_property_line(&self.property_lines[i])?;
+                self.visible_lines += 1;
+            }
         }
 
         // Redraw progress bar if it was there
@@ -454,23 +854,35 @@ impl ScraperTUI {
     }
 
     fn draw_property_line(&self, property_line: &PropertyLineState) -> io::Result<()> {
-        let (color, icon) = match property_line.status {
-            PropertyStatus::Pending => (Color::DarkGrey, "⏳"),
-            PropertyStatus::InProgress => (Color::White, "🔄"),
-            PropertyStatus::Completed => (Color::Green, "✅"),
-            PropertyStatus::Failed => (Color::Red, "❌"),
-        };
+        let (color, symbol) = property_line.status.style(self.icon_mode);
 
         execute!(
             io::stdout(),
             SetForegroundColor(color),
-            Print(format!("  {} {}\n", icon, &property_line.url)),
+            Print(format!("  {} {}\n", symbol, &property_line.url)),
             ResetColor
         )?;
 
         Ok(())
     }
 
+    /// Draw one row of the compact grid, each cell `cell_width` characters wide
+    fn draw_property_row(&self, row: &[PropertyLineState], cell_width: usize) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        for property_line in row {
+            let (color, symbol) = property_line.status.style(self.icon_mode);
+            // Indent, icon and the space after it
+            let tail_width = cell_width.saturating_sub(6);
+            execute!(
+                stdout,
+                SetForegroundColor(color),
+                Print(format!("  {} {:<width$}", symbol, url_tail(&property_line.url, tail_width), width = tail_width)),
+                ResetColor
+            )?;
+        }
+        execute!(stdout, Print("\n"))
+    }
+
 
     /// Print the progress bar for the first time
     fn print_progress_bar(&self) -> io::Result<()> {
@@ -483,7 +895,7 @@ impl ScraperTUI {
         // Print separator and progress bar
         execute!(
             io::stdout(),
-            Print("─".repeat(80)),
+            Print(self.icon("─", "-").repeat(80)),
             Print("\n"),
             SetForegroundColor(Color::White),
             Print(status_line),
@@ -499,29 +911,36 @@ impl ScraperTUI {
     fn create_progress_bar_text(&self) -> String {
         let completed = self.property_lines.iter().filter(|p| p.status == PropertyStatus::Completed).count();
         let failed = self.property_lines.iter().filter(|p| p.status == PropertyStatus::Failed).count();
+        let filtered = self.property_lines.iter().filter(|p| p.status == PropertyStatus::Filtered).count();
         let total = self.property_lines.len();
-        let percentage = if total > 0 { (completed * 100) / total } else { 0 };
+        // Filtered properties were scraped successfully, so they count towards progress
+        let done = completed + filtered;
+        let percentage = (done * 100).checked_div(total).unwrap_or(0);
 
         // Create progress bar (30 characters wide)
         let bar_width = 30;
-        let filled = (completed * bar_width) / total.max(1);
+        let filled = (done * bar_width) / total.max(1);
         let progress_bar = format!(
             "[{}{}]",
-            "█".repeat(filled),
-            "░".repeat(bar_width - filled)
+            self.icon("█", "#").repeat(filled),
+            self.icon("░", ".").repeat(bar_width - filled)
         );
 
+        let mut text = format!("Progress: {} {}/{} ({}%)", progress_bar, done, total, percentage);
         if failed > 0 {
-            format!(
-                "Progress: {} {}/{} ({}%) | {} failed | DB: {} total",
-                progress_bar, completed, total, percentage, failed, self.total_properties_in_db
-            )
-        } else {
-            format!(
-                "Progress: {} {}/{} ({}%) | DB: {} total",
-                progress_bar, completed, total, percentage, self.total_properties_in_db
-            )
+            text.push_str(&format!(" | {} failed", failed));
+        }
+        if filtered > 0 {
+            text.push_str(&format!(" | {} filtered", filtered));
         }
+        text.push_str(&format!(" | DB: {} total", self.total_properties_in_db));
+        if self.unsaved_count > 0 {
+            text.push_str(&format!(" | Unsaved: {} properties", self.unsaved_count));
+        }
+        if done + failed < total {
+            text.push_str(&format!(" | {}", self.show_estimated_time_remaining()));
+        }
+        text
     }
 
     /// Clear the progress bar (used before final summary)
@@ -542,4 +961,41 @@ impl Default for ScraperTUI {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_grid_layout() {
+        assert_eq!(grid_columns(4, 200), 4);
+        assert_eq!(grid_columns(4, 119), 1);
+        assert_eq!(grid_columns(0, 200), 1);
+
+        assert_eq!(url_tail("https://www.vol.at/haus-in-dornbirn-verkauft/8765432", 30), "haus-in-dornbirn-verkauft");
+        assert_eq!(url_tail("https://www.laendleimmo.at/immobilien/wohnung/vorarlberg/dornbirn/wohnung-am-see/", 30), "wohnung-am-see");
+        assert_eq!(url_tail("https://www.vol.at/haus-in-dornbirn-verkauft/8765432", 8), "haus-in…");
+    }
+
+    #[test]
+    fn test_estimated_time_remaining() {
+        assert_eq!(format_eta(Duration::from_secs(59)), "<1m");
+        assert_eq!(format_eta(Duration::from_secs(150)), "~2m30s");
+        assert_eq!(format_eta(Duration::from_secs(3900)), "~1h05m");
+
+        let mut tui = ScraperTUI::new();
+        tui.property_lines = (0..20)
+            .map(|i| PropertyLineState { url: i.to_string(), status: PropertyStatus::Pending })
+            .collect();
+        tui.scrape_times = vec![Duration::from_secs(4), Duration::from_secs(6)];
+        assert_eq!(tui.show_estimated_time_remaining(), "ETA: calculating...");
+
+        tui.scrape_times.push(Duration::from_secs(5));
+        for line in &mut tui.property_lines[..3] {
+            line.status = PropertyStatus::Completed;
+        }
+        assert_eq!(tui.estimated_time_remaining(), Some(Duration::from_secs(85)));
+        assert_eq!(tui.show_estimated_time_remaining(), "ETA: ~1m25s");
+    }
+}
@@ -1,8 +1,13 @@
 use anyhow::{Context, Result};
-use std::fs::{copy, File};
-use std::path::Path;
+use std::collections::VecDeque;
+use std::fs::{copy, rename, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 // Removed the unused import: use csv::Writer;
-use crate::models::Property;
+use crate::common_scraper::ScrapingContext;
+use crate::http_logging::SendLogged;
+use crate::models::{polygon_to_wkt, Coordinates, Property, PropertyFilter};
 use crate::{debug_println};
 use rand::seq::SliceRandom;
 
@@ -30,7 +35,7 @@ pub fn extract_property_id(url: &str) -> Option<String> {
         let clean_url = clean_url.trim_end_matches('/');
         
         // Split by '/' and take the last segment as the property ID
-        if let Some(id) = clean_url.split('/').last() {
+        if let Some(id) = clean_url.split('/').next_back() {
             if !id.is_empty() {
                 return Some(id.to_string());
             }
@@ -39,57 +44,1247 @@ pub fn extract_property_id(url: &str) -> Option<String> {
     None
 }
 
-/// Get a random user agent from a pool of common desktop browsers
-/// 
-/// This function returns different user agents for Chrome, Firefox, Safari and Edge
-/// across different operating systems (Windows, macOS, Linux) to avoid detection.
-pub fn get_random_user_agent() -> &'static str {
-    let user_agents = [
-        // Chrome on Windows
-        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36",
-        "Mozilla/5.0 (Windows NT 11.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-        
-        // Chrome on macOS
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36",
-        
-        // Chrome on Linux
-        "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-        "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36",
-        
-        // Firefox on Windows
-        "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0",
-        "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0",
-        "Mozilla/5.0 (Windows NT 11.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0",
-        
-        // Firefox on macOS
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:121.0) Gecko/20100101 Firefox/121.0",
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:120.0) Gecko/20100101 Firefox/120.0",
-        
-        // Firefox on Linux
-        "Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
-        "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
-        
-        // Safari on macOS
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Safari/605.1.15",
-        
-        // Edge on Windows
-        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0",
-        "Mozilla/5.0 (Windows NT 11.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0",
-        
-        // Edge on macOS
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0",
-    ];
+/// Pool of common desktop browsers
+///
+/// Chrome, Firefox, Safari and Edge across different operating systems
+/// (Windows, macOS, Linux) to avoid detection.
+pub const USER_AGENTS: [&str; 19] = [
+    // Chrome on Windows
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Windows NT 11.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    
+    // Chrome on macOS
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36",
+    
+    // Chrome on Linux
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36",
+    
+    // Firefox on Windows
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101 Firefox/120.0",
+    "Mozilla/5.0 (Windows NT 11.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0",
+    
+    // Firefox on macOS
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:121.0) Gecko/20100101 Firefox/121.0",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:120.0) Gecko/20100101 Firefox/120.0",
+    
+    // Firefox on Linux
+    "Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+    "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
     
-    let mut rng = rand::thread_rng();
-    user_agents.choose(&mut rng).unwrap_or(&user_agents[0])
+    // Safari on macOS
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Safari/605.1.15",
+    
+    // Edge on Windows
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0",
+    "Mozilla/5.0 (Windows NT 11.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0",
+    
+    // Edge on macOS
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0",
+];
+
+/// Headers sent with every request unless overridden by the configured ones
+///
+/// Austrian German page variants are what the parsers expect.
+pub const DEFAULT_HTTP_HEADERS: [(&str, &str); 2] = [
+    ("Accept-Language", "de-AT,de;q=0.9"),
+    ("Accept-Encoding", "gzip, deflate, br"),
+];
+
+/// The default headers merged with `configured`, which replace defaults of the same name
+pub fn http_headers(configured: &[(String, String)]) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    let defaults = DEFAULT_HTTP_HEADERS.iter().map(|(k, v)| (k.to_string(), v.to_string()));
+
+    for (name, value) in defaults.chain(configured.iter().cloned()) {
+        match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(&value)) {
+            (Ok(name), Ok(value)) => {
+                map.insert(name, value);
+            }
+            _ => debug_println!("Skipping invalid HTTP header {}: {}", name, value),
+        }
+    }
+    map
+}
+
+/// Client builder all HTTP requests go through, sending the default headers merged with `configured`
+pub fn http_client_builder(configured: &[(String, String)]) -> reqwest::blocking::ClientBuilder {
+    reqwest::blocking::Client::builder()
+        .default_headers(http_headers(configured))
+        .redirect(crate::http_logging::redirect_policy())
+}
+
+/// Answer one request on a local port with the given status line and body
+///
+/// Returns the URL to request and a handle yielding the lowercased request head.
+#[cfg(test)]
+pub(crate) fn serve_once(status: &'static str, body: &'static str) -> (String, std::thread::JoinHandle<String>) {
+    use std::io::{BufRead, BufReader};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut head = String::new();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        while reader.read_line(&mut head).unwrap() > 2 && !head.ends_with("\r\n\r\n") {}
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+        head.to_lowercase()
+    });
+    (url, server)
+}
+
+/// Rough bounding box of Vorarlberg as (min, max) latitude and longitude
+const VORARLBERG_LAT: (f64, f64) = (47.0, 47.7);
+const VORARLBERG_LNG: (f64, f64) = (9.5, 10.3);
+/// About 10 km in degrees of latitude and, at 47° north, of longitude
+const VORARLBERG_MARGIN: (f64, f64) = (0.09, 0.13);
+
+/// Bounding box of Austria as (min, max) latitude and longitude, generous by a few km
+const AUSTRIA_LAT: (f64, f64) = (46.3, 49.1);
+const AUSTRIA_LNG: (f64, f64) = (9.5, 17.2);
+
+/// Whether coordinates lie in Vorarlberg or within about 10 km of it
+pub fn is_coordinate_in_vorarlberg(lat: f64, lng: f64) -> bool {
+    (VORARLBERG_LAT.0 - VORARLBERG_MARGIN.0..=VORARLBERG_LAT.1 + VORARLBERG_MARGIN.0).contains(&lat)
+        && (VORARLBERG_LNG.0 - VORARLBERG_MARGIN.1..=VORARLBERG_LNG.1 + VORARLBERG_MARGIN.1).contains(&lng)
+}
+
+/// Whether coordinates lie within the bounding box of Austria
+pub fn is_coordinate_in_austria(lat: f64, lng: f64) -> bool {
+    (AUSTRIA_LAT.0..=AUSTRIA_LAT.1).contains(&lat) && (AUSTRIA_LNG.0..=AUSTRIA_LNG.1).contains(&lng)
+}
+
+/// Host of a URL without scheme, port and leading "www.", e.g. "vol.at"
+pub fn domain_from_url(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = without_scheme
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or(without_scheme);
+    let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+    let host = host.split(':').next().unwrap_or(host);
+    host.strip_prefix("www.").unwrap_or(host)
+}
+
+/// All JSON embedded in `application/json` and `application/ld+json` script tags
+///
+/// Scripts that don't parse are skipped, so scrapers can look through every
+/// JSON source of a page without relying on specific script tag IDs.
+pub fn extract_all_json_scripts(document: &scraper::Html) -> Vec<serde_json::Value> {
+    let selector = scraper::Selector::parse(
+        r#"script[type="application/json"], script[type="application/ld+json"]"#,
+    )
+    .unwrap();
+    document
+        .select(&selector)
+        .filter_map(|script| {
+            let content = script.inner_html();
+            match serde_json::from_str(content.trim()) {
+                Ok(json) => Some(json),
+                Err(e) => {
+                    debug_println!("Skipping unparseable JSON script {:?}: {}", script.value().id(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Markers only found on bot challenge interstitials, matched against the lowercased HTML
+///
+/// CAPTCHA widgets and Cloudflare's `challenge-platform` script also appear on
+/// regular pages, e.g. in contact forms, so they are no sign of a challenge.
+const CAPTCHA_MARKERS: &[&str] = &[
+    // Cloudflare challenge configuration and its German instructions
+    "cf_chl_opt",
+    "ob sie ein mensch sind",
+];
+
+/// Lowercased titles of bot challenge interstitials
+const CAPTCHA_TITLES: &[&str] = &[
+    "just a moment...",
+    "einen moment bitte...",
+    "attention required! | cloudflare",
+    "please verify you're human",
+    "please verify you are human",
+];
+
+/// Whether a page is a bot challenge interstitial instead of the requested content
+pub fn detect_captcha(html: &str) -> bool {
+    let lower = html.to_lowercase();
+    let title = lower
+        .split_once("<title")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</title>"))
+        .map(|(title, _)| title.trim());
+    CAPTCHA_MARKERS.iter().any(|marker| lower.contains(marker))
+        || title.is_some_and(|title| CAPTCHA_TITLES.contains(&title))
+}
+
+/// Properties that differ between two versions of the database, matched by URL
+#[derive(Debug, Clone, Default)]
+pub struct PropertyDelta {
+    pub added: Vec<Property>,
+    /// Old and new version of each changed property
+    pub updated: Vec<(Property, Property)>,
+    pub removed: Vec<Property>,
+}
+
+impl PropertyDelta {
+    /// Added properties and the new versions of updated ones, e.g. to pass on downstream
+    pub fn changed(&self) -> Vec<Property> {
+        self.added.iter().cloned().chain(self.updated.iter().map(|(_, new)| new.clone())).collect()
+    }
+}
+
+/// Compare two versions of the database
+///
+/// A property counts as updated if any field but `last_seen` differs, so
+/// merely seeing it again on a listing page is not a change.
+pub fn compute_property_delta(before: &[Property], after: &[Property]) -> PropertyDelta {
+    let comparable = |property: &Property| {
+        let mut record = property.to_json().unwrap_or_default();
+        if let Some(record) = record.as_object_mut() {
+            record.remove("last_seen");
+        }
+        record
+    };
+    let before_by_url: std::collections::HashMap<&str, &Property> =
+        before.iter().map(|property| (property.url.as_str(), property)).collect();
+    let after_urls: std::collections::HashSet<&str> = after.iter().map(|property| property.url.as_str()).collect();
+
+    let mut delta = PropertyDelta::default();
+    for property in after {
+        match before_by_url.get(property.url.as_str()) {
+            None => delta.added.push(property.clone()),
+            Some(old) if comparable(old) != comparable(property) => {
+                delta.updated.push(((*old).clone(), property.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    delta.removed = before.iter().filter(|property| !after_urls.contains(property.url.as_str())).cloned().collect();
+    delta
+}
+
+/// Share of the optional listing details a property has, from 0.0 to 1.0
+///
+/// Counts the listing date, coordinates, address, sizes, rooms, floor,
+/// construction year, description, features and agency contact. Geocoding
+/// quality, commute distance and bookkeeping fields don't count, as they
+/// don't come from the page.
+pub fn data_completeness_score(property: &Property) -> f64 {
+    let populated = [
+        property.date.is_some(),
+        property.coordinates.is_some(),
+        property.address.is_some(),
+        property.size_living.is_some(),
+        property.size_ground.is_some(),
+        property.rooms.is_some(),
+        property.floor_level.is_some(),
+        property.construction_year.is_some(),
+        property.description.is_some(),
+        !property.features.is_empty(),
+        property.agency_name.is_some(),
+        property.agency_phone.is_some(),
+    ];
+    populated.iter().filter(|populated| **populated).count() as f64 / populated.len() as f64
+}
+
+/// Elements holding the meaningful content of a property page, the first match is fingerprinted
+const FINGERPRINT_CONTENT_SELECTORS: [&str; 4] = ["main", "article", "#content", "body"];
+
+/// Elements whose text changes on every request: session IDs in scripts, ads and tracking pixels
+///
+/// Scripts holding JSON are no such elements, they are fingerprinted separately.
+const FINGERPRINT_IGNORED_ELEMENTS: [&str; 6] = ["script", "style", "noscript", "iframe", "img", "svg"];
+
+/// SHA-256 of the visible text of a page's main content and its embedded JSON data, as hex
+///
+/// The JSON data, like vol.at's post data or laendleimmo.at's JSON-LD, is
+/// taken from the whole page and hashed in a normalized form. Other scripts,
+/// times of day and session-ID-like tokens are removed before hashing, so the
+/// fingerprint only changes when the listing itself does.
+pub fn page_fingerprint(html: &str) -> String {
+    use scraper::{Html, Node, Selector};
+    use sha2::{Digest, Sha256};
+
+    let document = Html::parse_document(html);
+    let mut text = String::new();
+    for script in document.select(&Selector::parse("script").unwrap()) {
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(script.inner_html().trim()) {
+            text.push_str(&json.to_string());
+            text.push(' ');
+        }
+    }
+
+    let content = FINGERPRINT_CONTENT_SELECTORS
+        .iter()
+        .find_map(|selector| document.select(&Selector::parse(selector).unwrap()).next());
+    if let Some(content) = content {
+        for node in content.descendants() {
+            let Node::Text(fragment) = node.value() else {
+                continue;
+            };
+            let ignored = node.ancestors().any(|ancestor| {
+                ancestor
+                    .value()
+                    .as_element()
+                    .is_some_and(|element| FINGERPRINT_IGNORED_ELEMENTS.contains(&element.name()))
+            });
+            if !ignored {
+                text.push_str(fragment);
+                text.push(' ');
+            }
+        }
+    }
+
+    let volatile = regex::Regex::new(
+        r"(?i)\b\d{1,2}:\d{2}(:\d{2})?\b|\b(sid|session|sessionid|token)=\S+|\b[0-9a-f]{16,}\b",
+    )
+    .unwrap();
+    let text = volatile.replace_all(&text, " ");
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+/// Environment variable with a `|`-separated list of user agents replacing the built-in pool
+pub const USER_AGENTS_ENV: &str = "LAENDLEFINDER_USER_AGENTS";
+
+/// User agents from `LAENDLEFINDER_USER_AGENTS`, or the built-in pool if it is unset or empty
+pub fn user_agents_from_env() -> Vec<String> {
+    user_agent_pool(std::env::var(USER_AGENTS_ENV).ok().as_deref())
+}
+
+fn user_agent_pool(env_value: Option<&str>) -> Vec<String> {
+    let agents: Vec<String> = env_value
+        .unwrap_or_default()
+        .split('|')
+        .map(str::trim)
+        .filter(|ua| !ua.is_empty())
+        .map(str::to_string)
+        .collect();
+    if agents.is_empty() {
+        USER_AGENTS.iter().map(|ua| ua.to_string()).collect()
+    } else {
+        agents
+    }
+}
+
+/// Picks random user agents without repeating any of the last `no_repeat` ones
+pub struct UserAgentRotator {
+    pool: Vec<String>,
+    recent: VecDeque<usize>,
+    no_repeat: usize,
+}
+
+impl UserAgentRotator {
+    /// `no_repeat` is capped so that at least one agent is always available
+    pub fn new(pool: Vec<String>, no_repeat: usize) -> Self {
+        let pool = if pool.is_empty() {
+            user_agents_from_env()
+        } else {
+            pool
+        };
+        let no_repeat = no_repeat.min(pool.len() - 1);
+        Self { pool, recent: VecDeque::with_capacity(no_repeat + 1), no_repeat }
+    }
+
+    /// Pool of [`user_agents_from_env`] plus additional agents, e.g. from [`load_user_agents`]
+    pub fn with_additional(additional: &[String], no_repeat: usize) -> Self {
+        let mut pool = user_agents_from_env();
+        for ua in additional {
+            if !pool.contains(ua) {
+                pool.push(ua.clone());
+            }
+        }
+        Self::new(pool, no_repeat)
+    }
+
+    // Not an Iterator: the returned agent borrows from the rotator
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> &str {
+        let candidates: Vec<usize> = (0..self.pool.len())
+            .filter(|i| !self.recent.contains(i))
+            .collect();
+        let index = *candidates.choose(&mut rand::thread_rng()).unwrap_or(&0);
+
+        self.recent.push_back(index);
+        if self.recent.len() > self.no_repeat {
+            self.recent.pop_front();
+        }
+        &self.pool[index]
+    }
+}
+
+/// Read user agents from a text file, one per line
+///
+/// Blank lines and lines starting with `#` are ignored.
+pub fn load_user_agents(path: &str) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read user agents file: {}", path))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// A CSV row that couldn't be loaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvError {
+    /// Line in the file the row starts at, counting the header as line 1
+    pub line: u64,
+    pub message: String,
+    /// The row as it is in the file, None if it couldn't even be split into fields
+    pub record: Option<csv::StringRecord>,
+}
+
+impl std::fmt::Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// CSV reader for `path`, None if the file doesn't exist yet
+fn open_csv_reader(path: &Path) -> Result<Option<csv::Reader<File>>> {
+    if !path.exists() {
+        debug_println!(
+            "CSV file {} does not exist, creating a new one",
+            path.display()
+        );
+        return Ok(None);
+    }
+
+    let file =
+        File::open(path).with_context(|| format!("Failed to open CSV file: {}", path.display()))?;
+    Ok(Some(csv::Reader::from_reader(file)))
+}
+
+/// Load properties from a CSV file, skipping rows that can't be read
+///
+/// Returns the valid properties and an error per skipped row, so a single
+/// broken row (e.g. from a manual edit) doesn't make the whole file unusable.
+/// Fails only if the file itself can't be read.
+pub fn load_properties_from_csv_lenient(path: &str) -> Result<(Vec<Property>, Vec<CsvError>)> {
+    let path = Path::new(path);
+    let Some(mut reader) = open_csv_reader(path)? else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+
+    let headers = reader
+        .headers()
+        .with_context(|| format!("Failed to read CSV header: {}", path.display()))?
+        .clone();
+    let mut properties = Vec::new();
+    let mut errors = Vec::new();
+    for (i, result) in reader.records().enumerate() {
+        // Row i is on line i + 2 unless earlier rows span several lines
+        let fallback_line = i as u64 + 2;
+        let record = match result {
+            Ok(record) => record,
+            Err(e) if matches!(e.kind(), csv::ErrorKind::Io(_)) => {
+                return Err(anyhow::Error::from(e).context(format!("Failed to read CSV file: {}", path.display())));
+            }
+            Err(e) => {
+                let line = e.position().map_or(fallback_line, |position| position.line());
+                errors.push(CsvError { line, message: e.to_string(), record: None });
+                continue;
+            }
+        };
+        match record.deserialize::<Property>(Some(&headers)) {
+            Ok(mut property) => {
+                property.url = sanitize_url(&property.url);
+                properties.push(property);
+            }
+            Err(e) => {
+                let message = match e.kind() {
+                    csv::ErrorKind::Deserialize { err, .. } => err.to_string(),
+                    _ => e.to_string(),
+                };
+                let line = record.position().map_or(fallback_line, |position| position.line());
+                errors.push(CsvError { line, message, record: Some(record) });
+            }
+        }
+    }
+
+    debug_println!(
+        "Loaded {} properties from {}, skipped {} malformed rows",
+        properties.len(),
+        path.display(),
+        errors.len()
+    );
+
+    Ok((properties, errors))
+}
+
+/// File next to the CSV at `path` that keeps the rows `--lenient-csv` skipped
+pub fn rejected_rows_path(path: &str) -> PathBuf {
+    Path::new(path).with_extension("rejected.csv")
+}
+
+/// Append the raw rows of `errors` to `rejected_rows_path(path)`, returning that path
+///
+/// Rows already in the file are not added again, so loading the same CSV
+/// several times before it is saved doesn't duplicate them. The header of
+/// `path` is written when the file is created.
+fn save_rejected_rows(path: &str, errors: &[CsvError]) -> Result<PathBuf> {
+    let rejected_path = rejected_rows_path(path);
+    let mut known = std::collections::HashSet::new();
+    if rejected_path.exists() {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_path(&rejected_path)
+            .with_context(|| format!("Failed to open rejected rows file: {}", rejected_path.display()))?;
+        for record in reader.records() {
+            known.insert(record?.iter().map(str::to_string).collect::<Vec<_>>());
+        }
+    }
+    let header = if known.is_empty() {
+        csv::Reader::from_path(path)?.headers()?.clone()
+    } else {
+        csv::StringRecord::new()
+    };
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&rejected_path)
+        .with_context(|| format!("Failed to open rejected rows file: {}", rejected_path.display()))?;
+    let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(file);
+    if !header.is_empty() {
+        writer.write_record(&header)?;
+    }
+    for record in errors.iter().filter_map(|error| error.record.as_ref()) {
+        if known.insert(record.iter().map(str::to_string).collect()) {
+            writer.write_record(record)?;
+        }
+    }
+    writer.flush()?;
+    Ok(rejected_path)
+}
+
+/// Load properties from a CSV file, failing on the first malformed row
+///
+/// With `lenient` malformed rows are skipped with a warning instead.
+pub fn load_properties_from_csv(path: &str, lenient: bool) -> Result<Vec<Property>> {
+    if lenient {
+        let (properties, errors) = load_properties_from_csv_lenient(path)?;
+        for error in &errors {
+            eprintln!("Warning: skipping malformed row in {} at {}", path, error);
+        }
+        if !errors.is_empty() {
+            let rejected_path = save_rejected_rows(path, &errors)?;
+            eprintln!(
+                "Warning: {} malformed rows were kept in {}, they are dropped from {} when it is saved again",
+                errors.len(),
+                rejected_path.display(),
+                path
+            );
+        }
+        return Ok(properties);
+    }
+
+    let path = Path::new(path);
+    let Some(mut reader) = open_csv_reader(path)? else {
+        return Ok(Vec::new());
+    };
+    let mut properties = Vec::new();
+
+    for result in reader.deserialize() {
+        let mut property: Property =
+            result.with_context(|| "Failed to deserialize property from CSV")?;
+        // Sanitize URL to remove query parameters and fragments for deduplication
+        property.url = sanitize_url(&property.url);
+        properties.push(property);
+    }
+
+    debug_println!(
+        "Loaded {} properties from {}",
+        properties.len(),
+        path.display()
+    );
+
+    Ok(properties)
+}
+
+/// File formats properties can be written to, as told by the file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Csv,
+    Json,
+    GeoJson,
+    Sqlite,
+    Xlsx,
+    Kml,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            OutputFormat::Csv => "CSV",
+            OutputFormat::Json => "JSON",
+            OutputFormat::GeoJson => "GeoJSON",
+            OutputFormat::Sqlite => "SQLite",
+            OutputFormat::Xlsx => "Excel",
+            OutputFormat::Kml => "KML",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Infer the output format from a path's extension, defaulting to CSV
+pub fn detect_output_format(path: &str) -> OutputFormat {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+    match extension.as_deref() {
+        Some("json") => OutputFormat::Json,
+        Some("geojson") => OutputFormat::GeoJson,
+        Some("db" | "sqlite" | "sqlite3") => OutputFormat::Sqlite,
+        Some("xlsx") => OutputFormat::Xlsx,
+        Some("kml") => OutputFormat::Kml,
+        _ => OutputFormat::Csv,
+    }
+}
+
+const BACKUP_PATH: &str = "properties_backup.csv";
+
+/// Save properties by overwriting `path` in place, see `save_properties_to_csv_atomic`
+pub fn save_properties_to_csv(properties: &[Property], path: &str) -> Result<()> {
+    let path_obj = Path::new(path);
+
+    // Create backup if file exists
+    if path_obj.exists() {
+        let backup_path = BACKUP_PATH;
+
+        copy(path, backup_path)
+            .with_context(|| format!("Failed to create backup: {}", backup_path))?;
+
+        debug_println!("Created backup: {}", backup_path);
+    }
+
+    let file =
+        File::create(path).with_context(|| format!("Failed to create CSV file: {}", path))?;
+
+    let mut writer = csv::Writer::from_writer(file);
+
+    for property in properties {
+        writer
+            .serialize(property)
+            .with_context(|| "Failed to serialize property to CSV")?;
+    }
+
+    writer
+        .flush()
+        .with_context(|| "Failed to flush CSV writer")?;
+
+    debug_println!("Saved {} properties to {}", properties.len(), path);
+
+    Ok(())
+}
+
+/// Read URLs from a text file, one per line
+///
+/// Blank lines and lines starting with '#' are skipped; URLs are sanitized and
+/// deduplicated while keeping their order.
+pub fn load_urls_from_file(path: &str) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read URL file: {}", path))?;
+
+    let mut seen = std::collections::HashSet::new();
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(sanitize_url)
+        .filter(|url| seen.insert(url.clone()))
+        .collect())
+}
+
+/// Whether a file name matches a glob pattern with `*` and `?` wildcards
+fn matches_glob(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    // Position after the last `*` and the name position it was matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_n)) => {
+                    backtrack = Some((star_p, star_n + 1));
+                    p = star_p;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Files in `dir` whose name matches a glob `pattern` like `properties_*.csv`
+///
+/// Only `*` and `?` wildcards are supported. The files are sorted by
+/// modification time, see [`sort_by_modified`].
+pub fn auto_discover_csv_files(dir: &str, pattern: &str) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("Failed to read directory: {}", dir))? {
+        let entry = entry?;
+        if entry.metadata()?.is_file() && matches_glob(pattern, &entry.file_name().to_string_lossy()) {
+            files.push(entry.path());
+        }
+    }
+    sort_by_modified(files)
+}
+
+/// Sort files by modification time, oldest first, so that merging them in order lets newer data win
+pub fn sort_by_modified(files: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    let mut files = files
+        .into_iter()
+        .map(|path| {
+            let modified = std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .with_context(|| format!("Failed to read modification time of {}", path.display()))?;
+            Ok((modified, path))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    files.sort();
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Whether a sitemap is a sitemap index and the `<loc>` entries it lists
+fn parse_sitemap(xml: &str) -> (bool, Vec<String>) {
+    let loc_regex = regex::Regex::new(r"(?s)<loc>\s*(?:<!\[CDATA\[)?(.*?)(?:\]\]>)?\s*</loc>").unwrap();
+    let locs = loc_regex
+        .captures_iter(xml)
+        .map(|c| c[1].trim().replace("&amp;", "&"))
+        .filter(|loc| !loc.is_empty())
+        .collect();
+    (xml.contains("<sitemapindex"), locs)
+}
+
+/// Load all URLs listed in a sitemap on the same domain as the sitemap itself
+///
+/// Sitemap indexes are followed into their child sitemaps. Child sitemaps
+/// that fail to load are skipped, as long as the top-level one loads. URLs are
+/// sanitized and deduplicated while keeping their order. The context's
+/// headers and next user agent are sent with every sitemap request.
+pub fn load_urls_from_sitemap(context: &mut ScrapingContext, sitemap_url: &str) -> Result<Vec<String>> {
+    let client = context
+        .http_client_builder()
+        .user_agent(context.next_user_agent())
+        .build()?;
+    let fetch = |url: &str| -> Result<String> {
+        let response = client
+            .get(url)
+            .send_logged()
+            .with_context(|| format!("Failed to fetch sitemap: {}", url))?
+            .error_for_status()
+            .with_context(|| format!("Failed to fetch sitemap: {}", url))?;
+        response.text().context("Failed to read sitemap")
+    };
+
+    let domain = domain_from_url(sitemap_url);
+    let mut visited = std::collections::HashSet::from([sitemap_url.to_string()]);
+    let mut pending = vec![fetch(sitemap_url)?];
+    let mut seen = std::collections::HashSet::new();
+    let mut urls = Vec::new();
+
+    while let Some(xml) = pending.pop() {
+        let (is_index, locs) = parse_sitemap(&xml);
+        for loc in locs {
+            if domain_from_url(&loc) != domain {
+                continue;
+            }
+            if is_index {
+                if !visited.insert(loc.clone()) {
+                    continue;
+                }
+                match fetch(&loc) {
+                    Ok(xml) => pending.push(xml),
+                    Err(e) => debug_println!("Skipping child sitemap {}: {:#}", loc, e),
+                }
+            } else {
+                let loc = sanitize_url(&loc);
+                if seen.insert(loc.clone()) {
+                    urls.push(loc);
+                }
+            }
+        }
+    }
+
+    debug_println!("Found {} URLs in sitemap {}", urls.len(), sitemap_url);
+    Ok(urls)
+}
+
+/// Save properties by writing to `<path>.tmp` and renaming it over `path`
+///
+/// A crash mid-write leaves the previous file intact, as rename is atomic on
+/// Unix. The backup is created the same way so it is never partially written.
+pub fn save_properties_to_csv_atomic(properties: &[Property], path: &str) -> Result<()> {
+    if Path::new(path).exists() {
+        copy_atomic(path, BACKUP_PATH)
+            .with_context(|| format!("Failed to create backup: {}", BACKUP_PATH))?;
+        debug_println!("Created backup: {}", BACKUP_PATH);
+    }
+
+    let tmp_path = format!("{}.tmp", path);
+    let file = File::create(&tmp_path)
+        .with_context(|| format!("Failed to create CSV file: {}", tmp_path))?;
+
+    let mut writer = csv::Writer::from_writer(file);
+    for property in properties {
+        writer
+            .serialize(property)
+            .with_context(|| "Failed to serialize property to CSV")?;
+    }
+
+    let file = writer
+        .into_inner()
+        .map_err(|e| anyhow::anyhow!("Failed to flush CSV writer: {}", e.error()))?;
+    file.sync_all()
+        .with_context(|| format!("Failed to sync CSV file: {}", tmp_path))?;
+
+    rename(&tmp_path, path)
+        .with_context(|| format!("Failed to move {} to {}", tmp_path, path))?;
+
+    debug_println!("Atomically saved {} properties to {}", properties.len(), path);
+
+    Ok(())
+}
+
+/// Write properties to a CSV file that is not the database, so no backup is made
+pub fn export_properties_to_csv(properties: &[Property], path: &str) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("Failed to create CSV file: {}", path))?;
+    for property in properties {
+        writer
+            .serialize(property)
+            .with_context(|| "Failed to serialize property to CSV")?;
+    }
+    writer.flush().with_context(|| "Failed to flush CSV writer")?;
+
+    debug_println!("Exported {} properties to {}", properties.len(), path);
+    Ok(())
+}
+
+/// Copy a file via a temporary file and rename so `to` is never partially written
+fn copy_atomic(from: &str, to: &str) -> Result<()> {
+    let tmp_path = format!("{}.tmp", to);
+    copy(from, &tmp_path)?;
+    File::open(&tmp_path)?.sync_all()?;
+    rename(&tmp_path, to)?;
+    Ok(())
+}
+
+/// Columns deserialized as text, which external tools may have written as bare numbers
+const GEOJSON_TEXT_COLUMNS: [&str; 22] = [
+    "url",
+    "name",
+    "price",
+    "location",
+    "location_municipality",
+    "location_district",
+    "location_region",
+    "property_type",
+    "listing_type",
+    "coordinates",
+    "address",
+    "size_living",
+    "size_ground",
+    "description",
+    "features",
+    "floor_level",
+    "agency_name",
+    "agency_phone",
+    "unavailable_reason",
+    "internal_id",
+    "page_fingerprint",
+    "plot_polygon",
+];
+
+/// Load properties from a GeoJSON FeatureCollection
+///
+/// Each Feature's `properties` object uses the CSV column names. The point in
+/// `geometry.coordinates` is in GeoJSON `[lng, lat]` order and takes precedence
+/// over any `coordinates` property; a Polygon geometry becomes the plot
+/// boundary. Numbers and booleans in text columns are accepted, as external
+/// tools often write them unquoted.
+pub fn load_properties_from_geojson(path: &str) -> Result<Vec<Property>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read GeoJSON file: {}", path))?;
+    let collection: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse GeoJSON file: {}", path))?;
+
+    if collection["type"] != "FeatureCollection" {
+        return Err(anyhow::anyhow!("{} is not a GeoJSON FeatureCollection", path));
+    }
+    let features = collection["features"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("{} has no features array", path))?;
+
+    let mut properties = Vec::new();
+    for (i, feature) in features.iter().enumerate() {
+        let mut record = feature["properties"].as_object().cloned().unwrap_or_default();
+        for (key, value) in record.iter_mut() {
+            if GEOJSON_TEXT_COLUMNS.contains(&key.as_str()) && (value.is_number() || value.is_boolean()) {
+                *value = value.to_string().into();
+            }
+        }
+
+        if feature["geometry"]["type"] == "Polygon" {
+            let ring = feature["geometry"]["coordinates"][0].as_array().and_then(|ring| {
+                ring.iter()
+                    .map(|point| Some((point.get(1)?.as_f64()?, point.get(0)?.as_f64()?)))
+                    .collect::<Option<Vec<_>>>()
+            });
+            if let Some(ring) = ring {
+                record.insert("plot_polygon".to_string(), polygon_to_wkt(&ring).into());
+            }
+        }
+
+        let point = feature["geometry"]["coordinates"]
+            .as_array()
+            .and_then(|c| Some((c.first()?.as_f64()?, c.get(1)?.as_f64()?)));
+        let coordinates = point
+            .and_then(|(lng, lat)| Coordinates::new(lat, lng).ok())
+            .map(|c| c.to_string());
+        match coordinates {
+            Some(c) => {
+                record.insert("coordinates".to_string(), c.into());
+            }
+            None if !record.get("coordinates").is_some_and(|c| c.is_string()) => {
+                record.insert("coordinates".to_string(), "".into());
+            }
+            None => {}
+        }
+
+        let mut property: Property = serde_json::from_value(serde_json::Value::Object(record))
+            .with_context(|| format!("Failed to deserialize GeoJSON feature {}", i))?;
+        property.url = sanitize_url(&property.url);
+        properties.push(property);
+    }
+
+    debug_println!("Loaded {} properties from {}", properties.len(), path);
+    Ok(properties)
+}
+
+/// Save properties as a GeoJSON FeatureCollection with one Point per property
+///
+/// Properties with a plot boundary get it as Polygon geometry instead, and
+/// keep their point in the `coordinates` property. Properties without
+/// coordinates get a `null` geometry so no data is lost.
+pub fn save_properties_to_geojson(properties: &[Property], path: &str) -> Result<()> {
+    let features = properties
+        .iter()
+        .map(|property| {
+            let mut record = property.to_json()?;
+            if let Some(record) = record.as_object_mut() {
+                record.remove("plot_polygon");
+                match (&property.plot_polygon, property.coordinates) {
+                    (Some(_), Some(c)) => {
+                        record.insert("coordinates".to_string(), c.to_string().into());
+                    }
+                    _ => {
+                        record.remove("coordinates");
+                    }
+                }
+            }
+            let geometry = match &property.plot_polygon {
+                Some(polygon) => Some(serde_json::json!({
+                    "type": "Polygon",
+                    "coordinates": [polygon.iter().map(|(lat, lng)| [*lng, *lat]).collect::<Vec<_>>()],
+                })),
+                None => property.coordinates.map(|c| {
+                    serde_json::json!({ "type": "Point", "coordinates": [c.lng, c.lat] })
+                }),
+            };
+            Ok(serde_json::json!({ "type": "Feature", "geometry": geometry, "properties": record }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let collection = serde_json::json!({ "type": "FeatureCollection", "features": features });
+    std::fs::write(path, serde_json::to_string_pretty(&collection)?)
+        .with_context(|| format!("Failed to write GeoJSON file: {}", path))?;
+
+    debug_println!("Saved {} properties to {}", properties.len(), path);
+    Ok(())
+}
+
+/// Save properties as a JSON array, with coordinates as `{"lat", "lng"}` objects
+pub fn save_properties_to_json(properties: &[Property], path: &str) -> Result<()> {
+    let records = properties
+        .iter()
+        .map(Property::to_json)
+        .collect::<serde_json::Result<Vec<_>>>()?;
+    std::fs::write(path, serde_json::to_string_pretty(&records)?)
+        .with_context(|| format!("Failed to write JSON file: {}", path))?;
+
+    debug_println!("Saved {} properties to {}", properties.len(), path);
+    Ok(())
+}
+
+/// Save properties as a Parquet file with typed columns
+///
+/// Prices, sizes, rooms and distances are `Float64`, dates `Date32` and
+/// coordinates split into `lat` and `lng` columns; everything else is `Utf8`.
+/// Features are pipe-delimited as in the CSV.
+#[cfg(feature = "parquet")]
+pub fn save_properties_to_parquet(properties: &[Property], path: &str) -> Result<()> {
+    use arrow_array::{ArrayRef, Date32Array, Float64Array, RecordBatch, StringArray, UInt16Array, UInt8Array};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    let strings = |f: fn(&Property) -> Option<String>| -> ArrayRef {
+        Arc::new(properties.iter().map(f).collect::<StringArray>())
+    };
+    let floats = |f: fn(&Property) -> Option<f64>| -> ArrayRef {
+        Arc::new(properties.iter().map(f).collect::<Float64Array>())
+    };
+    let dates = |f: fn(&Property) -> Option<chrono::NaiveDate>| -> ArrayRef {
+        Arc::new(
+            properties
+                .iter()
+                .map(|p| f(p).map(|date| (date - epoch).num_days() as i32))
+                .collect::<Date32Array>(),
+        )
+    };
+
+    let columns: Vec<(&str, DataType, ArrayRef)> = vec![
+        ("url", DataType::Utf8, strings(|p| Some(p.url.clone()))),
+        ("internal_id", DataType::Utf8, strings(|p| p.internal_id.clone())),
+        ("name", DataType::Utf8, strings(|p| Some(p.name.clone()))),
+        ("price", DataType::Float64, floats(|p| p.price.euros())),
+        ("location", DataType::Utf8, strings(|p| Some(p.location.to_string()))),
+        ("property_type", DataType::Utf8, strings(|p| Some(p.property_type.to_string()))),
+        ("listing_type", DataType::Utf8, strings(|p| Some(p.listing_type.to_string()))),
+        ("unavailable_reason", DataType::Utf8, strings(|p| p.unavailable_reason.map(|r| r.to_string()))),
+        ("date", DataType::Date32, dates(|p| p.date)),
+        ("lat", DataType::Float64, floats(|p| p.coordinates.map(|c| c.lat))),
+        ("lng", DataType::Float64, floats(|p| p.coordinates.map(|c| c.lng))),
+        (
+            "plot_polygon",
+            DataType::Utf8,
+            strings(|p| p.plot_polygon.as_deref().map(polygon_to_wkt)),
+        ),
+        (
+            "quality_score",
+            DataType::UInt8,
+            Arc::new(properties.iter().map(|p| p.quality_score).collect::<UInt8Array>()),
+        ),
+        ("description", DataType::Utf8, strings(|p| p.description.clone())),
+        ("features", DataType::Utf8, strings(|p| Some(p.features.join("|")))),
+        ("driving_distance_km", DataType::Float64, floats(|p| p.driving_distance_km)),
+        ("rooms", DataType::Float64, floats(|p| p.rooms)),
+        ("floor_level", DataType::Utf8, strings(|p| p.floor_level.clone())),
+        (
+            "construction_year",
+            DataType::UInt16,
+            Arc::new(properties.iter().map(|p| p.construction_year).collect::<UInt16Array>()),
+        ),
+        ("agency_name", DataType::Utf8, strings(|p| p.agency_name.clone())),
+        ("agency_phone", DataType::Utf8, strings(|p| p.agency_phone.clone())),
+        ("address", DataType::Utf8, strings(|p| p.address.clone())),
+        ("size_living", DataType::Float64, floats(|p| p.parsed_living_size().map(|size| size.sqm))),
+        ("size_ground", DataType::Float64, floats(|p| p.parsed_ground_size().map(|size| size.sqm))),
+        ("first_seen", DataType::Date32, dates(|p| p.first_seen)),
+        ("last_seen", DataType::Date32, dates(|p| p.last_seen)),
+        ("completeness_score", DataType::Float64, floats(|p| Some(data_completeness_score(p)))),
+        (
+            "property_type_confidence",
+            DataType::Float64,
+            floats(|p| Some(f64::from(p.property_type.confidence))),
+        ),
+    ];
+
+    let schema = Arc::new(Schema::new(
+        columns
+            .iter()
+            .map(|(name, data_type, _)| Field::new(*name, data_type.clone(), true))
+            .collect::<Vec<_>>(),
+    ));
+    let batch = RecordBatch::try_new(schema.clone(), columns.into_iter().map(|(_, _, array)| array).collect())
+        .context("Failed to build Parquet record batch")?;
+
+    let file = File::create(path).with_context(|| format!("Failed to create Parquet file: {}", path))?;
+    let mut writer = parquet::arrow::ArrowWriter::try_new(file, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+
+    debug_println!("Saved {} properties to {}", properties.len(), path);
+    Ok(())
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn save_properties_to_parquet(_properties: &[Property], _path: &str) -> Result<()> {
+    Err(anyhow::anyhow!("Parquet export requires building with --features parquet"))
+}
+
+/// Return references to all properties matching the given filter, preserving order
+pub fn filter_properties<'a>(properties: &'a [Property], filter: &PropertyFilter) -> Vec<&'a Property> {
+    properties.iter().filter(|p| filter.matches(p)).collect()
+}
+
+/// Run a user-provided shell command with the output file path as its argument
+///
+/// The path is passed as a separate argument (`$1` for `sh`), so it needs no quoting.
+pub fn run_post_hook_command(command: &str, output_path: &str) -> Result<()> {
+    debug_println!("Running post-scrape hook: {} {}", command, output_path);
+
+    #[cfg(windows)]
+    let status = std::process::Command::new("cmd")
+        .args(["/C", &format!("{} \"{}\"", command, output_path)])
+        .status();
+    #[cfg(not(windows))]
+    let status = std::process::Command::new("sh")
+        .args(["-c", &format!("{} \"$1\"", command), "sh", output_path])
+        .status();
+
+    let status = status.with_context(|| format!("Failed to run post-scrape hook: {}", command))?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("Post-scrape hook '{}' exited with {}", command, status));
+    }
+    Ok(())
+}
+
+/// Append URLs to a text file, one URL per line
+///
+/// The file is created if it doesn't exist. URLs are appended so that multiple
+/// scrapers can export into the same file during a single run.
+pub fn export_urls(urls: &[String], path: &str) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open URL export file: {}", path))?;
+
+    for url in urls {
+        writeln!(file, "{}", url).with_context(|| format!("Failed to write URL to {}", path))?;
+    }
+
+    Ok(())
+}
+
+pub const OSRM_ROUTE_URL: &str = "https://router.project-osrm.org/route/v1/driving";
+
+/// Driving distance in km between two `(lat, lng)` points via the public OSRM demo server
+pub fn compute_driving_distance(from: (f64, f64), to: (f64, f64)) -> Result<f64> {
+    // OSRM expects lng,lat pairs
+    let url = format!(
+        "{}/{},{};{},{}?overview=false",
+        OSRM_ROUTE_URL, from.1, from.0, to.1, to.0
+    );
+    let _t = crate::debug::Tracer::new("fetch_osrm_route", 5000);
+    let response: serde_json::Value = http_client_builder(&[])
+        .user_agent("LaendleFinder/1.0 (Real Estate Scraper)")
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?
+        .get(&url)
+        .send_logged()
+        .context("Failed to query OSRM")?
+        .json()
+        .context("Failed to parse OSRM response")?;
+
+    if response["code"] != "Ok" {
+        return Err(anyhow::anyhow!("OSRM returned {}", response["code"]));
+    }
+    let meters = response["routes"][0]["distance"]
+        .as_f64()
+        .context("OSRM response contains no route")?;
+    Ok(meters / 1000.0)
+}
+
+/// Append properties as Markdown cards to a notes file, separated by rules
+pub fn append_properties_markdown(properties: &[&Property], path: &str) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open Markdown file: {}", path))?;
+
+    for property in properties {
+        writeln!(file, "{}\n---\n", property.to_markdown())
+            .with_context(|| format!("Failed to write Markdown to {}", path))?;
+    }
+
+    Ok(())
+}
+
+/// Copy a file to a timestamped sibling like `properties.csv.bak-20250601-120000`
+///
+/// Works for every output format, since the file is copied as is. Returns the
+/// backup's path, or None if there is no file to back up yet.
+pub fn backup_file(path: &str) -> Result<Option<PathBuf>> {
+    if !Path::new(path).exists() {
+        return Ok(None);
+    }
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let backup_path = PathBuf::from(format!("{}.bak-{}", path, timestamp));
+    copy(path, &backup_path).with_context(|| format!("Failed to create backup: {}", backup_path.display()))?;
+    debug_println!("Created backup: {}", backup_path.display());
+    Ok(Some(backup_path))
+}
+
+/// Remove sold/unavailable properties whose date is more than `days` days ago
+///
+/// Returns the remaining properties and the number of pruned entries. Properties
+/// without a date are always kept. In dry-run mode nothing is removed, but the
+/// returned count is the number of properties that would have been pruned.
+pub fn prune_properties(properties: Vec<Property>, days: u32, dry_run: bool) -> (Vec<Property>, usize) {
+    let cutoff = chrono::Utc::now().naive_utc().date() - chrono::Duration::days(days as i64);
+    let is_prunable = |p: &Property| {
+        !p.is_available() && p.date.is_some_and(|date| date < cutoff)
+    };
+
+    let pruned_count = properties.iter().filter(|p| is_prunable(p)).count();
+    if dry_run {
+        return (properties, pruned_count);
+    }
+
+    let remaining = properties.into_iter().filter(|p| !is_prunable(p)).collect();
+    (remaining, pruned_count)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::ListingType;
+
+    #[test]
+    fn test_coordinate_bounding_boxes() {
+        // Dornbirn, Lindau just across the border, Innsbruck, Zurich
+        assert!(is_coordinate_in_vorarlberg(47.4125, 9.7417));
+        assert!(is_coordinate_in_vorarlberg(47.5460, 9.6830));
+        assert!(!is_coordinate_in_vorarlberg(47.2692, 11.4041));
+        assert!(!is_coordinate_in_vorarlberg(47.3769, 8.5417));
+
+        assert!(is_coordinate_in_austria(47.2692, 11.4041));
+        assert!(is_coordinate_in_austria(48.2082, 16.3738));
+        assert!(!is_coordinate_in_austria(52.52, 13.405));
+    }
 
     #[test]
     fn test_sanitize_url() {
@@ -163,105 +1358,492 @@ mod tests {
         );
     }
 
+
     #[test]
-    fn test_get_random_user_agent() {
-        // Test that the function returns a valid user agent
-        let user_agent = get_random_user_agent();
-        assert!(!user_agent.is_empty());
-        assert!(user_agent.starts_with("Mozilla/"));
-        
-        // Test that different calls may return different user agents (not guaranteed but likely)
-        let user_agents: std::collections::HashSet<&str> = (0..10)
-            .map(|_| get_random_user_agent())
-            .collect();
-        
-        // With 20 different user agents, getting at least 2 different ones in 10 calls is very likely
-        // This is probabilistic but should work in practice
-        assert!(user_agents.len() >= 1); // At minimum we get one valid user agent
-        
-        // Verify some common browser identifiers appear in our pool
-        let all_agents = [
-            get_random_user_agent(),
-            get_random_user_agent(),
-            get_random_user_agent(),
-            get_random_user_agent(),
-            get_random_user_agent(),
-        ];
-        
-        let has_chrome = all_agents.iter().any(|ua| ua.contains("Chrome"));
-        let has_firefox = all_agents.iter().any(|ua| ua.contains("Firefox"));
-        
-        // Due to randomness, we can't guarantee both will appear, but at least one should
-        assert!(has_chrome || has_firefox, "Should contain Chrome or Firefox user agents");
+    fn test_http_headers_sent_with_requests() {
+        let request_head = |configured: &[(String, String)]| {
+            let (url, server) = serve_once("200 OK", "ok");
+            http_client_builder(configured).build().unwrap().get(&url).send().unwrap();
+            server.join().unwrap()
+        };
+
+        let head = request_head(&[]);
+        assert!(head.contains("accept-language: de-at,de;q=0.9\r\n"), "{}", head);
+        assert!(head.contains("accept-encoding: gzip, deflate, br\r\n"), "{}", head);
+
+        let head = request_head(&[
+            ("Accept-Language".to_string(), "en".to_string()),
+            ("X-Debug".to_string(), "1".to_string()),
+        ]);
+        assert!(head.contains("accept-language: en\r\n"), "{}", head);
+        assert!(!head.contains("de-at"), "{}", head);
+        assert!(head.contains("x-debug: 1\r\n"), "{}", head);
     }
 
-}
+    #[test]
+    fn test_domain_from_url() {
+        assert_eq!(domain_from_url("https://www.vol.at/some/article"), "vol.at");
+        assert_eq!(domain_from_url("https://www.laendleimmo.at/immobilien?page=2"), "laendleimmo.at");
+        assert_eq!(domain_from_url("http://localhost:8080/x"), "localhost");
+        assert_eq!(domain_from_url("vol.at/x"), "vol.at");
+    }
 
+    #[test]
+    fn test_extract_all_json_scripts() {
+        let document = scraper::Html::parse_document(r#"<html><head>
+            <script type="application/ld+json">{"@type": "House", "name": "Haus"}</script>
+            <script type="application/json" id="topicDataNode">[1, 2]</script>
+            <script type="application/json">{"broken": </script>
+            <script>var notJson = {"a": 1};</script>
+        </head><body><script type="application/json">{"post": {"title": "Titel"}}</script></body></html>"#);
 
-pub fn load_properties_from_csv(path: &str) -> Result<Vec<Property>> {
-    let path = Path::new(path);
+        let json = extract_all_json_scripts(&document);
+        assert_eq!(json, vec![
+            serde_json::json!({"@type": "House", "name": "Haus"}),
+            serde_json::json!([1, 2]),
+            serde_json::json!({"post": {"title": "Titel"}}),
+        ]);
+    }
 
-    // If the file doesn't exist, return an empty vector
-    if !path.exists() {
-        debug_println!(
-            "CSV file {} does not exist, creating a new one",
-            path.display()
+    #[test]
+    fn test_matches_glob() {
+        assert!(matches_glob("properties_*.csv", "properties_laptop.csv"));
+        assert!(matches_glob("properties_*.csv", "properties_.csv"));
+        assert!(matches_glob("*.csv", "a.b.csv"));
+        assert!(matches_glob("run?.csv", "run1.csv"));
+        assert!(matches_glob("*_*_x", "a_b_c_x"));
+        assert!(!matches_glob("properties_*.csv", "properties_laptop.csv.bak"));
+        assert!(!matches_glob("properties_*.csv", "other_laptop.csv"));
+        assert!(!matches_glob("run?.csv", "run10.csv"));
+    }
+
+    #[test]
+    fn test_auto_discover_csv_files() {
+        let dir = std::env::temp_dir().join(format!("laendlefinder-discover-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, age_secs) in [("properties_b.csv", 100), ("properties_a.csv", 10), ("notes.txt", 0), ("properties_c.csv", 50)] {
+            let path = dir.join(name);
+            std::fs::write(&path, "").unwrap();
+            let modified = std::time::SystemTime::now() - std::time::Duration::from_secs(age_secs);
+            File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+        }
+
+        let files = auto_discover_csv_files(dir.to_str().unwrap(), "properties_*.csv").unwrap();
+        let sorted = sort_by_modified(vec![dir.join("properties_a.csv"), dir.join("notes.txt"), dir.join("properties_b.csv")]);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+
+        let names: Vec<_> = files.iter().map(|f| f.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, vec!["properties_b.csv", "properties_c.csv", "properties_a.csv"]);
+
+        let sorted = sorted.unwrap();
+        let names: Vec<_> = sorted.iter().map(|f| f.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, vec!["properties_b.csv", "properties_a.csv", "notes.txt"]);
+    }
+
+    #[test]
+    fn test_detect_captcha() {
+        for page in [
+            r#"<html><head><title>Just a moment...</title></head><body><div id="cf-challenge-running"></div></body></html>"#,
+            r#"<html><head><title>Einen Moment bitte...</title></head><body></body></html>"#,
+            r#"<script>window._cf_chl_opt={cvId: '3', cType: 'managed'};</script>"#,
+            "<html><head><title lang=\"en\">Please verify you're human</title></head></html>",
+            "<p>Überprüfen Sie, ob Sie ein Mensch sind, indem Sie die folgende Aktion ausführen.</p>",
+        ] {
+            assert!(detect_captcha(page), "{}", page);
+        }
+        for page in [
+            "<html><body><h1>Einfamilienhaus in Dornbirn</h1><p>Bitte überprüfen Sie die Unterlagen.</p></body></html>",
+            r#"<script type="application/ld+json">{"@type": "House"}</script>"#,
+            // A listing with a reCAPTCHA protected contact form and Cloudflare's page script
+            r#"<html><head><title>Wohnung in Bregenz</title>
+            <script src="https://www.google.com/recaptcha/api.js" async defer></script>
+            <script src="/cdn-cgi/challenge-platform/scripts/jsd/main.js"></script></head>
+            <body><h1>Wohnung in Bregenz</h1><form id="contact"><div class="g-recaptcha" data-sitekey="x"></div>
+            <p>Please verify you are human before sending.</p></form></body></html>"#,
+        ] {
+            assert!(!detect_captcha(page), "{}", page);
+        }
+    }
+
+    #[test]
+    fn test_parse_sitemap() {
+        let index = r#"<?xml version="1.0" encoding="UTF-8"?>
+<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://www.laendleimmo.at/sitemap-1.xml</loc></sitemap>
+  <sitemap><loc>
+    https://www.laendleimmo.at/sitemap-2.xml
+  </loc></sitemap>
+</sitemapindex>"#;
+        assert_eq!(parse_sitemap(index), (true, vec![
+            "https://www.laendleimmo.at/sitemap-1.xml".to_string(),
+            "https://www.laendleimmo.at/sitemap-2.xml".to_string(),
+        ]));
+
+        let urlset = r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://www.vol.at/haus-dornbirn/123</loc><lastmod>2024-05-01</lastmod></url>
+  <url><loc><![CDATA[https://www.vol.at/suche?q=a&amp;b]]></loc></url>
+  <url><loc></loc></url>
+</urlset>"#;
+        assert_eq!(parse_sitemap(urlset), (false, vec![
+            "https://www.vol.at/haus-dornbirn/123".to_string(),
+            "https://www.vol.at/suche?q=a&b".to_string(),
+        ]));
+    }
+
+    #[test]
+    fn test_user_agent_rotator_does_not_repeat_recent() {
+        let pool: Vec<String> = ["a", "b", "c", "d"].iter().map(|ua| ua.to_string()).collect();
+        let mut rotator = UserAgentRotator::new(pool, 3);
+        let picked: Vec<String> = (0..40).map(|_| rotator.next().to_string()).collect();
+        for window in picked.windows(4) {
+            let unique: std::collections::HashSet<&String> = window.iter().collect();
+            assert_eq!(unique.len(), 4, "repeated within {:?}", window);
+        }
+
+        // A window larger than the pool is capped instead of running dry
+        let mut rotator = UserAgentRotator::new(vec!["only".to_string()], 5);
+        assert_eq!(rotator.next(), "only");
+        assert_eq!(rotator.next(), "only");
+
+        let rotator = UserAgentRotator::with_additional(&["Custom/1.0".to_string()], 5);
+        assert_eq!(rotator.pool.len(), USER_AGENTS.len() + 1);
+    }
+
+    #[test]
+    fn test_user_agent_pool() {
+        assert_eq!(user_agent_pool(None).len(), USER_AGENTS.len());
+        assert_eq!(user_agent_pool(Some(" | ")).len(), USER_AGENTS.len());
+        assert_eq!(user_agent_pool(Some("Agent/1.0 | Agent/2.0|")), vec!["Agent/1.0", "Agent/2.0"]);
+    }
+
+    #[test]
+    fn test_page_fingerprint() {
+        let page = |price: &str, noise: &str| {
+            format!(
+                r#"<html><head><title>{noise}</title>
+                <script type="application/ld+json">{{"@type": "Residence", "offers": {{"price": "{price}"}}}}</script>
+                </head><body><nav>{noise}</nav><main>
+                <h1>Wohnung in Dornbirn</h1><p>Verkauft am <time>12.10.2026</time> um 14:32</p>
+                <script>var session = "{noise}";</script><img src="https://ads.example/pixel?{noise}">
+                </main></body></html>"#
+            )
+        };
+        let fingerprint = page_fingerprint(&page("450000", "a1b2c3"));
+        assert_eq!(fingerprint.len(), 64);
+        assert_eq!(page_fingerprint(&page("450000", "d4e5f6")), fingerprint);
+        assert_eq!(page_fingerprint(&page("450000", "x").replace("14:32", "09:05")), fingerprint);
+        assert_eq!(
+            page_fingerprint(&page("450000", "x").replace(r#""@type": "Residence", "#, r#""@type":"Residence","#)),
+            fingerprint
         );
-        return Ok(Vec::new());
+        // The price is only in the JSON-LD, the date only in a time element
+        assert_ne!(page_fingerprint(&page("430000", "a1b2c3")), fingerprint);
+        assert_ne!(page_fingerprint(&page("450000", "a1b2c3").replace("12.10.2026", "13.10.2026")), fingerprint);
     }
 
-    let file =
-        File::open(path).with_context(|| format!("Failed to open CSV file: {}", path.display()))?;
+    #[cfg(unix)]
+    #[test]
+    fn test_run_post_hook_command() {
+        assert!(run_post_hook_command("test -n", "/tmp/with space.csv").is_ok());
+        assert!(run_post_hook_command("test \"/tmp/with space.csv\" =", "/tmp/with space.csv").is_ok());
+        assert!(run_post_hook_command("false", "properties.csv").is_err());
+    }
 
-    let mut reader = csv::Reader::from_reader(file);
-    let mut properties = Vec::new();
+    fn property(url: &str, listing_type: ListingType, age_days: Option<i64>) -> Property {
+        let today = chrono::Utc::now().naive_utc().date();
+        Property {
+            url: url.to_string(),
+            internal_id: None,
+            page_fingerprint: None,
+            completeness_score: None,
+            name: String::new(),
+            price: crate::models::Price::from_str("100000"),
+            location: crate::models::Location::new("Dornbirn"),
+            property_type: crate::models::PropertyKind::House.into(),
+            listing_type,
+            unavailable_reason: None,
+            date: age_days.map(|d| today - chrono::Duration::days(d)),
+            coordinates: None,
+            quality_score: None,
+            plot_polygon: None,
+            description: None,
+            features: Vec::new(),
+            driving_distance_km: None,
+            rooms: None,
+            floor_level: None,
+            construction_year: None,
+            agency_name: None,
+            agency_phone: None,
+            address: None,
+            size_living: None,
+            size_ground: None,
+            first_seen: None,
+            last_seen: None,
+        }
+    }
 
-    for result in reader.deserialize() {
-        let mut property: Property =
-            result.with_context(|| "Failed to deserialize property from CSV")?;
-        // Sanitize URL to remove query parameters and fragments for deduplication
-        property.url = sanitize_url(&property.url);
-        properties.push(property);
+    #[test]
+    fn test_prune_properties() {
+        let properties = vec![
+            property("old-sold", ListingType::Sold, Some(400)),
+            property("old-unavailable", ListingType::Unavailable, Some(400)),
+            property("old-available", ListingType::Available, Some(400)),
+            property("recent-sold", ListingType::Sold, Some(10)),
+            property("undated-sold", ListingType::Sold, None),
+        ];
+
+        let (kept, pruned) = prune_properties(properties.clone(), 365, true);
+        assert_eq!(pruned, 2);
+        assert_eq!(kept.len(), 5);
+
+        let (kept, pruned) = prune_properties(properties, 365, false);
+        assert_eq!(pruned, 2);
+        let urls: Vec<&str> = kept.iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(urls, vec!["old-available", "recent-sold", "undated-sold"]);
     }
 
-    debug_println!(
-        "Loaded {} properties from {}",
-        properties.len(),
-        path.display()
-    );
+    #[test]
+    fn test_backup_file() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-backup-{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+        assert!(backup_file(path).unwrap().is_none());
 
-    Ok(properties)
-}
+        std::fs::write(path, "stored").unwrap();
+        let backup = backup_file(path).unwrap().unwrap();
+        let content = std::fs::read_to_string(&backup).unwrap();
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(&backup).unwrap();
 
-pub fn save_properties_to_csv(properties: &[Property], path: &str) -> Result<()> {
-    let path_obj = Path::new(path);
+        assert_eq!(content, "stored");
+        assert!(backup.to_str().unwrap().starts_with(&format!("{}.bak-", path)), "{}", backup.display());
+    }
 
-    // Create backup if file exists
-    if path_obj.exists() {
-        let backup_path = "properties_backup.csv";
+    #[test]
+    fn test_save_properties_to_csv_atomic_leaves_no_tmp_file() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-atomic-{}.csv", std::process::id()));
+        let path = path.to_str().unwrap();
 
-        copy(path, backup_path)
-            .with_context(|| format!("Failed to create backup: {}", backup_path))?;
+        save_properties_to_csv_atomic(&[], path).unwrap();
 
-        debug_println!("Created backup: {}", backup_path);
+        assert!(Path::new(path).exists());
+        assert!(!Path::new(&format!("{}.tmp", path)).exists());
+        assert!(load_properties_from_csv(path, false).unwrap().is_empty());
+        std::fs::remove_file(path).unwrap();
     }
 
-    let file =
-        File::create(path).with_context(|| format!("Failed to create CSV file: {}", path))?;
+    #[test]
+    fn test_load_properties_from_geojson() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-{}.geojson", std::process::id()));
+        let path = path.to_str().unwrap();
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": [9.64, 47.27] },
+                "properties": {
+                    "url": "https://www.vol.at/test?ref=map",
+                    "name": "Haus in Rankweil",
+                    "price": 450000,
+                    "location_municipality": "Rankweil",
+                    "property_type": "house",
+                    "listing_type": "sold",
+                    "size_living": 120
+                }
+            }, {
+                "type": "Feature",
+                "geometry": null,
+                "properties": {
+                    "url": "https://www.vol.at/other",
+                    "price": "unknown",
+                    "property_type": "land",
+                    "listing_type": "available"
+                }
+            }]
+        }"#;
+        std::fs::write(path, geojson).unwrap();
 
-    let mut writer = csv::Writer::from_writer(file);
+        let loaded = load_properties_from_geojson(path).unwrap();
+        std::fs::remove_file(path).unwrap();
 
-    for property in properties {
-        writer
-            .serialize(property)
-            .with_context(|| "Failed to serialize property to CSV")?;
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].url, "https://www.vol.at/test");
+        assert_eq!(loaded[0].price.euros(), Some(450000.0));
+        assert_eq!(loaded[0].size_living.as_deref(), Some("120"));
+        let coordinates = loaded[0].coordinates.unwrap();
+        assert_eq!((coordinates.lat, coordinates.lng), (47.27, 9.64));
+        assert_eq!(loaded[1].coordinates, None);
     }
 
-    writer
-        .flush()
-        .with_context(|| "Failed to flush CSV writer")?;
+    #[test]
+    fn test_geojson_round_trip() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-rt-{}.geojson", std::process::id()));
+        let path = path.to_str().unwrap();
+        let mut property = property("https://www.vol.at/rt", ListingType::Sold, Some(3));
+        property.coordinates = Some(Coordinates::new(47.41, 9.74).unwrap());
+        property.property_type.confidence = 0.7;
+        property.rooms = Some(3.5);
+        property.construction_year = Some(1990);
+        property.driving_distance_km = Some(12.4);
+        property.quality_score = Some(80);
 
-    debug_println!("Saved {} properties to {}", properties.len(), path);
+        save_properties_to_geojson(std::slice::from_ref(&property), path).unwrap();
+        let loaded = load_properties_from_geojson(path).unwrap();
+        std::fs::remove_file(path).unwrap();
 
-    Ok(())
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].url, property.url);
+        assert_eq!(loaded[0].coordinates, property.coordinates);
+        assert_eq!(loaded[0].date, property.date);
+        assert_eq!(loaded[0].property_type, property.property_type);
+        assert_eq!(loaded[0].rooms, Some(3.5));
+        assert_eq!(loaded[0].construction_year, Some(1990));
+        assert_eq!(loaded[0].driving_distance_km, Some(12.4));
+        assert_eq!(loaded[0].quality_score, Some(80));
+    }
+
+    #[test]
+    fn test_load_properties_from_csv_lenient() {
+        let path = std::env::temp_dir().join(format!("laendlefinder-lenient-{}.csv", std::process::id()));
+        let path = path.to_str().unwrap();
+        let properties = vec![
+            property("https://www.vol.at/a", ListingType::Sold, Some(3)),
+            property("https://www.vol.at/b", ListingType::Sold, Some(2)),
+        ];
+        export_properties_to_csv(&properties, path).unwrap();
+        // Break the listing type of the first row, as a manual edit might
+        let content = std::fs::read_to_string(path).unwrap();
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        lines[1] = lines[1].replacen(",sold,", ",verkauft,", 1);
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+
+        let strict = load_properties_from_csv(path, false);
+        let (loaded, errors) = load_properties_from_csv_lenient(path).unwrap();
+        save_rejected_rows(path, &errors).unwrap();
+        save_rejected_rows(path, &errors).unwrap();
+        let rejected = std::fs::read_to_string(rejected_rows_path(path)).unwrap();
+        std::fs::remove_file(rejected_rows_path(path)).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(strict.is_err());
+        assert_eq!(rejected, format!("{}\n{}\n", lines[0], lines[1]));
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].url, "https://www.vol.at/b");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 2);
+        assert!(errors[0].to_string().starts_with("line 2: "), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_plot_polygon_in_csv_and_geojson() {
+        let dir = std::env::temp_dir();
+        let csv_path = dir.join(format!("laendlefinder-plot-{}.csv", std::process::id()));
+        let geojson_path = dir.join(format!("laendlefinder-plot-{}.geojson", std::process::id()));
+        let mut property = property("https://www.laendleimmo.at/plot", ListingType::Available, None);
+        property.coordinates = Some(Coordinates::new(47.413, 9.742).unwrap());
+        property.plot_polygon = Some(vec![(47.412, 9.741), (47.412, 9.743), (47.414, 9.743), (47.412, 9.741)]);
+
+        let record = property.to_json().unwrap();
+        assert_eq!(record["plot_polygon"], "POLYGON((9.741 47.412, 9.743 47.412, 9.743 47.414, 9.741 47.412))");
+
+        export_properties_to_csv(std::slice::from_ref(&property), csv_path.to_str().unwrap()).unwrap();
+        let from_csv = load_properties_from_csv(csv_path.to_str().unwrap(), false).unwrap();
+        save_properties_to_geojson(std::slice::from_ref(&property), geojson_path.to_str().unwrap()).unwrap();
+        let geojson: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&geojson_path).unwrap()).unwrap();
+        let from_geojson = load_properties_from_geojson(geojson_path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&csv_path).unwrap();
+        std::fs::remove_file(&geojson_path).unwrap();
+
+        assert_eq!(from_csv[0].plot_polygon, property.plot_polygon);
+        assert_eq!(geojson["features"][0]["geometry"]["type"], "Polygon");
+        assert_eq!(geojson["features"][0]["geometry"]["coordinates"][0][1], serde_json::json!([9.743, 47.412]));
+        assert_eq!(from_geojson[0].plot_polygon, property.plot_polygon);
+        assert_eq!(from_geojson[0].coordinates, property.coordinates);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_save_properties_to_parquet() {
+        use arrow_array::{Array, Date32Array, Float64Array, StringArray};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let path = std::env::temp_dir().join(format!("laendlefinder-{}.parquet", std::process::id()));
+        let mut with_coordinates = property("https://www.vol.at/a", ListingType::Sold, Some(3));
+        with_coordinates.coordinates = Some(Coordinates::new(47.41, 9.74).unwrap());
+        with_coordinates.price = crate::models::Price::from_str("450000");
+        let without = property("https://www.vol.at/b", ListingType::Sold, None);
+
+        save_properties_to_parquet(&[with_coordinates.clone(), without], path.to_str().unwrap()).unwrap();
+        let file = File::open(&path).unwrap();
+        let batch = ParquetRecordBatchReaderBuilder::try_new(file).unwrap().build().unwrap().next().unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        let column = |name: &str| batch.column(batch.schema().index_of(name).unwrap()).clone();
+        let urls = column("url");
+        let urls = urls.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(urls.value(0), "https://www.vol.at/a");
+        let price = column("price");
+        let price = price.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(price.value(0), 450000.0);
+        let lat = column("lat");
+        let lat = lat.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(lat.value(0), 47.41);
+        assert!(lat.is_null(1));
+        let date = column("date");
+        let date = date.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(date.value_as_date(0), with_coordinates.date);
+    }
+
+    #[test]
+    fn test_detect_output_format() {
+        assert_eq!(detect_output_format("properties.csv"), OutputFormat::Csv);
+        assert_eq!(detect_output_format("properties"), OutputFormat::Csv);
+        assert_eq!(detect_output_format("export/properties.JSON"), OutputFormat::Json);
+        assert_eq!(detect_output_format("properties.geojson"), OutputFormat::GeoJson);
+        assert_eq!(detect_output_format("properties.db"), OutputFormat::Sqlite);
+        assert_eq!(detect_output_format("properties.sqlite"), OutputFormat::Sqlite);
+        assert_eq!(detect_output_format("properties.xlsx"), OutputFormat::Xlsx);
+        assert_eq!(detect_output_format("properties.kml"), OutputFormat::Kml);
+    }
+
+    #[test]
+    fn test_data_completeness_score() {
+        let mut sparse = property("sparse", ListingType::Sold, None);
+        assert_eq!(data_completeness_score(&sparse), 0.0);
+
+        sparse.date = Some(chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+        sparse.coordinates = Some(Coordinates::new(47.41, 9.74).unwrap());
+        sparse.features = vec!["balkon".to_string()];
+        assert_eq!(data_completeness_score(&sparse), 0.25);
+
+        // Saved files carry the current score, not the one that was loaded
+        let record = sparse.to_json().unwrap();
+        assert_eq!(record["completeness_score"], 0.25);
+    }
+
+    #[test]
+    fn test_compute_property_delta() {
+        let before = vec![
+            property("unchanged", ListingType::Available, Some(10)),
+            property("seen-again", ListingType::Available, Some(10)),
+            property("sold", ListingType::Available, Some(10)),
+            property("gone", ListingType::Available, Some(10)),
+        ];
+        let mut after = before[..3].to_vec();
+        after[1].last_seen = Some(chrono::Utc::now().naive_utc().date());
+        after[2].listing_type = ListingType::Sold;
+        after.push(property("new", ListingType::Available, Some(1)));
+
+        let delta = compute_property_delta(&before, &after);
+
+        let urls = |properties: &[Property]| properties.iter().map(|p| p.url.clone()).collect::<Vec<_>>();
+        assert_eq!(urls(&delta.added), ["new"]);
+        assert_eq!(delta.updated.len(), 1);
+        assert_eq!(delta.updated[0].0.listing_type, ListingType::Available);
+        assert_eq!(delta.updated[0].1.listing_type, ListingType::Sold);
+        assert_eq!(urls(&delta.removed), ["gone"]);
+        assert_eq!(urls(&delta.changed()), ["new", "sold"]);
+    }
 }
@@ -0,0 +1,48 @@
+use laendlefinder::laendleimmo_scraper::{extract_from_json_ld, parse_property_page};
+use laendlefinder::models::PropertyKind;
+use laendlefinder::utils::is_coordinate_in_austria;
+use scraper::Html;
+
+const URL: &str = "https://www.laendleimmo.at/immobilien/haus/einfamilienhaus/vorarlberg/feldkirch/1182405";
+const HOUSE_WITH_JSON_LD: &str = include_str!("../fixtures/laendleimmo_house_with_json_ld.html");
+const HOUSE_WITH_MALFORMED_JSON_LD: &str = include_str!("../fixtures/laendleimmo_house_malformed_json_ld.html");
+
+#[test]
+fn extracts_house_from_json_ld() {
+    let property = extract_from_json_ld(HOUSE_WITH_JSON_LD, URL).unwrap();
+
+    assert_eq!(property.url, URL);
+    assert_eq!(property.name, "Einfamilienhaus mit Garten in Feldkirch-Tosters");
+    assert_eq!(property.price.euros(), Some(789000.0));
+    assert_eq!(property.location.municipality, "Feldkirch");
+    assert_eq!(property.location.district.as_deref(), Some("Feldkirch"));
+    assert_eq!(property.address.as_deref(), Some("Alberweg 12, Feldkirch"));
+    assert_eq!(property.property_type.kind, PropertyKind::House);
+    assert_eq!(property.size_living.as_deref(), Some("145.50"));
+    assert_eq!(property.date.map(|d| d.to_string()).as_deref(), Some("2024-03-18"));
+    assert_eq!(property.agency_name.as_deref(), Some("Rhomberg Immobilien GmbH"));
+    assert_eq!(property.agency_phone.as_deref(), Some("+43552212345"));
+
+    // JSON-LD coordinates take precedence over the map loader
+    let coordinates = property.coordinates.expect("coordinates from JSON-LD");
+    assert_eq!((coordinates.lat, coordinates.lng), (47.2398, 9.5876));
+    assert!(is_coordinate_in_austria(coordinates.lat, coordinates.lng));
+}
+
+#[test]
+fn malformed_json_ld_falls_back_to_html() {
+    assert!(extract_from_json_ld(HOUSE_WITH_MALFORMED_JSON_LD, URL).is_err());
+
+    let document = Html::parse_document(HOUSE_WITH_MALFORMED_JSON_LD);
+    let property = parse_property_page(HOUSE_WITH_MALFORMED_JSON_LD, &document, URL).unwrap();
+
+    assert_eq!(property.name, "Einfamilienhaus mit Garten in Feldkirch-Tosters");
+    assert_eq!(property.price.euros(), Some(789000.0));
+    assert_eq!(property.location.municipality, "Feldkirch");
+    assert_eq!(property.property_type.kind, PropertyKind::House);
+    assert_eq!(property.size_living.as_deref(), Some("145.50"));
+    assert_eq!(property.agency_name.as_deref(), Some("Rhomberg Immobilien GmbH"));
+
+    let coordinates = property.coordinates.expect("coordinates from the map loader");
+    assert_eq!((coordinates.lat, coordinates.lng), (47.2401, 9.5880));
+}
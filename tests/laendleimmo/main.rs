@@ -0,0 +1 @@
+mod json_ld_extraction;
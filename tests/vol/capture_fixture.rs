@@ -0,0 +1,69 @@
+use scraper::{Html, Selector};
+use serde_json::{json, Value};
+
+/// Block types of the post the extraction reads, all others are dropped from the fixture
+const KEPT_BLOCKS: &[&str] = &["core/paragraph", "russmedia/grund-und-boden"];
+
+/// The `#newExternalPostDataNode` payload of a real article, trimmed to what the extraction reads
+fn trimmed_post_data(html: &str) -> Value {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("#newExternalPostDataNode, #externalPostDataNode").unwrap();
+    let script = document.select(&selector).next().expect("article without post data node");
+    let data: Value = serde_json::from_str(&script.inner_html()).expect("post data is JSON");
+
+    let post = &data["content"]["data"]["post"];
+    let blocks: Vec<&Value> = post["blocks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|block| block["ot"].as_str().is_some_and(|ot| KEPT_BLOCKS.contains(&ot)))
+        .collect();
+    json!({
+        "content": {
+            "type": data["content"]["type"],
+            "data": {
+                "post": {
+                    "id": post["id"],
+                    "slug": post["slug"],
+                    "title": post["title"],
+                    "date": post["date"],
+                    "categories": post["categories"],
+                    "blocks": blocks,
+                }
+            }
+        },
+        "meta": data["meta"],
+    })
+}
+
+/// Replace `tests/fixtures/vol_at_property.json` with the trimmed payload of a live article
+///
+/// Needs network access, run it with the URL of a sold-house article:
+/// `VOL_FIXTURE_URL=https://www.vol.at/... cargo test --test vol -- --ignored capture`
+#[test]
+#[ignore]
+fn capture_house_article_fixture() {
+    let url = std::env::var("VOL_FIXTURE_URL").expect("set VOL_FIXTURE_URL to a sold-house article");
+    let html = laendlefinder::utils::http_client_builder(&[])
+        .build()
+        .unwrap()
+        .get(&url)
+        .header("User-Agent", laendlefinder::utils::UserAgentRotator::new(Vec::new(), 0).next())
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .unwrap();
+
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/vol_at_property.json");
+    std::fs::write(path, serde_json::to_string_pretty(&trimmed_post_data(&html)).unwrap() + "\n").unwrap();
+}
+
+#[test]
+fn trimming_keeps_what_the_extraction_reads() {
+    let fixture: Value = serde_json::from_str(include_str!("../fixtures/vol_at_property.json")).unwrap();
+    let html = format!(
+        r#"<html><body><script id="newExternalPostDataNode" type="application/json">{}</script></body></html>"#,
+        fixture
+    );
+    assert_eq!(trimmed_post_data(&html), fixture);
+}
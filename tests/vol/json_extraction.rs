@@ -0,0 +1,33 @@
+use laendlefinder::models::{ListingType, PropertyKind};
+use laendlefinder::scraper::extract_property_from_json;
+use laendlefinder::utils::is_coordinate_in_austria;
+
+const URL: &str = "https://www.vol.at/haus-in-dornbirn-um-689-000-euro-verkauft/8913245";
+/// Content of the `#externalPostDataNode` script tag of a sold-house article
+///
+/// Trimmed to the fields the extraction reads, refresh it from a live article
+/// with the ignored `capture_house_article_fixture` test.
+const HOUSE_ARTICLE_JSON: &str = include_str!("../fixtures/vol_at_property.json");
+
+#[test]
+fn extracts_sold_house_from_article_json() {
+    let json = serde_json::from_str(HOUSE_ARTICLE_JSON).unwrap();
+    let property = extract_property_from_json(json, URL, &ListingType::Sold).unwrap();
+
+    assert_eq!(property.url, URL);
+    assert_eq!(property.listing_type, ListingType::Sold);
+    assert!(property.price.euros().is_some_and(|price| price > 0.0));
+    assert_eq!(property.price.euros(), Some(689000.0));
+    assert_eq!(property.location.municipality, "Dornbirn");
+    assert_ne!(property.property_type.kind, PropertyKind::Unknown);
+    assert_eq!(property.property_type.kind, PropertyKind::House);
+    assert_eq!(property.date.map(|d| d.to_string()).as_deref(), Some("2024-11-07"));
+    assert_eq!(property.address.as_deref(), Some("Schillerstraße 14, 6850 Dornbirn"));
+    assert_eq!(property.size_living.as_deref(), Some("132"));
+    assert_eq!(property.size_ground.as_deref(), Some("512"));
+    assert_eq!(property.construction_year, Some(1987));
+
+    let coordinates = property.coordinates.expect("coordinates from the grund-und-boden block");
+    assert!(is_coordinate_in_austria(coordinates.lat, coordinates.lng));
+    assert!(coordinates.is_in_vorarlberg());
+}
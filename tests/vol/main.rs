@@ -0,0 +1,2 @@
+mod json_extraction;
+mod capture_fixture;